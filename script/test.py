@@ -13,6 +13,15 @@ Test layout:
         ir/             # Test for the intermediate representation
             [test].rs   # Input
             [test].ir   # Expected IR
+            opt/        # Tests for the optimized IR (`-O`)
+                [test].rs   # Input
+                [test].ir   # Expected IR
+        cfg-dot/        # Tests for the control flow graph export
+            [test].rs   # Input
+            [test].dot  # Expected Graphviz output of the IR's CFG
+            asm/        # Tests for the assembly's CFG
+                [test].rs   # Input
+                [test].dot  # Expected Graphviz output with lifetime intervals
 
 `compile-fail` tests can tell the test runner which error they expect by
 using special comments:
@@ -230,7 +239,9 @@ def tests_run_pass():
             session.skip()
             continue
 
-        cresult = compile_file(test)
+        # Binaries can't be linked until registers are allocated, so only
+        # check that the program compiles all the way down to assembly
+        cresult = compile_file(test, ['--target', 'asm'])
 
         # Verify errors
         errors, stderr = parse_errors(cresult.output)
@@ -242,8 +253,9 @@ def tests_run_pass():
                                        '\n'.join(stderr), None))
 
 
-def tests_emit(target, ext, descr):
-    tests = [name for name in sorted(list((TEST_DIR / target).iterdir()))
+def tests_emit(target, ext, descr, directory=None, args=[]):
+    directory = directory or target
+    tests = [name for name in sorted(list((TEST_DIR / directory).iterdir()))
              if name.suffix == '.rs']
 
     for test in tests:
@@ -255,7 +267,7 @@ def tests_emit(target, ext, descr):
             continue
 
         # Get generated IR
-        cresult = compile_file(test, ['--target', target])
+        cresult = compile_file(test, ['--target', target] + args)
 
         if cresult.exit_code != 0:
             session.failure(FailedTest(test, test_name, None, None, cresult.output,
@@ -265,7 +277,7 @@ def tests_emit(target, ext, descr):
         generated = cresult.output.strip()
 
         # Get expceted IR
-        with (TEST_DIR / target / (test.stem + ext)).open() as f:
+        with (TEST_DIR / directory / (test.stem + ext)).open() as f:
             expected_ir = f.read().strip()
 
         if generated == expected_ir:
@@ -330,9 +342,19 @@ if __name__ == '__main__':
         'compile-fail': ('compile-fail tests', tests_compile_fail),
         'run-pass': ('run-pass tests', tests_run_pass),
         'ir': ('IR tests', lambda: tests_emit(target='ir', ext='.ir', descr='IR')),
-        'asm': ('ASM tests', lambda: tests_emit(target='asm', ext='.s', descr='ASM'))
+        'ir-opt': ('optimized IR tests', lambda: tests_emit(target='ir', ext='.ir', descr='IR',
+                                                            directory='ir/opt',
+                                                            args=['-O'])),
+        'asm': ('ASM tests', lambda: tests_emit(target='asm', ext='.s', descr='ASM')),
+        'cfg-dot': ('CFG tests', lambda: tests_emit(target='ir', ext='.dot', descr='CFG',
+                                                    directory='cfg-dot',
+                                                    args=['--emit', 'cfg-dot'])),
+        'cfg-dot-asm': ('ASM CFG tests', lambda: tests_emit(target='asm', ext='.dot', descr='CFG',
+                                                            directory='cfg-dot/asm',
+                                                            args=['--emit', 'cfg-dot']))
     }
-    default_set = ['internal', 'compile-fail', 'run-pass', 'ir', 'asm']
+    default_set = ['internal', 'compile-fail', 'run-pass', 'ir', 'ir-opt', 'asm', 'cfg-dot',
+                   'cfg-dot-asm']
 
     if len(sys.argv) == 2:
         suites = sys.argv[1].split(',')
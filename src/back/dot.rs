@@ -0,0 +1,52 @@
+//! Export the control flow graph of assembly functions to Graphviz
+
+use back::machine::asm::Assembly;
+use back::regalloc::lifetime_intervals::{build_intervals, format_intervals};
+use util::dot::Digraph;
+
+/// Render one digraph per function
+///
+/// Every block becomes a node showing its numbered assembly lines followed
+/// by the lifetime intervals of the virtual registers used in the block.
+/// The intervals refer to the line numbers.
+pub fn cfg_dot(asm: &Assembly) -> String {
+    let lifetimes = build_intervals(asm);
+    let mut out = String::new();
+
+    for func in asm.fns() {
+        let mut graph = Digraph::new(&func.name());
+
+        for block in func.code() {
+            let label = block.label().to_string();
+
+            let mut lines: Vec<String> = block
+                .code()
+                .enumerate()
+                .map(|(i, line)| format!("{:>3}: {}", i, line))
+                .collect();
+
+            let mut intervals: Vec<String> = lifetimes
+                .iter()
+                .filter(|&(&(block_label, _), _)| block_label == block.label())
+                .map(|(&(_, reg), intervals)| format!("{}: {}", reg, format_intervals(intervals)))
+                .collect();
+            intervals.sort();
+
+            if !intervals.is_empty() {
+                lines.push(String::new());
+                lines.push("lifetimes:".to_owned());
+                lines.extend(intervals);
+            }
+
+            graph.node(&label, lines);
+
+            for successor in block.successors() {
+                graph.edge(&label, &successor.to_string(), None);
+            }
+        }
+
+        out.push_str(&graph.to_string());
+    }
+
+    out
+}
@@ -5,14 +5,20 @@
 //!       before I can get there.
 
 // TODO: Instruction selection for calls and function epilogue
-// TODO: Add pow intrinsics
 // TODO: Add tests
-// TODO: Implement constant folding
-// TODO: How are phi nodes handeled?
 
-use back::machine::{asm, MachineRegister};
+use back::machine::{asm, cconv, MachineRegister, Word};
 use driver::interner::Ident;
+use front::intrinsics::Intrinsic;
 use middle::ir;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Switches with fewer cases are lowered to a chain of comparisons
+const JUMP_TABLE_MIN_CASES: usize = 4;
+
+/// The maximal number of entries in a jump table
+const JUMP_TABLE_MAX_SIZE: i128 = 256;
 
 pub use self::rulecomp::compile_rules;
 
@@ -22,6 +28,16 @@ mod rules; // TODO: Include rule compilation in build.rs
 struct InstructionSelector<'a> {
     ir: &'a ir::Program,
     code: asm::Assembly,
+    /// The number of trap messages emitted so far
+    traps: u32,
+    /// The number of jump tables emitted so far
+    jump_tables: u32,
+    /// The number of switches translated so far
+    switches: u32,
+    /// The number of `pow` instructions translated so far
+    pows: u32,
+    /// The contents of all string symbols
+    strings: HashMap<Ident, Ident>,
 }
 
 impl<'a> InstructionSelector<'a> {
@@ -29,12 +45,309 @@ impl<'a> InstructionSelector<'a> {
         InstructionSelector {
             ir,
             code: asm::Assembly::new(),
+            traps: 0,
+            jump_tables: 0,
+            switches: 0,
+            pows: 0,
+            strings: HashMap::new(),
+        }
+    }
+
+    /// Print a trap's message to stderr and exit with a non-zero code
+    ///
+    /// Like the intrinsics, this uses the Linux syscalls directly instead of
+    /// going through libc.
+    fn trans_trap(&mut self, msg: Ident, code: &mut asm::Block) {
+        // Store the message in the data section
+        self.traps += 1;
+        let label = Ident::from_str(&format!("trap_msg{}", self.traps));
+        let msg = format!("runtime error: {}\n", msg);
+        self.code.emit_data(format!("{}:", label));
+        self.code
+            .emit_data(format!(".ascii \"{}\"", asm::escape_str(&msg)));
+
+        let reg = |r| asm::Argument::Register(asm::Register::Machine(r));
+        let mut emit = |mnemonic, args| {
+            code.emit_instruction(asm::Instruction::new(Ident::from_str(mnemonic), args))
+        };
+
+        // write(2, msg, len)
+        emit(
+            "mov",
+            vec![reg(MachineRegister::RAX), asm::Argument::Immediate(1)],
+        );
+        emit(
+            "mov",
+            vec![reg(MachineRegister::RDI), asm::Argument::Immediate(2)],
+        );
+        emit(
+            "lea",
+            vec![reg(MachineRegister::RSI), asm::Argument::Address(label)],
+        );
+        emit(
+            "mov",
+            vec![
+                reg(MachineRegister::RDX),
+                asm::Argument::Immediate(msg.len() as Word),
+            ],
+        );
+        emit("syscall", vec![]);
+
+        // exit(1)
+        emit(
+            "mov",
+            vec![reg(MachineRegister::RAX), asm::Argument::Immediate(60)],
+        );
+        emit(
+            "mov",
+            vec![reg(MachineRegister::RDI), asm::Argument::Immediate(1)],
+        );
+        emit("syscall", vec![]);
+    }
+
+    /// Translate a call to an intrinsic
+    ///
+    /// Like traps, intrinsics use the Linux syscalls directly.
+    fn trans_intrinsic(&mut self, intrinsic: Intrinsic, args: &[ir::Value], code: &mut asm::Block) {
+        let reg = |r| asm::Argument::Register(asm::Register::Machine(r));
+        let mut emit = |mnemonic, args| {
+            code.emit_instruction(asm::Instruction::new(Ident::from_str(mnemonic), args))
+        };
+
+        match intrinsic {
+            Intrinsic::PrintStr => {
+                let label = match args[0] {
+                    ir::Value::Static(label) => label,
+                    arg => panic!("print_str called with {}", arg),
+                };
+                let len = self.strings[&label].len();
+
+                // write(1, str, len)
+                emit(
+                    "mov",
+                    vec![reg(MachineRegister::RAX), asm::Argument::Immediate(1)],
+                );
+                emit(
+                    "mov",
+                    vec![reg(MachineRegister::RDI), asm::Argument::Immediate(1)],
+                );
+                emit(
+                    "lea",
+                    vec![reg(MachineRegister::RSI), asm::Argument::Address(label)],
+                );
+                emit(
+                    "mov",
+                    vec![
+                        reg(MachineRegister::RDX),
+                        asm::Argument::Immediate(len as Word),
+                    ],
+                );
+                emit("syscall", vec![]);
+            }
+        }
+    }
+
+    /// Raise a value to a power
+    ///
+    /// There's no instruction for this, so we square and multiply in a loop.
+    /// Like the interpreter, only the lower 32 bits of the exponent are used
+    /// and the result wraps around.
+    fn trans_pow(
+        &mut self,
+        lhs: ir::Value,
+        rhs: ir::Value,
+        dst: ir::Register,
+        code: &mut asm::Block,
+    ) {
+        // Every pow gets its own registers and labels
+        self.pows += 1;
+        let n = self.pows;
+        let name = |name| Ident::from_str(&format!("{}{}", name, n));
+        let vreg = |name| asm::Argument::Register(asm::Register::Virtual(name));
+        let emit = |code: &mut asm::Block, mnemonic, args| {
+            code.emit_instruction(asm::Instruction::new(Ident::from_str(mnemonic), args))
+        };
+
+        let operand = |value: ir::Value| match value {
+            ir::Value::Register(ir::Register::Local(reg)) => vreg(reg),
+            ir::Value::Immediate(imm) => asm::Argument::Immediate(imm.val()),
+            _ => panic!("cannot raise {} to a power", value),
+        };
+
+        let result = vreg(name("pow_result"));
+        let base = vreg(name("pow_base"));
+        let exp = vreg(name("pow_exp"));
+        let label_loop = name("pow_loop");
+        let label_square = name("pow_square");
+        let label_done = name("pow_done");
+
+        emit(code, "mov", vec![result, asm::Argument::Immediate(1)]);
+        emit(code, "mov", vec![base, operand(lhs)]);
+        emit(code, "mov", vec![exp, operand(rhs)]);
+        emit(code, "shl", vec![exp, asm::Argument::Immediate(32)]);
+        emit(code, "shr", vec![exp, asm::Argument::Immediate(32)]);
+
+        // Multiply the result with the base's powers that belong to the
+        // exponent's set bits
+        code.emit_directive(format!("{}:", label_loop));
+        emit(code, "test", vec![exp, exp]);
+        emit(code, "jz", vec![asm::Argument::Label(label_done)]);
+        emit(code, "test", vec![exp, asm::Argument::Immediate(1)]);
+        emit(code, "jz", vec![asm::Argument::Label(label_square)]);
+        emit(code, "imul", vec![result, base]);
+        code.emit_directive(format!("{}:", label_square));
+        emit(code, "imul", vec![base, base]);
+        emit(code, "shr", vec![exp, asm::Argument::Immediate(1)]);
+        emit(code, "jmp", vec![asm::Argument::Label(label_loop)]);
+        code.emit_directive(format!("{}:", label_done));
+        emit(code, "mov", vec![operand(ir::Value::Register(dst)), result]);
+    }
+
+    /// Jump to the destination of a switch's matching case
+    ///
+    /// If the cases cover most of the values between the smallest and the
+    /// largest one, we look the destination up in a jump table. Otherwise we
+    /// compare the value with one case after the other.
+    fn trans_switch(
+        &mut self,
+        value: ir::Value,
+        cases: &[(ir::Immediate, ir::Immediate, ir::Label)],
+        default: ir::Label,
+        code: &mut asm::Block,
+    ) {
+        // Every switch gets its own registers
+        self.switches += 1;
+        let n = self.switches;
+        let vreg = |name| asm::Register::Virtual(Ident::from_str(&format!("{}{}", name, n)));
+
+        let tmp = vreg("switch");
+        let imm = vreg("switch_imm");
+        let label = |label: ir::Label| asm::Argument::Label(label.ident());
+        let emit = |code: &mut asm::Block, mnemonic, args| {
+            code.emit_instruction(asm::Instruction::new(Ident::from_str(mnemonic), args))
+        };
+
+        let value = match value {
+            ir::Value::Register(ir::Register::Local(reg)) => {
+                asm::Argument::Register(asm::Register::Virtual(reg))
+            }
+            ir::Value::Immediate(imm) => {
+                // `cmp` can't compare two immediates
+                let reg = asm::Argument::Register(vreg("switch_value"));
+                emit(code, "mov", vec![reg, asm::Argument::Immediate(imm.val())]);
+                reg
+            }
+            _ => panic!("cannot switch on {}", value),
+        };
+
+        // `cmp` and `sub` only take sign-extended 32-bit immediates, larger
+        // case values have to be loaded into a register first
+        let operand = |code: &mut asm::Block, val: Word| {
+            if i32::try_from(val).is_ok() {
+                asm::Argument::Immediate(val)
+            } else {
+                let reg = asm::Argument::Register(imm);
+                emit(code, "mov", vec![reg, asm::Argument::Immediate(val)]);
+                reg
+            }
+        };
+
+        // The number of values the cases cover and the range they lie in
+        let covered: i128 = cases
+            .iter()
+            .map(|&(lo, hi, _)| i128::from(hi.val()) - i128::from(lo.val()) + 1)
+            .sum();
+        let min = cases.iter().map(|case| case.0.val()).min().unwrap_or(0);
+        let max = cases.iter().map(|case| case.1.val()).max().unwrap_or(0);
+        let size = i128::from(max) - i128::from(min) + 1;
+
+        if cases.len() >= JUMP_TABLE_MIN_CASES && size <= JUMP_TABLE_MAX_SIZE && size <= 2 * covered
+        {
+            // Fill the table, the first case containing a value wins
+            let entries: Vec<_> = (min..=max)
+                .map(|val| {
+                    cases
+                        .iter()
+                        .find(|&&(lo, hi, _)| lo.val() <= val && val <= hi.val())
+                        .map_or(default, |case| case.2)
+                })
+                .collect();
+
+            self.jump_tables += 1;
+            let table = Ident::from_str(&format!("jump_table{}", self.jump_tables));
+            self.code.emit_data(format!("{}:", table));
+            for entry in entries {
+                self.code
+                    .emit_data(format!(".quad {}", asm::label_name(entry.ident())));
+            }
+
+            // Values outside of the table (including those below `min`,
+            // which wrap around) go to the default case
+            emit(code, "mov", vec![asm::Argument::Register(tmp), value]);
+            if min != 0 {
+                let min = operand(code, min);
+                emit(code, "sub", vec![asm::Argument::Register(tmp), min]);
+            }
+            emit(
+                code,
+                "cmp",
+                vec![
+                    asm::Argument::Register(tmp),
+                    asm::Argument::Immediate(max.wrapping_sub(min)),
+                ],
+            );
+            emit(code, "ja", vec![label(default)]);
+
+            let table_reg = vreg("table");
+            emit(
+                code,
+                "lea",
+                vec![
+                    asm::Argument::Register(table_reg),
+                    asm::Argument::Address(table),
+                ],
+            );
+            emit(
+                code,
+                "jmp",
+                vec![asm::Argument::Indirect {
+                    size: Some(asm::OperandSize::QWord),
+                    base: Some(table_reg),
+                    index: Some((tmp, 8)),
+                    disp: None,
+                }],
+            );
+        } else {
+            for &(lo, hi, dest) in cases {
+                if lo == hi {
+                    let lo = operand(code, lo.val());
+                    emit(code, "cmp", vec![value, lo]);
+                    emit(code, "je", vec![label(dest)]);
+                } else {
+                    // lo <= value <= hi  <=>  (value - lo) <= (hi - lo) as unsigned
+                    emit(code, "mov", vec![asm::Argument::Register(tmp), value]);
+                    let offset = operand(code, lo.val());
+                    emit(code, "sub", vec![asm::Argument::Register(tmp), offset]);
+                    let span = operand(code, hi.val().wrapping_sub(lo.val()));
+                    emit(code, "cmp", vec![asm::Argument::Register(tmp), span]);
+                    emit(code, "jbe", vec![label(dest)]);
+                }
+            }
+
+            emit(code, "jmp", vec![label(default)]);
         }
     }
 
     fn trans_global(&mut self, name: Ident, value: ir::Immediate) {
         self.code.emit_data(format!("{}:", name));
-        self.code.emit_data(format!(".long {}", value));
+        self.code.emit_data(format!(".quad {}", value));
+    }
+
+    fn trans_str(&mut self, name: Ident, value: Ident) {
+        self.strings.insert(name, value);
+        self.code.emit_rodata(format!("{}:", name));
+        self.code
+            .emit_rodata(format!(".asciz \"{}\"", asm::escape_str(&value)));
     }
 
     fn trans_fn(&mut self, name: Ident, body: &[ir::Block], args: &[Ident]) {
@@ -46,12 +359,12 @@ impl<'a> InstructionSelector<'a> {
             let mut asm_block = asm::Block::new(ir_block.label.ident());
 
             if first_block {
-                // Determine stack usage by the number of alloca calls
+                // Determine stack usage by the number of allocated slots
                 let mut stack_usage = 0;
 
                 for inst in &ir_block.inst {
-                    if let ir::Instruction::Alloca { .. } = *inst {
-                        stack_usage += 1;
+                    if let ir::Instruction::Alloca { size, .. } = *inst {
+                        stack_usage += Word::from(size);
                     } else {
                         break;
                     }
@@ -80,48 +393,102 @@ impl<'a> InstructionSelector<'a> {
                         asm::Argument::Immediate(8 * stack_usage),
                     ], // FIXME: Use this function's stack usage here
                 ));
+                cconv::translate_args(&mut asm_block, name, args);
 
                 // NOT VALID FOR NOW: (Don't emit the label of the first block (usually "entry-block"))
                 first_block = false;
             }
 
-            asm_block.emit_directive(format!("{}:", ir_block.label));
+            asm_block.emit_directive(format!("{}:", asm::label_name(ir_block.label.ident())));
 
             // Pass Phi instructionos
             asm_block.set_phis(ir_block.phis.to_vec());
 
+            // `main` is called by the C startup code, which uses its return
+            // value as the exit code. Like in the interpreter, a `main` without
+            // a return value exits with 0.
+            let exit_success = ir::ControlFlowInstruction::Return {
+                value: Some(ir::Value::Immediate(ir::Immediate(0))),
+            };
+            let last = match ir_block.last {
+                ir::ControlFlowInstruction::Return { value: None }
+                    if name == Ident::from_str("main") =>
+                {
+                    &exit_success
+                }
+                ref last => last,
+            };
+
             // Translate instructions
             let instructions: Vec<_> = ir_block.inst.iter().collect();
             let mut idx = 0;
             let mut processed_last = false;
 
             while idx < instructions.len() {
+                if let ir::Instruction::Call { name, ref args, .. } = *instructions[idx] {
+                    if let Some(intrinsic) = Intrinsic::lookup(name) {
+                        self.trans_intrinsic(intrinsic, args, &mut asm_block);
+                        idx += 1;
+                        continue;
+                    }
+                }
+
+                if let ir::Instruction::BinOp {
+                    op: ir::InfixOp::Pow,
+                    lhs,
+                    rhs,
+                    dst,
+                } = *instructions[idx]
+                {
+                    self.trans_pow(lhs, rhs, dst, &mut asm_block);
+                    idx += 1;
+                    continue;
+                }
+
                 let (count, _processed_last) =
-                    rules::trans_instr(&instructions[idx..], &ir_block.last, &mut asm_block);
+                    rules::trans_instr(&instructions[idx..], last, &mut asm_block);
                 idx += count;
                 processed_last = _processed_last;
             }
 
-            if !processed_last {
-                rules::trans_instr(&[], &ir_block.last, &mut asm_block);
+            if let ir::ControlFlowInstruction::Trap { msg } = *last {
+                self.trans_trap(msg, &mut asm_block);
+            } else if let ir::ControlFlowInstruction::Switch {
+                value,
+                ref cases,
+                default,
+            } = *last
+            {
+                self.trans_switch(value, cases, default, &mut asm_block);
+            } else if !processed_last {
+                rules::trans_instr(&[], last, &mut asm_block);
             }
 
+            legalize_immediates(&mut asm_block);
+
             // Add sucessors
-            asm_block.add_successors(&ir_block.last.successors());
+            asm_block.add_successors(&last.successors());
 
             code.push(asm_block);
         }
 
+        lower_phis(&mut code);
+
         self.code.emit_fn(name, args.to_vec(), code);
 
         // TODO: Where will the epilogue/stack cleanup codegen go?
     }
 
     fn translate(mut self) -> asm::Assembly {
-        // Translate all globals
+        // Translate all globals and strings
         for symbol in self.ir {
-            if let ir::Symbol::Global { name, value } = *symbol {
-                self.trans_global(name, value);
+            match *symbol {
+                ir::Symbol::Global { name, value } => self.trans_global(name, value),
+                ir::Symbol::Str { name, value } => self.trans_str(name, value),
+                ir::Symbol::Function { .. } => {}
+                // Defined elsewhere and resolved by the linker, so there's
+                // no `.globl` and no code to emit
+                ir::Symbol::Extern { .. } => {}
             }
         }
 
@@ -141,6 +508,96 @@ impl<'a> InstructionSelector<'a> {
     }
 }
 
+/// Lower phis to moves at the end of their predecessors
+///
+/// The moves are placed right before the jumps that leave the predecessor
+/// (`mov` doesn't change the flags a conditional jump might test). The phis
+/// stay attached to their blocks for the lifetime interval analysis.
+fn lower_phis(code: &mut [asm::Block]) {
+    let operand = |value: ir::Value| match value {
+        ir::Value::Register(ir::Register::Local(reg)) => {
+            asm::Argument::Register(asm::Register::Virtual(reg))
+        }
+        ir::Value::Register(ir::Register::Stack(slot)) => {
+            asm::Argument::StackSlot(asm::Register::Virtual(slot))
+        }
+        ir::Value::Immediate(imm) => asm::Argument::Immediate(imm.val()),
+        _ => panic!("cannot merge {} in a phi", value),
+    };
+
+    let moves: Vec<(Ident, asm::Instruction)> = code
+        .iter()
+        .flat_map(|block| block.phis())
+        .flat_map(|phi| {
+            phi.srcs.iter().map(move |&(src, pred)| {
+                let dst = operand(ir::Value::Register(phi.dst));
+                let mov = asm::Instruction::new(Ident::from_str("mov"), vec![dst, operand(src)]);
+                (pred.ident(), mov)
+            })
+        })
+        .collect();
+
+    for (pred, mov) in moves {
+        let block = code
+            .iter_mut()
+            .find(|block| block.label() == pred)
+            .unwrap_or_else(|| panic!("phi predecessor {} doesn't exist", pred));
+
+        let jumps = block
+            .code()
+            .rev()
+            .take_while(|line| match **line {
+                asm::AssemblyLine::Instruction(ref inst) => inst.mnemonic().starts_with('j'),
+                asm::AssemblyLine::Directive(..) => false,
+            })
+            .count();
+        let idx = block.len() - jumps;
+        block.insert_instruction(idx, mov);
+    }
+}
+
+/// Move immediates that don't fit into 32 bits into a register first
+///
+/// Apart from `mov reg, imm`, x86-64 instructions only take sign-extended
+/// 32-bit immediates.
+fn legalize_immediates(code: &mut asm::Block) {
+    let tmp = asm::Register::Virtual(Ident::from_str("imm"));
+    let mut idx = 0;
+
+    while idx < code.len() {
+        let wide = match code.code_mut().nth(idx) {
+            Some(&mut asm::AssemblyLine::Instruction(ref mut inst)) => {
+                let is_mov_to_reg = &*inst.mnemonic() == "mov"
+                    && matches!(inst.args[0], asm::Argument::Register(..));
+
+                inst.args.iter_mut().find_map(|arg| match *arg {
+                    asm::Argument::Immediate(val)
+                        if !is_mov_to_reg && i32::try_from(val).is_err() =>
+                    {
+                        *arg = asm::Argument::Register(tmp);
+                        Some(val)
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(val) = wide {
+            code.insert_instruction(
+                idx,
+                asm::Instruction::new(
+                    Ident::from_str("mov"),
+                    vec![asm::Argument::Register(tmp), asm::Argument::Immediate(val)],
+                ),
+            );
+            idx += 1;
+        }
+
+        idx += 1;
+    }
+}
+
 pub fn select_instructions(ir: &ir::Program) -> asm::Assembly {
     let is = InstructionSelector::new(ir);
     is.translate()
@@ -26,18 +26,41 @@ pub enum IrPattern {
     Mod(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     Shl(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     Shr(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    UDiv(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    UMod(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    UShr(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     And(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     Or(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     Xor(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    FAdd(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    FSub(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    FMul(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    FDiv(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     Neg(Node<IrRegister>, Node<IrArg>),
     Not(Node<IrRegister>, Node<IrArg>),
+    FNeg(Node<IrRegister>, Node<IrArg>),
+    SiToFp(Node<IrRegister>, Node<IrArg>),
+    FpToSi(Node<IrRegister>, Node<IrArg>),
     CmpLt(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     CmpLe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     CmpEq(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     CmpNe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     CmpGe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     CmpGt(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpULt(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpULe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpUGe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpUGt(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpFLt(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpFLe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpFEq(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpFNe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpFGe(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    CmpFGt(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
+    /// Sign or zero extension: signed?, number of bits
+    Extend(Node<IrRegister>, Node<IrArg>, bool, u32),
     Alloca(Node<IrRegister>),
+    Index(Node<IrRegister>, Node<IrArg>, Node<IrArg>),
     Load(Node<IrRegister>, Node<IrArg>),
     Store(Node<IrArg>, Node<IrArg>),
     Call(Node<IrRegister>, Node<Ident>, Node<Ident>),
@@ -55,12 +78,16 @@ pub enum IrArg {
     Register(IrRegister),
     Literal(Ident),
     Static(Ident),
+    Indirect(IrRegister),
 }
 
 impl IrArg {
     pub fn get_name(&self) -> Ident {
         match *self {
-            IrArg::Register(IrRegister(id, ..)) | IrArg::Literal(id) | IrArg::Static(id) => id,
+            IrArg::Register(IrRegister(id, ..))
+            | IrArg::Indirect(IrRegister(id, ..))
+            | IrArg::Literal(id)
+            | IrArg::Static(id) => id,
         }
     }
 }
@@ -97,6 +124,8 @@ pub enum AsmArg {
     IrArg(Node<Ident>),
     Literal(Node<Ident>),
     Label(Node<Ident>),
+    /// The lower part of a register (`byte $src` -> `al`)
+    SubRegister(Box<AsmArg>, OperandSize),
     Indirect {
         size: Option<OperandSize>,
         base: Option<Box<AsmArg>>,
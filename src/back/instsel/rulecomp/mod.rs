@@ -147,16 +147,39 @@ fn update_pattern_types(pattern: &IrPattern, map: &mut HashMap<Ident, IrArg>) {
         | IrPattern::Mod(ref dest, ref lhs, ref rhs)
         | IrPattern::Shl(ref dest, ref lhs, ref rhs)
         | IrPattern::Shr(ref dest, ref lhs, ref rhs)
+        | IrPattern::UDiv(ref dest, ref lhs, ref rhs)
+        | IrPattern::UMod(ref dest, ref lhs, ref rhs)
+        | IrPattern::UShr(ref dest, ref lhs, ref rhs)
         | IrPattern::And(ref dest, ref lhs, ref rhs)
         | IrPattern::Or(ref dest, ref lhs, ref rhs)
         | IrPattern::Xor(ref dest, ref lhs, ref rhs)
+        | IrPattern::FAdd(ref dest, ref lhs, ref rhs)
+        | IrPattern::FSub(ref dest, ref lhs, ref rhs)
+        | IrPattern::FMul(ref dest, ref lhs, ref rhs)
+        | IrPattern::FDiv(ref dest, ref lhs, ref rhs)
         | IrPattern::CmpLt(ref dest, ref lhs, ref rhs)
         | IrPattern::CmpLe(ref dest, ref lhs, ref rhs)
         | IrPattern::CmpEq(ref dest, ref lhs, ref rhs)
         | IrPattern::CmpNe(ref dest, ref lhs, ref rhs)
         | IrPattern::CmpGe(ref dest, ref lhs, ref rhs)
-        | IrPattern::CmpGt(ref dest, ref lhs, ref rhs) => binop!(map, dest, lhs, rhs),
-        IrPattern::Neg(ref dest, ref arg) | IrPattern::Not(ref dest, ref arg) => {
+        | IrPattern::CmpGt(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpULt(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpULe(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpUGe(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpUGt(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpFLt(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpFLe(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpFEq(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpFNe(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpFGe(ref dest, ref lhs, ref rhs)
+        | IrPattern::CmpFGt(ref dest, ref lhs, ref rhs)
+        | IrPattern::Index(ref dest, ref lhs, ref rhs) => binop!(map, dest, lhs, rhs),
+        IrPattern::Neg(ref dest, ref arg)
+        | IrPattern::Not(ref dest, ref arg)
+        | IrPattern::FNeg(ref dest, ref arg)
+        | IrPattern::SiToFp(ref dest, ref arg)
+        | IrPattern::FpToSi(ref dest, ref arg)
+        | IrPattern::Extend(ref dest, ref arg, _, _) => {
             map.insert(dest.0, IrArg::Register(IrRegister(dest.0, dest.1)));
             map.insert(arg.get_name(), (**arg).clone());
         }
@@ -266,6 +289,24 @@ fn translate_ir_pattern(ir_pattern: &IrPattern) -> String {
                     translate_ir_arg(arg2),
                     translate_ir_register(dest))
         }
+        IrPattern::UDiv(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::UDiv, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::UMod(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::UMod, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::UShr(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::UShr, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
         IrPattern::And(ref dest, ref arg1, ref arg2) => {
             format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::And, lhs: {}, rhs: {}, dst: {} }})",
                     translate_ir_arg(arg1),
@@ -284,6 +325,30 @@ fn translate_ir_pattern(ir_pattern: &IrPattern) -> String {
                     translate_ir_arg(arg2),
                     translate_ir_register(dest))
         }
+        IrPattern::FAdd(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::FAdd, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::FSub(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::FSub, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::FMul(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::FMul, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::FDiv(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::BinOp {{ op: ir::InfixOp::FDiv, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
         IrPattern::Neg(ref dest, ref arg) => {
             format!("IrLine::Instruction(&ir::Instruction::UnOp {{ op: ir::PrefixOp::Neg, item: {}, dst: {} }})",
                     translate_ir_arg(arg),
@@ -294,6 +359,21 @@ fn translate_ir_pattern(ir_pattern: &IrPattern) -> String {
                     translate_ir_arg(arg),
                     translate_ir_register(dest))
         }
+        IrPattern::FNeg(ref dest, ref arg) => {
+            format!("IrLine::Instruction(&ir::Instruction::UnOp {{ op: ir::PrefixOp::FNeg, item: {}, dst: {} }})",
+                    translate_ir_arg(arg),
+                    translate_ir_register(dest))
+        }
+        IrPattern::SiToFp(ref dest, ref arg) => {
+            format!("IrLine::Instruction(&ir::Instruction::UnOp {{ op: ir::PrefixOp::IntToFloat, item: {}, dst: {} }})",
+                    translate_ir_arg(arg),
+                    translate_ir_register(dest))
+        }
+        IrPattern::FpToSi(ref dest, ref arg) => {
+            format!("IrLine::Instruction(&ir::Instruction::UnOp {{ op: ir::PrefixOp::FloatToInt, item: {}, dst: {} }})",
+                    translate_ir_arg(arg),
+                    translate_ir_register(dest))
+        }
         IrPattern::CmpLt(ref dest, ref arg1, ref arg2) => {
             format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::Lt, lhs: {}, rhs: {}, dst: {} }})",
                     translate_ir_arg(arg1),
@@ -330,9 +410,86 @@ fn translate_ir_pattern(ir_pattern: &IrPattern) -> String {
                     translate_ir_arg(arg2),
                     translate_ir_register(dest))
         }
+        IrPattern::CmpULt(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::ULt, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpULe(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::ULe, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpUGe(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::UGe, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpUGt(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::UGt, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpFLt(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::FLt, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpFLe(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::FLe, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpFEq(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::FEq, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpFNe(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::FNe, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpFGe(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::FGe, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::CmpFGt(ref dest, ref arg1, ref arg2) => {
+            format!("IrLine::Instruction(&ir::Instruction::Cmp {{ cmp: ir::CmpOp::FGt, lhs: {}, rhs: {}, dst: {} }})",
+                    translate_ir_arg(arg1),
+                    translate_ir_arg(arg2),
+                    translate_ir_register(dest))
+        }
+        IrPattern::Extend(ref dest, ref arg, signed, bits) => {
+            format!(
+                "IrLine::Instruction(&ir::Instruction::Extend {{ signed: {}, bits: {}, src: {}, dst: {} }})",
+                signed,
+                bits,
+                translate_ir_arg(arg),
+                translate_ir_register(dest)
+            )
+        }
         IrPattern::Alloca(ref dest) => {
             format!(
-                "IrLine::Instruction(&ir::Instruction::Alloca {{ dst: {} }})",
+                "IrLine::Instruction(&ir::Instruction::Alloca {{ dst: {}, .. }})",
+                translate_ir_register(dest)
+            )
+        }
+        IrPattern::Index(ref dest, ref base, ref index) => {
+            format!(
+                "IrLine::Instruction(&ir::Instruction::Index {{ base: {}, index: {}, dst: {} }})",
+                translate_ir_arg(base),
+                translate_ir_arg(index),
                 translate_ir_register(dest)
             )
         }
@@ -397,6 +554,9 @@ fn translate_ir_arg(arg: &IrArg) -> String {
         }
         IrArg::Literal(lit) => format!("ir::Value::Immediate(ir::Immediate({}))", lit),
         IrArg::Static(lit) => format!("ir::Value::Static({})", lit),
+        IrArg::Indirect(IrRegister(reg, _)) => {
+            format!("ir::Value::Indirect(ir::Register::Local({}))", reg)
+        }
     }
 }
 
@@ -474,9 +634,19 @@ fn translate_asm_arg(arg: &AsmArg, types: &HashMap<Ident, IrArg>) -> String {
             }
             IrArg::Literal(..) => format!("asm::Argument::Immediate(machine::Word::from({}))", arg),
             IrArg::Static(..) => format!("asm::Argument::Address({})", arg),
+            IrArg::Indirect(..) => format!(
+                "asm::Argument::Indirect {{ size: Some(asm::OperandSize::QWord), \
+                 base: Some(asm::Register::Virtual({})), index: None, disp: None }}",
+                arg
+            ),
         },
         AsmArg::Literal(ref lit) => format!("asm::Argument::Immediate({})", lit),
         AsmArg::Label(ref target) => format!("asm::Argument::Label({})", target),
+        AsmArg::SubRegister(ref reg, size) => format!(
+            "asm::Argument::SubRegister({}, asm::OperandSize::{:?})",
+            translate_asm_arg_register(reg),
+            size
+        ),
         AsmArg::Indirect {
             ref size,
             ref base,
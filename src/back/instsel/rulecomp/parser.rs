@@ -165,9 +165,16 @@ impl<'a> Parser<'a> {
                 Token::Keyword(Keyword::Mod) => binop!(dst, IrPattern::Mod),
                 Token::Keyword(Keyword::Shl) => binop!(dst, IrPattern::Shl),
                 Token::Keyword(Keyword::Shr) => binop!(dst, IrPattern::Shr),
+                Token::Keyword(Keyword::UDiv) => binop!(dst, IrPattern::UDiv),
+                Token::Keyword(Keyword::UMod) => binop!(dst, IrPattern::UMod),
+                Token::Keyword(Keyword::UShr) => binop!(dst, IrPattern::UShr),
                 Token::Keyword(Keyword::And) => binop!(dst, IrPattern::And),
                 Token::Keyword(Keyword::Or) => binop!(dst, IrPattern::Or),
                 Token::Keyword(Keyword::Xor) => binop!(dst, IrPattern::Xor),
+                Token::Keyword(Keyword::FAdd) => binop!(dst, IrPattern::FAdd),
+                Token::Keyword(Keyword::FSub) => binop!(dst, IrPattern::FSub),
+                Token::Keyword(Keyword::FMul) => binop!(dst, IrPattern::FMul),
+                Token::Keyword(Keyword::FDiv) => binop!(dst, IrPattern::FDiv),
                 Token::Keyword(Keyword::Neg) => {
                     self.bump();
                     IrPattern::Neg(dst, self.parse_ir_arg())
@@ -176,6 +183,18 @@ impl<'a> Parser<'a> {
                     self.bump();
                     IrPattern::Not(dst, self.parse_ir_arg())
                 }
+                Token::Keyword(Keyword::FNeg) => {
+                    self.bump();
+                    IrPattern::FNeg(dst, self.parse_ir_arg())
+                }
+                Token::Keyword(Keyword::SiToFp) => {
+                    self.bump();
+                    IrPattern::SiToFp(dst, self.parse_ir_arg())
+                }
+                Token::Keyword(Keyword::FpToSi) => {
+                    self.bump();
+                    IrPattern::FpToSi(dst, self.parse_ir_arg())
+                }
                 Token::Keyword(Keyword::Cmp) => {
                     self.bump();
 
@@ -186,13 +205,40 @@ impl<'a> Parser<'a> {
                         Token::Keyword(Keyword::Ne) => binop!(dst, IrPattern::CmpNe),
                         Token::Keyword(Keyword::Ge) => binop!(dst, IrPattern::CmpGe),
                         Token::Keyword(Keyword::Gt) => binop!(dst, IrPattern::CmpGt),
+                        Token::Keyword(Keyword::ULt) => binop!(dst, IrPattern::CmpULt),
+                        Token::Keyword(Keyword::ULe) => binop!(dst, IrPattern::CmpULe),
+                        Token::Keyword(Keyword::UGe) => binop!(dst, IrPattern::CmpUGe),
+                        Token::Keyword(Keyword::UGt) => binop!(dst, IrPattern::CmpUGt),
+                        Token::Keyword(Keyword::FLt) => binop!(dst, IrPattern::CmpFLt),
+                        Token::Keyword(Keyword::FLe) => binop!(dst, IrPattern::CmpFLe),
+                        Token::Keyword(Keyword::FEq) => binop!(dst, IrPattern::CmpFEq),
+                        Token::Keyword(Keyword::FNe) => binop!(dst, IrPattern::CmpFNe),
+                        Token::Keyword(Keyword::FGe) => binop!(dst, IrPattern::CmpFGe),
+                        Token::Keyword(Keyword::FGt) => binop!(dst, IrPattern::CmpFGt),
                         _ => self.fatal(format!("Invalid comparison: {}", self.token)),
                     }
                 }
+                Token::Keyword(Keyword::Zext8) => {
+                    self.bump();
+                    IrPattern::Extend(dst, self.parse_ir_arg(), false, 8)
+                }
+                Token::Keyword(Keyword::Sext8) => {
+                    self.bump();
+                    IrPattern::Extend(dst, self.parse_ir_arg(), true, 8)
+                }
+                Token::Keyword(Keyword::Zext32) => {
+                    self.bump();
+                    IrPattern::Extend(dst, self.parse_ir_arg(), false, 32)
+                }
+                Token::Keyword(Keyword::Sext32) => {
+                    self.bump();
+                    IrPattern::Extend(dst, self.parse_ir_arg(), true, 32)
+                }
                 Token::Keyword(Keyword::Alloca) => {
                     self.bump();
                     IrPattern::Alloca(dst)
                 }
+                Token::Keyword(Keyword::Index) => binop!(dst, IrPattern::Index),
                 Token::Keyword(Keyword::Load) => {
                     self.bump();
                     let val = self.parse_ir_arg();
@@ -318,7 +364,14 @@ impl<'a> Parser<'a> {
 
                 IrArg::Static(*ident)
             }
-            _ => self.unexpected_token(Some("one of '%' | '0'")),
+            Token::LBracket => {
+                self.bump();
+                let reg = self.parse_ir_register();
+                self.expect(Token::RBracket);
+
+                IrArg::Indirect(*reg)
+            }
+            _ => self.unexpected_token(Some("one of '%' | '0' | '@' | '['")),
         };
 
         Node::new(arg, lo + self.span)
@@ -393,23 +446,19 @@ impl<'a> Parser<'a> {
             Token::LBracket => self.parse_asm_memory_operand(None),
             Token::Keyword(Keyword::Byte) => {
                 self.bump();
-                self.expect(Token::Keyword(Keyword::Ptr));
-                self.parse_asm_memory_operand(Some(OperandSize::Byte))
+                self.parse_asm_sized_operand(OperandSize::Byte)
             }
             Token::Keyword(Keyword::Word) => {
                 self.bump();
-                self.expect(Token::Keyword(Keyword::Ptr));
-                self.parse_asm_memory_operand(Some(OperandSize::Word))
+                self.parse_asm_sized_operand(OperandSize::Word)
             }
             Token::Keyword(Keyword::DWord) => {
                 self.bump();
-                self.expect(Token::Keyword(Keyword::Ptr));
-                self.parse_asm_memory_operand(Some(OperandSize::DWord))
+                self.parse_asm_sized_operand(OperandSize::DWord)
             }
             Token::Keyword(Keyword::QWord) => {
                 self.bump();
-                self.expect(Token::Keyword(Keyword::Ptr));
-                self.parse_asm_memory_operand(Some(OperandSize::QWord))
+                self.parse_asm_sized_operand(OperandSize::QWord)
             }
             _ => AsmArg::Register(self.parse_asm_register()),
         };
@@ -417,6 +466,23 @@ impl<'a> Parser<'a> {
         Node::new(arg, lo + self.span)
     }
 
+    /// Parse the operand following a size keyword
+    ///
+    /// `byte ptr [...]` accesses memory, `byte $reg` the register's lowest byte.
+    fn parse_asm_sized_operand(&mut self, size: OperandSize) -> AsmArg {
+        if self.eat(Token::Keyword(Keyword::Ptr)) {
+            return self.parse_asm_memory_operand(Some(size));
+        }
+
+        let reg = self.parse_asm_arg().unwrap();
+        match reg {
+            AsmArg::Register(..) | AsmArg::NewRegister(..) | AsmArg::IrArg(..) => {}
+            _ => self.fatal(format!("Invalid sub-register: {:?}", reg)),
+        }
+
+        AsmArg::SubRegister(Box::new(reg), size)
+    }
+
     fn parse_asm_register(&mut self) -> MachineRegister {
         let ident = self.parse_ident();
         match &*ident.to_lowercase() {
@@ -437,6 +503,10 @@ impl<'a> Parser<'a> {
             "rsp" => MachineRegister::RSP,
             "rbp" => MachineRegister::RBP,
             "cl" => MachineRegister::CL,
+            name if name.starts_with("xmm") => match name[3..].parse::<usize>() {
+                Ok(n) if n < MachineRegister::xmm().len() => MachineRegister::xmm()[n],
+                _ => self.fatal(format!("Invalid register: {}", ident)),
+            },
             _ => self.fatal(format!("Invalid register: {}", ident)),
         }
     }
@@ -128,6 +128,9 @@ keywords! {
     Pow     => "pow",
     Shl     => "shl",
     Shr     => "shr",
+    UDiv    => "udiv",
+    UMod    => "umod",
+    UShr    => "ushr",
     And     => "and",
     Or      => "or",
     Xor     => "xor",
@@ -140,7 +143,29 @@ keywords! {
     Ne      => "ne",
     Ge      => "ge",
     Gt      => "gt",
+    ULt     => "ult",
+    ULe     => "ule",
+    UGe     => "uge",
+    UGt     => "ugt",
+    FAdd    => "fadd",
+    FSub    => "fsub",
+    FMul    => "fmul",
+    FDiv    => "fdiv",
+    FNeg    => "fneg",
+    SiToFp  => "sitofp",
+    FpToSi  => "fptosi",
+    FLt     => "flt",
+    FLe     => "fle",
+    FEq     => "feq",
+    FNe     => "fne",
+    FGe     => "fge",
+    FGt     => "fgt",
+    Zext8   => "zext8",
+    Sext8   => "sext8",
+    Zext32  => "zext32",
+    Sext32  => "sext32",
     Alloca  => "alloca",
+    Index   => "index",
     Load    => "load",
     Store   => "store",
     Call    => "call",
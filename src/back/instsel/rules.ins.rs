@@ -63,63 +63,120 @@ rules!{
 
     // Integer division
     [%(dst) = div %(lhs), %(rhs); ..] => {
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;            // Sign-extend rax into rdx
         idiv $rhs;
         mov $dst, rax;
     },
     [%(dst) = div %(lhs), 0(rhs); ..] => {
         mov %(tmp), $rhs;  // Create a temporary virtual register
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $tmp;
         mov $dst, rax;
     },
     [%(dst) = div 0(lhs), %(rhs); ..] => {
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $rhs;
         mov $dst, rax;
     },
     [%(dst) = div 0(lhs), 0(rhs); ..] => {
         mov %(tmp), $rhs;  // Create a temporary virtual register
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $tmp;
         mov $dst, rax;
     },
 
-    // Note: pow will be implemented as an intrinsic
+    // Note: pow is translated by the instruction selector as an intrinsic
 
     // Modulo
     // Like div but use the remainder of the division
     [%(dst) = mod %(lhs), %(rhs); ..] => {
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $rhs;
         mov $dst, rdx;
     },
     [%(dst) = mod %(lhs), 0(rhs); ..] => {
         mov %(tmp), $rhs;  // Create a temporary virtual register
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $tmp;
         mov $dst, rdx;
     },
     [%(dst) = mod 0(lhs), %(rhs); ..] => {
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $rhs;
         mov $dst, rdx;
     },
     [%(dst) = mod 0(lhs), 0(rhs); ..] => {
         mov %(tmp), $rhs;  // Create a temporary virtual register
-        xor rdx, rdx;
         mov rax, $lhs;
+        cqo;
         idiv $tmp;
         mov $dst, rdx;
     },
 
+    // Unsigned division
+    // Like div but zero-extend rax into rdx
+    [%(dst) = udiv %(lhs), %(rhs); ..] => {
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $rhs;
+        mov $dst, rax;
+    },
+    [%(dst) = udiv %(lhs), 0(rhs); ..] => {
+        mov %(tmp), $rhs;
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $tmp;
+        mov $dst, rax;
+    },
+    [%(dst) = udiv 0(lhs), %(rhs); ..] => {
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $rhs;
+        mov $dst, rax;
+    },
+    [%(dst) = udiv 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $rhs;
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $tmp;
+        mov $dst, rax;
+    },
+
+    // Unsigned modulo
+    [%(dst) = umod %(lhs), %(rhs); ..] => {
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $rhs;
+        mov $dst, rdx;
+    },
+    [%(dst) = umod %(lhs), 0(rhs); ..] => {
+        mov %(tmp), $rhs;
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $tmp;
+        mov $dst, rdx;
+    },
+    [%(dst) = umod 0(lhs), %(rhs); ..] => {
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $rhs;
+        mov $dst, rdx;
+    },
+    [%(dst) = umod 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $rhs;
+        xor rdx, rdx;
+        mov rax, $lhs;
+        div $tmp;
+        mov $dst, rdx;
+    },
+
     // Shift left
     [%(dst) = shl %(lhs), %(rhs); ..] => {
         mov $dst, $lhs;
@@ -160,6 +217,26 @@ rules!{
         sar $dst, $rhs;
     },
 
+    // Unsigned (logical) shift right
+    [%(dst) = ushr %(lhs), %(rhs); ..] => {
+        mov $dst, $lhs;
+        mov rcx, $rhs;
+        shr $dst, cl;
+    },
+    [%(dst) = ushr %(lhs), 0(rhs); ..] => {
+        mov $dst, $lhs;
+        shr $dst, $rhs;
+    },
+    [%(dst) = ushr 0(lhs), %(rhs); ..] => {
+        mov $dst, $lhs;
+        mov rcx, $rhs;
+        shr $dst, cl;
+    },
+    [%(dst) = ushr 0(lhs), 0(rhs); ..] => {
+        mov $dst, $lhs;
+        shr $dst, $rhs;
+    },
+
     // And
     [%(dst) = and %(lhs), %(rhs); ..] => {
         mov $dst, $lhs;
@@ -275,6 +352,14 @@ rules!{
         and cl, 1;
         movzx $dst, cl;
     },
+    [%(dst) = cmp lt 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setl cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
 
     // Lower than or equal: With branch
     [%(dst) = cmp le %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
@@ -314,6 +399,14 @@ rules!{
         and cl, 1;
         movzx $dst, cl;
     },
+    [%(dst) = cmp le 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setle cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
 
     // Greater than or equal: With branch
     [%(dst) = cmp ge %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
@@ -353,6 +446,14 @@ rules!{
         and cl, 1;
         movzx $dst, cl;
     },
+    [%(dst) = cmp ge 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setge cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
 
     // Greater than: With branch
     [%(dst) = cmp gt %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
@@ -392,6 +493,14 @@ rules!{
         and cl, 1;
         movzx $dst, cl;
     },
+    [%(dst) = cmp gt 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setg cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
 
     // Equality: With branch
     [%(dst) = cmp eq %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
@@ -431,6 +540,14 @@ rules!{
         and cl, 1;
         movzx $dst, cl;
     },
+    [%(dst) = cmp eq 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        sete cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
 
     // Inequality: With branch
     [%(dst) = cmp ne %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
@@ -470,9 +587,633 @@ rules!{
         and cl, 1;
         movzx $dst, cl;
     },
+    [%(dst) = cmp ne 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setne cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    // Unsigned lower than: With branch
+    [%(dst) = cmp ult %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        jb .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp ult %(lhs), 0(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        jb .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp ult 0(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        ja .conseq;
+        jmp .altern;
+    },
+
+    // Unsigned lower than: Without branch
+    [%(dst) = cmp ult %(lhs), %(rhs); ..] => {
+        cmp $lhs, $rhs;
+        setb cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ult %(lhs), 0(rhs); ..] => {
+        cmp $lhs, $rhs;
+        setb cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ult 0(lhs), %(rhs); ..] => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ult 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setb cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    // Unsigned lower than or equal: With branch
+    [%(dst) = cmp ule %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        jbe .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp ule %(lhs), 0(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        jbe .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp ule 0(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        jae .conseq;
+        jmp .altern;
+    },
+
+    // Unsigned lower than or equal: Without branch
+    [%(dst) = cmp ule %(lhs), %(rhs); ..] => {
+        cmp $lhs, $rhs;
+        setbe cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ule %(lhs), 0(rhs); ..] => {
+        cmp $lhs, $rhs;
+        setbe cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ule 0(lhs), %(rhs); ..] => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ule 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setbe cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    // Unsigned greater than or equal: With branch
+    [%(dst) = cmp uge %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        jae .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp uge %(lhs), 0(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        jae .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp uge 0(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        jbe .conseq;
+        jmp .altern;
+    },
+
+    // Unsigned greater than or equal: Without branch
+    [%(dst) = cmp uge %(lhs), %(rhs); ..] => {
+        cmp $lhs, $rhs;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp uge %(lhs), 0(rhs); ..] => {
+        cmp $lhs, $rhs;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp uge 0(lhs), %(rhs); ..] => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        setbe cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp uge 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    // Unsigned greater than: With branch
+    [%(dst) = cmp ugt %(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        ja .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp ugt %(lhs), 0(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        cmp $lhs, $rhs;
+        ja .conseq;
+        jmp .altern;
+    },
+    [%(dst) = cmp ugt 0(lhs), %(rhs); br %(cond), conseq, altern] if { dst == cond } => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        jb .conseq;
+        jmp .altern;
+    },
+
+    // Unsigned greater than: Without branch
+    [%(dst) = cmp ugt %(lhs), %(rhs); ..] => {
+        cmp $lhs, $rhs;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ugt %(lhs), 0(rhs); ..] => {
+        cmp $lhs, $rhs;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ugt 0(lhs), %(rhs); ..] => {
+        // Inverted cmp
+        cmp $rhs, $lhs;
+        setb cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp ugt 0(lhs), 0(rhs); ..] => {
+        // `cmp` can't compare two immediates
+        mov $dst, $lhs;
+        cmp $dst, $rhs;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
 
     // TODO: Special case: a == 0 => jz
 
+    // --- Floating point ------------------------------------------------------
+
+    // Floats are kept in general purpose registers as their bit pattern and
+    // moved into SSE registers for each operation. SSE instructions don't
+    // take immediates, so these go through a general purpose register first.
+
+    [%(dst) = fadd %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        addsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fadd %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        addsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fadd 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        addsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fadd 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        addsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+
+    [%(dst) = fsub %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        subsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fsub %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        subsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fsub 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        subsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fsub 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        subsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+
+    [%(dst) = fmul %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        mulsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fmul %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        mulsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fmul 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        mulsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fmul 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        mulsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+
+    [%(dst) = fdiv %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        divsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fdiv %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        divsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fdiv 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        divsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fdiv 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        divsd xmm0, xmm1;
+        movq $dst, xmm0;
+    },
+
+    // Negation: flip the sign bit
+    [%(dst) = fneg %(item); ..] => {
+        mov $dst, $item;
+        btc $dst, 63;
+    },
+    [%(dst) = fneg 0(item); ..] => {
+        mov $dst, $item;
+        btc $dst, 63;
+    },
+
+    // Conversions
+    [%(dst) = sitofp %(item); ..] => {
+        cvtsi2sd xmm0, $item;
+        movq $dst, xmm0;
+    },
+    [%(dst) = sitofp 0(item); ..] => {
+        mov %(tmp), $item;
+        cvtsi2sd xmm0, $tmp;
+        movq $dst, xmm0;
+    },
+    [%(dst) = fptosi %(item); ..] => {
+        movq xmm0, $item;
+        cvttsd2si $dst, xmm0;  // Truncate towards zero
+    },
+    [%(dst) = fptosi 0(item); ..] => {
+        mov %(tmp), $item;
+        movq xmm0, $tmp;
+        cvttsd2si $dst, xmm0;
+    },
+
+    // Comparisons
+    //
+    // `ucomisd` sets CF and ZF like an unsigned comparison. If an operand is
+    // NaN, it sets CF, ZF and PF. Thus `<` and `<=` swap the operands and use
+    // `a`/`ae`, which are false for NaN.
+
+    [%(dst) = cmp flt %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        ucomisd xmm1, xmm0;
+        seta cl;
+        and cl, 1;      // Truncate to first bit
+        movzx $dst, cl; // Move with zero extension
+    },
+    [%(dst) = cmp flt %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm1, xmm0;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp flt 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        ucomisd xmm1, xmm0;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp flt 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm1, xmm0;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    [%(dst) = cmp fle %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        ucomisd xmm1, xmm0;
+        setae cl;
+        and cl, 1;      // Truncate to first bit
+        movzx $dst, cl; // Move with zero extension
+    },
+    [%(dst) = cmp fle %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm1, xmm0;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp fle 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        ucomisd xmm1, xmm0;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp fle 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm1, xmm0;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    [%(dst) = cmp fge %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        setae cl;
+        and cl, 1;      // Truncate to first bit
+        movzx $dst, cl; // Move with zero extension
+    },
+    [%(dst) = cmp fge %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp fge 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp fge 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        setae cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    [%(dst) = cmp fgt %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        seta cl;
+        and cl, 1;      // Truncate to first bit
+        movzx $dst, cl; // Move with zero extension
+    },
+    [%(dst) = cmp fgt %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp fgt 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+    [%(dst) = cmp fgt 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        seta cl;
+        and cl, 1;
+        movzx $dst, cl;
+    },
+
+    [%(dst) = cmp feq %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        sete cl;
+        movzx $dst, cl;
+        setnp cl;           // NaN is unequal to everything
+        movzx %(parity), cl;
+        and $dst, $parity;
+    },
+    [%(dst) = cmp feq %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        sete cl;
+        movzx $dst, cl;
+        setnp cl;
+        movzx %(parity), cl;
+        and $dst, $parity;
+    },
+    [%(dst) = cmp feq 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        sete cl;
+        movzx $dst, cl;
+        setnp cl;
+        movzx %(parity), cl;
+        and $dst, $parity;
+    },
+    [%(dst) = cmp feq 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        sete cl;
+        movzx $dst, cl;
+        setnp cl;
+        movzx %(parity), cl;
+        and $dst, $parity;
+    },
+
+    [%(dst) = cmp fne %(lhs), %(rhs); ..] => {
+        movq xmm0, $lhs;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        setne cl;
+        movzx $dst, cl;
+        setp cl;            // NaN is unequal to everything
+        movzx %(parity), cl;
+        or $dst, $parity;
+    },
+    [%(dst) = cmp fne %(lhs), 0(rhs); ..] => {
+        movq xmm0, $lhs;
+        mov %(tmp), $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        setne cl;
+        movzx $dst, cl;
+        setp cl;
+        movzx %(parity), cl;
+        or $dst, $parity;
+    },
+    [%(dst) = cmp fne 0(lhs), %(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        movq xmm1, $rhs;
+        ucomisd xmm0, xmm1;
+        setne cl;
+        movzx $dst, cl;
+        setp cl;
+        movzx %(parity), cl;
+        or $dst, $parity;
+    },
+    [%(dst) = cmp fne 0(lhs), 0(rhs); ..] => {
+        mov %(tmp), $lhs;
+        movq xmm0, $tmp;
+        mov $tmp, $rhs;
+        movq xmm1, $tmp;
+        ucomisd xmm0, xmm1;
+        setne cl;
+        movzx $dst, cl;
+        setp cl;
+        movzx %(parity), cl;
+        or $dst, $parity;
+    },
+
+    // --- Sign/zero extension --------------------------------------------------
+
+    [%(dst) = zext8 %(src); ..] => {
+        movzx $dst, byte $src;
+    },
+    [%(dst) = zext8 0(src); ..] => {
+        mov %(tmp), $src;
+        movzx $dst, byte $tmp;
+    },
+    [%(dst) = sext8 %(src); ..] => {
+        movsx $dst, byte $src;
+    },
+    [%(dst) = sext8 0(src); ..] => {
+        mov %(tmp), $src;
+        movsx $dst, byte $tmp;
+    },
+    [%(dst) = zext32 %(src); ..] => {
+        // Writing the lower half of a register clears the upper half
+        mov dword $dst, dword $src;
+    },
+    [%(dst) = zext32 0(src); ..] => {
+        mov %(tmp), $src;
+        mov dword $dst, dword $tmp;
+    },
+    [%(dst) = sext32 %(src); ..] => {
+        movsxd $dst, dword $src;
+    },
+    [%(dst) = sext32 0(src); ..] => {
+        mov %(tmp), $src;
+        movsxd $dst, dword $tmp;
+    },
+
     // --- Alloca/load/store ----------------------------------------------------
 
     [{dst} = alloca; ..] => {
@@ -500,6 +1241,42 @@ rules!{
     [store 0(val), %(dst); ..] => {
         mov $dst, $val;
     },
+    [store %(val), @(dst); ..] => {
+        mov {dst}, $val;
+    },
+    [store 0(val), @(dst); ..] => {
+        mov {dst}, $val;
+    },
+
+    // Array elements: `index` computes the address, load/store go through it
+    // (`[%(elem)]`)
+
+    [%(dst) = index {base}, %(idx); ..] => {
+        lea $dst, {base};
+        lea $dst, [$dst + $idx * 8];
+    },
+    [%(dst) = index {base}, 0(idx); ..] => {
+        lea $dst, {base};
+        mov %(tmp), $idx;
+        lea $dst, [$dst + %(tmp) * 8];
+    },
+    [%(dst) = index %(base), %(idx); ..] => {
+        lea $dst, [$base + $idx * 8];
+    },
+    [%(dst) = index %(base), 0(idx); ..] => {
+        mov %(tmp), $idx;
+        lea $dst, [$base + %(tmp) * 8];
+    },
+
+    [%(dst) = load [%(src)]; ..] => {
+        mov $dst, qword ptr [$src];
+    },
+    [store %(val), [%(dst)]; ..] => {
+        mov qword ptr [$dst], $val;
+    },
+    [store 0(val), [%(dst)]; ..] => {
+        mov qword ptr [$dst], $val;
+    },
 
     // --- Call -----------------------------------------------------------------
 
@@ -521,16 +1298,21 @@ rules!{
         leave;  // Reset the stack pointer
         ret;
     },
+    [ret 0(val)] => {
+        mov rax, $val;
+        leave;  // Reset the stack pointer
+        ret;
+    },
 
     [br %(cond), conseq, altern] => {
         test $cond, 1;
-        je .conseq;
+        jne .conseq;
         jmp .altern;
     },
     [br 0(cond), conseq, altern] => {
         mov %(tmp), $cond;
         test $tmp, 1;
-        je .conseq;
+        jne .conseq;
         jmp .altern;
     },
 
@@ -279,6 +279,254 @@ pub fn trans_instr(
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
             rhs: ir::Value::Register(ir::Register::Local(rhs)),
             dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Div,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Div,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Div,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Mod,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RDX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Mod,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RDX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Mod,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RDX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::Mod,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("cqo"), vec![]));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("idiv"),
+                vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RDX)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::UDiv,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
         }), ..] => {
             code.emit_instruction(asm::Instruction::new(
                 Ident::from_str("xor"),
@@ -295,7 +543,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -308,7 +556,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Div,
+            op: ir::InfixOp::UDiv,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
             rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
@@ -336,7 +584,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -349,7 +597,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Div,
+            op: ir::InfixOp::UDiv,
             lhs: ir::Value::Immediate(ir::Immediate(lhs)),
             rhs: ir::Value::Register(ir::Register::Local(rhs)),
             dst: ir::Register::Local(dst),
@@ -369,7 +617,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -382,7 +630,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Div,
+            op: ir::InfixOp::UDiv,
             lhs: ir::Value::Immediate(ir::Immediate(lhs)),
             rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
@@ -410,7 +658,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -423,7 +671,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Mod,
+            op: ir::InfixOp::UMod,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
             rhs: ir::Value::Register(ir::Register::Local(rhs)),
             dst: ir::Register::Local(dst),
@@ -443,7 +691,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -456,7 +704,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Mod,
+            op: ir::InfixOp::UMod,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
             rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
@@ -484,7 +732,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -497,7 +745,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Mod,
+            op: ir::InfixOp::UMod,
             lhs: ir::Value::Immediate(ir::Immediate(lhs)),
             rhs: ir::Value::Register(ir::Register::Local(rhs)),
             dst: ir::Register::Local(dst),
@@ -517,7 +765,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(rhs))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -530,7 +778,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::Mod,
+            op: ir::InfixOp::UMod,
             lhs: ir::Value::Immediate(ir::Immediate(lhs)),
             rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
@@ -558,7 +806,7 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("idiv"),
+                Ident::from_str("div"),
                 vec![asm::Argument::Register(asm::Register::Virtual(tmp))],
             ));
             code.emit_instruction(asm::Instruction::new(
@@ -775,7 +1023,7 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::BinOp {
-            op: ir::InfixOp::And,
+            op: ir::InfixOp::UShr,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
             rhs: ir::Value::Register(ir::Register::Local(rhs)),
             dst: ir::Register::Local(dst),
@@ -788,7 +1036,109 @@ pub fn trans_instr(
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("and"),
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RCX)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("shr"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::UShr,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("shr"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::UShr,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RCX)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("shr"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::UShr,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("shr"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::And,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
                 vec![
                     asm::Argument::Register(asm::Register::Virtual(dst)),
                     asm::Argument::Register(asm::Register::Virtual(rhs)),
@@ -1296,6 +1646,48 @@ pub fn trans_instr(
             ));
             (1, false)
         }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::Lt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setl"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
         [IrLine::Instruction(&ir::Instruction::Cmp {
             cmp: ir::CmpOp::Le,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
@@ -1482,6 +1874,48 @@ pub fn trans_instr(
             ));
             (1, false)
         }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::Le,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setle"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
         [IrLine::Instruction(&ir::Instruction::Cmp {
             cmp: ir::CmpOp::Ge,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
@@ -1668,6 +2102,48 @@ pub fn trans_instr(
             ));
             (1, false)
         }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::Ge,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setge"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
         [IrLine::Instruction(&ir::Instruction::Cmp {
             cmp: ir::CmpOp::Gt,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
@@ -1855,33 +2331,75 @@ pub fn trans_instr(
             (1, false)
         }
         [IrLine::Instruction(&ir::Instruction::Cmp {
-            cmp: ir::CmpOp::Eq,
-            lhs: ir::Value::Register(ir::Register::Local(lhs)),
-            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            cmp: ir::CmpOp::Gt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
-        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
-            cond: ir::Value::Register(ir::Register::Local(cond)),
-            conseq: ir::Label(conseq),
-            altern: ir::Label(altern),
-        })] if dst == cond => {
+        }), ..] => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("cmp"),
+                Ident::from_str("mov"),
                 vec![
-                    asm::Argument::Register(asm::Register::Virtual(lhs)),
-                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("je"),
-                vec![asm::Argument::Label(conseq)],
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("jmp"),
-                vec![asm::Argument::Label(altern)],
+                Ident::from_str("setg"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
             ));
-            (1, true)
-        }
-        [IrLine::Instruction(&ir::Instruction::Cmp {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::Eq,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("je"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
             cmp: ir::CmpOp::Eq,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
             rhs: ir::Value::Immediate(ir::Immediate(rhs)),
@@ -2040,6 +2558,48 @@ pub fn trans_instr(
             ));
             (1, false)
         }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::Eq,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("sete"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
         [IrLine::Instruction(&ir::Instruction::Cmp {
             cmp: ir::CmpOp::Ne,
             lhs: ir::Value::Register(ir::Register::Local(lhs)),
@@ -2226,159 +2786,3800 @@ pub fn trans_instr(
             ));
             (1, false)
         }
-        [IrLine::Instruction(&ir::Instruction::Alloca {
-            dst: ir::Register::Stack(dst),
-        }), ..] => (1, false),
-        [IrLine::Instruction(&ir::Instruction::Load {
-            src: ir::Value::Register(ir::Register::Stack(src)),
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::Ne,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
         }), ..] => {
             code.emit_instruction(asm::Instruction::new(
                 Ident::from_str("mov"),
                 vec![
                     asm::Argument::Register(asm::Register::Virtual(dst)),
-                    asm::Argument::StackSlot(asm::Register::Virtual(src)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
                 ],
             ));
-            (1, false)
-        }
-        [IrLine::Instruction(&ir::Instruction::Load {
-            src: ir::Value::Static(src),
-            dst: ir::Register::Local(dst),
-        }), ..] => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
+                Ident::from_str("cmp"),
                 vec![
                     asm::Argument::Register(asm::Register::Virtual(dst)),
-                    asm::Argument::StackSlot(asm::Register::Virtual(src)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
                 ],
             ));
-            (1, false)
-        }
-        [IrLine::Instruction(&ir::Instruction::Store {
-            src: ir::Value::Register(ir::Register::Local(val)),
-            dst: ir::Value::Register(ir::Register::Stack(dst)),
-        }), ..] => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
-                vec![
-                    asm::Argument::StackSlot(asm::Register::Virtual(dst)),
-                    asm::Argument::Register(asm::Register::Virtual(val)),
-                ],
+                Ident::from_str("setne"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
             ));
-            (1, false)
-        }
-        [IrLine::Instruction(&ir::Instruction::Store {
-            src: ir::Value::Immediate(ir::Immediate(val)),
-            dst: ir::Value::Register(ir::Register::Stack(dst)),
-        }), ..] => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
+                Ident::from_str("and"),
                 vec![
-                    asm::Argument::StackSlot(asm::Register::Virtual(dst)),
-                    asm::Argument::Immediate(machine::Word::from(val)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
                 ],
             ));
-            (1, false)
-        }
-        [IrLine::Instruction(&ir::Instruction::Store {
-            src: ir::Value::Register(ir::Register::Local(val)),
-            dst: ir::Value::Register(ir::Register::Local(dst)),
-        }), ..] => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
+                Ident::from_str("movzx"),
                 vec![
                     asm::Argument::Register(asm::Register::Virtual(dst)),
-                    asm::Argument::Register(asm::Register::Virtual(val)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
                 ],
             ));
             (1, false)
         }
-        [IrLine::Instruction(&ir::Instruction::Store {
-            src: ir::Value::Immediate(ir::Immediate(val)),
-            dst: ir::Value::Register(ir::Register::Local(dst)),
-        }), ..] => {
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
+                Ident::from_str("cmp"),
                 vec![
-                    asm::Argument::Register(asm::Register::Virtual(dst)),
-                    asm::Argument::Immediate(machine::Word::from(val)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
                 ],
             ));
-            (1, false)
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jb"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
         }
-        [IrLine::Instruction(&ir::Instruction::Call {
-            name: callee,
-            args: ref args,
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
             dst: ir::Register::Local(dst),
-        }), ..] => {
-            // Note: This is Rust code, not assembler
-            cconv::translate_call(code, callee, args, dst);
-            (1, false)
-        }
-        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Return { value: None })] => {
-            code.emit_instruction(asm::Instruction::new(Ident::from_str("leave"), vec![]));
-            code.emit_instruction(asm::Instruction::new(Ident::from_str("ret"), vec![]));
-            (0, true)
-        }
-        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Return {
-            value: Some(ir::Value::Register(ir::Register::Local(val))),
-        })] => {
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
+                Ident::from_str("cmp"),
                 vec![
-                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
-                    asm::Argument::Register(asm::Register::Virtual(val)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
                 ],
             ));
-            code.emit_instruction(asm::Instruction::new(Ident::from_str("leave"), vec![]));
-            code.emit_instruction(asm::Instruction::new(Ident::from_str("ret"), vec![]));
-            (0, true)
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jb"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
         }
-        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
             cond: ir::Value::Register(ir::Register::Local(cond)),
             conseq: ir::Label(conseq),
             altern: ir::Label(altern),
-        })] => {
+        })] if dst == cond => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("test"),
+                Ident::from_str("cmp"),
                 vec![
-                    asm::Argument::Register(asm::Register::Virtual(cond)),
-                    asm::Argument::Immediate(1),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("je"),
+                Ident::from_str("ja"),
                 vec![asm::Argument::Label(conseq)],
             ));
             code.emit_instruction(asm::Instruction::new(
                 Ident::from_str("jmp"),
                 vec![asm::Argument::Label(altern)],
             ));
-            (0, true)
+            (1, true)
         }
-        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
-            cond: ir::Value::Immediate(ir::Immediate(cond)),
-            conseq: ir::Label(conseq),
-            altern: ir::Label(altern),
-        })] => {
-            let tmp = Ident::from_str("tmp");
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("mov"),
+                Ident::from_str("cmp"),
                 vec![
-                    asm::Argument::Register(asm::Register::Virtual(tmp)),
-                    asm::Argument::Immediate(machine::Word::from(cond)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("test"),
+                Ident::from_str("setb"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
                 vec![
-                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
                     asm::Argument::Immediate(1),
                 ],
             ));
             code.emit_instruction(asm::Instruction::new(
-                Ident::from_str("je"),
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setb"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setb"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jbe"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jbe"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jae"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setbe"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setbe"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::ULe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setbe"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jae"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jae"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jbe"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setbe"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ja"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ja"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] if dst == cond => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jb"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (1, true)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setb"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::UGt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cmp"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FAdd,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("addsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FAdd,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("addsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FAdd,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("addsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FAdd,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("addsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FSub,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("subsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FSub,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("subsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FSub,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("subsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FSub,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("subsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FMul,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mulsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FMul,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mulsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FMul,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mulsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FMul,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mulsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FDiv,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("divsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FDiv,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("divsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FDiv,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("divsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::BinOp {
+            op: ir::InfixOp::FDiv,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("divsd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::UnOp {
+            op: ir::PrefixOp::FNeg,
+            item: ir::Value::Register(ir::Register::Local(item)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(item)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("btc"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(63),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::UnOp {
+            op: ir::PrefixOp::FNeg,
+            item: ir::Value::Immediate(ir::Immediate(item)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(item)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("btc"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(63),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::UnOp {
+            op: ir::PrefixOp::IntToFloat,
+            item: ir::Value::Register(ir::Register::Local(item)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cvtsi2sd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(item)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::UnOp {
+            op: ir::PrefixOp::IntToFloat,
+            item: ir::Value::Immediate(ir::Immediate(item)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(item)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cvtsi2sd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::UnOp {
+            op: ir::PrefixOp::FloatToInt,
+            item: ir::Value::Register(ir::Register::Local(item)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(item)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cvttsd2si"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::UnOp {
+            op: ir::PrefixOp::FloatToInt,
+            item: ir::Value::Immediate(ir::Immediate(item)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(item)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("cvttsd2si"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FLe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setae"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGt,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FGt,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("seta"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FEq,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("sete"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setnp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FEq,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("sete"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setnp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FEq,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("sete"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setnp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FEq,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("sete"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setnp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("and"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FNe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setne"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("or"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FNe,
+            lhs: ir::Value::Register(ir::Register::Local(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setne"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("or"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FNe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Register(ir::Register::Local(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setne"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("or"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Cmp {
+            cmp: ir::CmpOp::FNe,
+            lhs: ir::Value::Immediate(ir::Immediate(lhs)),
+            rhs: ir::Value::Immediate(ir::Immediate(rhs)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            let parity = Ident::from_str("parity");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(lhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(rhs)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movq"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("ucomisd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM0)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::XMM1)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setne"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("setp"),
+                vec![asm::Argument::Register(asm::Register::Machine(
+                    MachineRegister::CL,
+                ))],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::CL)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("or"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(parity)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: false,
+            bits: 8,
+            src: ir::Value::Register(ir::Register::Local(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::SubRegister(asm::Register::Virtual(src), asm::OperandSize::Byte),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: false,
+            bits: 8,
+            src: ir::Value::Immediate(ir::Immediate(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(src)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movzx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::SubRegister(asm::Register::Virtual(tmp), asm::OperandSize::Byte),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: true,
+            bits: 8,
+            src: ir::Value::Register(ir::Register::Local(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movsx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::SubRegister(asm::Register::Virtual(src), asm::OperandSize::Byte),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: true,
+            bits: 8,
+            src: ir::Value::Immediate(ir::Immediate(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(src)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movsx"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::SubRegister(asm::Register::Virtual(tmp), asm::OperandSize::Byte),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: false,
+            bits: 32,
+            src: ir::Value::Register(ir::Register::Local(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::SubRegister(
+                        asm::Register::Virtual(dst),
+                        asm::OperandSize::DWord,
+                    ),
+                    asm::Argument::SubRegister(
+                        asm::Register::Virtual(src),
+                        asm::OperandSize::DWord,
+                    ),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: false,
+            bits: 32,
+            src: ir::Value::Immediate(ir::Immediate(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(src)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::SubRegister(
+                        asm::Register::Virtual(dst),
+                        asm::OperandSize::DWord,
+                    ),
+                    asm::Argument::SubRegister(
+                        asm::Register::Virtual(tmp),
+                        asm::OperandSize::DWord,
+                    ),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: true,
+            bits: 32,
+            src: ir::Value::Register(ir::Register::Local(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movsxd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::SubRegister(
+                        asm::Register::Virtual(src),
+                        asm::OperandSize::DWord,
+                    ),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Extend {
+            signed: true,
+            bits: 32,
+            src: ir::Value::Immediate(ir::Immediate(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(src)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("movsxd"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::SubRegister(
+                        asm::Register::Virtual(tmp),
+                        asm::OperandSize::DWord,
+                    ),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Alloca {
+            dst: ir::Register::Stack(dst),
+            ..
+        }), ..] => (1, false),
+        [IrLine::Instruction(&ir::Instruction::Load {
+            src: ir::Value::Register(ir::Register::Stack(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::StackSlot(asm::Register::Virtual(src)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Load {
+            src: ir::Value::Static(src),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::StackSlot(asm::Register::Virtual(src)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Register(ir::Register::Local(val)),
+            dst: ir::Value::Register(ir::Register::Stack(dst)),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::StackSlot(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Immediate(ir::Immediate(val)),
+            dst: ir::Value::Register(ir::Register::Stack(dst)),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::StackSlot(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Register(ir::Register::Local(val)),
+            dst: ir::Value::Register(ir::Register::Local(dst)),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Immediate(ir::Immediate(val)),
+            dst: ir::Value::Register(ir::Register::Local(dst)),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Register(ir::Register::Local(val)),
+            dst: ir::Value::Static(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::StackSlot(asm::Register::Virtual(dst)),
+                    asm::Argument::Register(asm::Register::Virtual(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Immediate(ir::Immediate(val)),
+            dst: ir::Value::Static(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::StackSlot(asm::Register::Virtual(dst)),
+                    asm::Argument::Immediate(machine::Word::from(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Index {
+            base: ir::Value::Register(ir::Register::Stack(base)),
+            index: ir::Value::Register(ir::Register::Local(idx)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("lea"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::StackSlot(asm::Register::Virtual(base)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("lea"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Indirect {
+                        size: None,
+                        base: Some(asm::Register::Virtual(dst)),
+                        index: Some((asm::Register::Virtual(idx), 8)),
+                        disp: None,
+                    },
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Index {
+            base: ir::Value::Register(ir::Register::Stack(base)),
+            index: ir::Value::Immediate(ir::Immediate(idx)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("lea"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::StackSlot(asm::Register::Virtual(base)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(idx)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("lea"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Indirect {
+                        size: None,
+                        base: Some(asm::Register::Virtual(dst)),
+                        index: Some((asm::Register::Virtual(tmp), 8)),
+                        disp: None,
+                    },
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Index {
+            base: ir::Value::Register(ir::Register::Local(base)),
+            index: ir::Value::Register(ir::Register::Local(idx)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("lea"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Indirect {
+                        size: None,
+                        base: Some(asm::Register::Virtual(base)),
+                        index: Some((asm::Register::Virtual(idx), 8)),
+                        disp: None,
+                    },
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Index {
+            base: ir::Value::Register(ir::Register::Local(base)),
+            index: ir::Value::Immediate(ir::Immediate(idx)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(idx)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("lea"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Indirect {
+                        size: None,
+                        base: Some(asm::Register::Virtual(base)),
+                        index: Some((asm::Register::Virtual(tmp), 8)),
+                        disp: None,
+                    },
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Load {
+            src: ir::Value::Indirect(ir::Register::Local(src)),
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(dst)),
+                    asm::Argument::Indirect {
+                        size: Some(asm::OperandSize::QWord),
+                        base: Some(asm::Register::Virtual(src)),
+                        index: None,
+                        disp: None,
+                    },
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Register(ir::Register::Local(val)),
+            dst: ir::Value::Indirect(ir::Register::Local(dst)),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Indirect {
+                        size: Some(asm::OperandSize::QWord),
+                        base: Some(asm::Register::Virtual(dst)),
+                        index: None,
+                        disp: None,
+                    },
+                    asm::Argument::Register(asm::Register::Virtual(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Store {
+            src: ir::Value::Immediate(ir::Immediate(val)),
+            dst: ir::Value::Indirect(ir::Register::Local(dst)),
+        }), ..] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Indirect {
+                        size: Some(asm::OperandSize::QWord),
+                        base: Some(asm::Register::Virtual(dst)),
+                        index: None,
+                        disp: None,
+                    },
+                    asm::Argument::Immediate(machine::Word::from(val)),
+                ],
+            ));
+            (1, false)
+        }
+        [IrLine::Instruction(&ir::Instruction::Call {
+            name: callee,
+            args: ref args,
+            dst: ir::Register::Local(dst),
+        }), ..] => {
+            // Note: This is Rust code, not assembler
+            cconv::translate_call(code, callee, args, dst);
+            (1, false)
+        }
+        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Return { value: None })] => {
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("leave"), vec![]));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("ret"), vec![]));
+            (0, true)
+        }
+        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Return {
+            value: Some(ir::Value::Register(ir::Register::Local(val))),
+        })] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Register(asm::Register::Virtual(val)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("leave"), vec![]));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("ret"), vec![]));
+            (0, true)
+        }
+        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Return {
+            value: Some(ir::Value::Immediate(ir::Immediate(val))),
+        })] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Machine(MachineRegister::RAX)),
+                    asm::Argument::Immediate(machine::Word::from(val)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("leave"), vec![]));
+            code.emit_instruction(asm::Instruction::new(Ident::from_str("ret"), vec![]));
+            (0, true)
+        }
+        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Register(ir::Register::Local(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] => {
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("test"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(cond)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jne"),
+                vec![asm::Argument::Label(conseq)],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jmp"),
+                vec![asm::Argument::Label(altern)],
+            ));
+            (0, true)
+        }
+        [IrLine::CFInstruction(&ir::ControlFlowInstruction::Branch {
+            cond: ir::Value::Immediate(ir::Immediate(cond)),
+            conseq: ir::Label(conseq),
+            altern: ir::Label(altern),
+        })] => {
+            let tmp = Ident::from_str("tmp");
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(machine::Word::from(cond)),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("test"),
+                vec![
+                    asm::Argument::Register(asm::Register::Virtual(tmp)),
+                    asm::Argument::Immediate(1),
+                ],
+            ));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("jne"),
                 vec![asm::Argument::Label(conseq)],
             ));
             code.emit_instruction(asm::Instruction::new(
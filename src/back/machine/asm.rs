@@ -1,11 +1,11 @@
 use back::machine::{MachineRegister, Word};
 use driver::interner::Ident;
 use middle::ir;
-use std::collections::HashMap;
 use std::fmt;
 
 #[derive(Clone, Debug)]
 pub struct Fn {
+    name: Ident,
     args: Vec<Ident>,
     code: Vec<Block>,
     /// Stack usage in bytes
@@ -13,14 +13,19 @@ pub struct Fn {
 }
 
 impl Fn {
-    pub fn new(args: Vec<Ident>, code: Vec<Block>) -> Fn {
+    pub fn new(name: Ident, args: Vec<Ident>, code: Vec<Block>) -> Fn {
         Fn {
+            name,
             args,
             code,
             stack_usage: 0,
         }
     }
 
+    pub fn name(&self) -> Ident {
+        self.name
+    }
+
     pub fn emit_block(&mut self, block: Block) {
         self.code.push(block);
     }
@@ -86,6 +91,10 @@ impl Block {
         self.asm.len()
     }
 
+    pub fn insert_instruction(&mut self, idx: usize, i: Instruction) {
+        self.asm.insert(idx, AssemblyLine::Instruction(i));
+    }
+
     pub fn phis(&self) -> &[ir::Phi] {
         &self.phis
     }
@@ -120,6 +129,10 @@ impl Instruction {
         Instruction { mnemonic, args }
     }
 
+    pub fn mnemonic(&self) -> Ident {
+        self.mnemonic
+    }
+
     pub fn inputs(&self) -> Vec<&Register> {
         if !self.args.is_empty() {
             if self.has_inputs_only() || self.is_inplace() {
@@ -142,7 +155,10 @@ impl Instruction {
 
     fn has_inputs_only(&self) -> bool {
         match &*self.mnemonic {
-            "test" | "cmp" | "push" => return true,
+            // `jmp [%table + %index * 8]` only reads the registers
+            "test" | "cmp" | "ucomisd" | "push" | "jmp" => return true,
+            // `idiv %a` reads %a, the results go to rax and rdx
+            "idiv" | "div" => return true,
             _ => {}
         };
 
@@ -158,7 +174,10 @@ impl Instruction {
 
     fn is_inplace(&self) -> bool {
         match &*self.mnemonic {
-            "add" | "sub" | "and" | "or" | "xor" | "sal" | "sar" | "idiv" | "neg" | "not" => true,
+            "add" | "sub" | "and" | "or" | "xor" | "sal" | "sar" | "shr" | "neg" | "not"
+            | "btc" | "addsd" | "subsd" | "mulsd" | "divsd" => true,
+            // imul dst, src >> dst is read too (the three-operand form isn't)
+            "imul" => self.args.len() == 2,
             _ => false,
         }
     }
@@ -166,7 +185,7 @@ impl Instruction {
     fn get_regs<'a>(&'a self, args: &'a [Argument]) -> Vec<&Register> {
         args.iter()
             .flat_map(|arg| match *arg {
-                Argument::Register(ref r) => vec![r],
+                Argument::Register(ref r) | Argument::SubRegister(ref r, _) => vec![r],
                 Argument::Indirect {
                     ref base,
                     ref index,
@@ -191,11 +210,15 @@ impl Instruction {
 #[derive(Copy, Clone, Debug)]
 pub enum Argument {
     Immediate(Word),
+    /// The memory at a label (addressed relative to `rip`)
     Address(Ident),
     Label(Ident),
 
     Register(Register),
 
+    /// The lower part of a register (e.g. `al` for `rax`)
+    SubRegister(Register, OperandSize),
+
     /// A stack slot whose position is yet to be determined
     ///
     /// Basically a register which also needs a slot on the
@@ -229,7 +252,30 @@ pub enum Register {
     Virtual(Ident),
 }
 
+impl Argument {
+    /// Whether the argument still refers to a virtual register or a stack
+    /// slot, i.e. something the assembler doesn't know
+    pub fn is_virtual(&self) -> bool {
+        match *self {
+            Argument::Register(reg) | Argument::SubRegister(reg, _) => reg.is_virtual(),
+            Argument::StackSlot(..) => true,
+            Argument::Indirect { base, index, .. } => {
+                base.is_some_and(Register::is_virtual)
+                    || index.is_some_and(|(reg, _)| reg.is_virtual())
+            }
+            Argument::Immediate(..) | Argument::Address(..) | Argument::Label(..) => false,
+        }
+    }
+}
+
 impl Register {
+    pub fn is_virtual(self) -> bool {
+        match self {
+            Register::Machine(..) => false,
+            Register::Virtual(..) => true,
+        }
+    }
+
     pub fn into_machine(self) -> MachineRegister {
         match self {
             Register::Machine(r) => r,
@@ -241,14 +287,18 @@ impl Register {
 #[derive(Debug)]
 pub struct Assembly {
     data: Vec<String>,
-    code: HashMap<Ident, Fn>,
+    /// Read-only data (string literals)
+    rodata: Vec<String>,
+    /// The functions in the order they were emitted (i.e. in source order)
+    code: Vec<Fn>,
 }
 
 impl Assembly {
     pub fn new() -> Assembly {
         Assembly {
             data: Vec::new(),
-            code: HashMap::new(),
+            rodata: Vec::new(),
+            code: Vec::new(),
         }
     }
 
@@ -256,22 +306,70 @@ impl Assembly {
         self.data.push(d);
     }
 
+    pub fn emit_rodata(&mut self, d: String) {
+        self.rodata.push(d);
+    }
+
     pub fn emit_fn(&mut self, name: Ident, args: Vec<Ident>, code: Vec<Block>) {
-        self.code.insert(name, Fn::new(args, code));
+        self.code.push(Fn::new(name, args, code));
     }
 
     pub fn get_fn(&mut self, name: Ident) -> &Fn {
-        &self.code[&name]
+        self.code
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("no function named {}", name))
     }
 
     #[allow(needless_lifetimes)]
     pub fn fns<'a>(&'a self) -> impl Iterator<Item = &'a Fn> {
-        self.code.values()
+        self.code.iter()
     }
 
     pub fn fns_mut(&mut self) -> impl Iterator<Item = &mut Fn> {
-        self.code.values_mut()
+        self.code.iter_mut()
+    }
+
+    /// Whether any instruction still uses virtual registers (the register
+    /// allocator hasn't replaced them yet)
+    pub fn has_virtual_registers(&self) -> bool {
+        self.fns()
+            .flat_map(|func| func.code())
+            .flat_map(|block| block.code())
+            .any(|line| match *line {
+                AssemblyLine::Instruction(ref inst) => inst.args.iter().any(Argument::is_virtual),
+                AssemblyLine::Directive(..) => false,
+            })
+    }
+}
+
+/// The assembler's name of a block label
+///
+/// IR labels contain dashes (`entry-block1`) which aren't allowed in symbol
+/// names. Dots are, and they can't clash with a function's name.
+pub fn label_name(label: Ident) -> String {
+    label.replace('-', ".")
+}
+
+/// Escape a string for use in an `.ascii`/`.asciz` directive
+///
+/// Everything but printable ASCII characters is written as an octal escape,
+/// so multi-byte characters end up as their UTF-8 encoding.
+pub fn escape_str(s: &str) -> String {
+    let mut escaped = String::new();
+
+    for byte in s.bytes() {
+        match byte {
+            b'"' => escaped.push_str("\\\""),
+            b'\\' => escaped.push_str("\\\\"),
+            b'\n' => escaped.push_str("\\n"),
+            b'\t' => escaped.push_str("\\t"),
+            b' '..=b'~' => escaped.push(char::from(byte)),
+            _ => escaped.push_str(&format!("\\{:03o}", byte)),
+        }
     }
+
+    escaped
 }
 
 impl fmt::Display for Assembly {
@@ -281,7 +379,7 @@ impl fmt::Display for Assembly {
         if !self.data.is_empty() {
             writeln!(f)?;
             writeln!(f, ".data")?;
-            writeln!(f, ".align 4")?;
+            writeln!(f, ".align 8")?;
 
             for line in &self.data {
                 writeln!(f, "{}", line)?
@@ -290,6 +388,16 @@ impl fmt::Display for Assembly {
             writeln!(f)?;
         }
 
+        if !self.rodata.is_empty() {
+            writeln!(f, ".section .rodata")?;
+
+            for line in &self.rodata {
+                writeln!(f, "{}", line)?
+            }
+
+            writeln!(f)?;
+        }
+
         writeln!(f, ".text")?;
 
         for func in self.fns() {
@@ -335,9 +443,13 @@ impl fmt::Display for Argument {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Argument::Immediate(ref val) => write!(f, "{}", val),
-            Argument::Address(ref val) => write!(f, "{}", val),
-            Argument::Label(ref label) => write!(f, "{}", label),
+            Argument::Address(ref val) => write!(f, "[rip + {}]", val),
+            Argument::Label(label) => write!(f, "{}", label_name(label)),
             Argument::Register(ref reg) => write!(f, "{}", reg),
+            Argument::SubRegister(Register::Machine(reg), size) => {
+                write!(f, "{}", reg.sub_register(size))
+            }
+            Argument::SubRegister(reg, size) => write!(f, "{}:{}", reg, size),
             Argument::StackSlot(ref name) => write!(f, "{{{}}}", name),
             Argument::Indirect {
                 size,
@@ -346,12 +458,7 @@ impl fmt::Display for Argument {
                 disp,
             } => {
                 if let Some(size) = size {
-                    match size {
-                        OperandSize::Byte => write!(f, "byte ptr ")?,
-                        OperandSize::Word => write!(f, "word ptr ")?,
-                        OperandSize::DWord => write!(f, "dword ptr ")?,
-                        OperandSize::QWord => write!(f, "qword ptr ")?,
-                    }
+                    write!(f, "{} ptr ", size)?;
                 }
 
                 write!(f, "[")?;
@@ -370,6 +477,17 @@ impl fmt::Display for Argument {
     }
 }
 
+impl fmt::Display for OperandSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OperandSize::Byte => write!(f, "byte"),
+            OperandSize::Word => write!(f, "word"),
+            OperandSize::DWord => write!(f, "dword"),
+            OperandSize::QWord => write!(f, "qword"),
+        }
+    }
+}
+
 impl fmt::Display for Register {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
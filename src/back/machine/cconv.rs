@@ -1,10 +1,15 @@
 //! The `RusTiny` calling convention
 //!
-//! Later on we'll want to use the X64 ABI so we can call libc functions
-//! but for now we'll just implement our own scheme here.
+//! Functions defined in the program use our own scheme. Extern functions are
+//! called with the System V AMD64 ABI so libc functions can be called.
 //!
 //! # Arguments
-//! All arguments are pushed on the stack.
+//! The first eight floating point arguments are passed in XMM0 to XMM7 (in
+//! the order they appear in). All other arguments are pushed on the stack.
+//!
+//! Extern functions additionally get the first six integer arguments in RDI,
+//! RSI, RDX, RCX, R8 and R9. The remaining arguments are pushed from right to
+//! left.
 //!
 //! # Return values
 //!
@@ -14,26 +19,53 @@
 //!
 //! TBD
 
-use back::machine::{asm, MachineRegister, Word};
+use back::machine::{asm, MachineRegister};
 use driver::interner::Ident;
+use driver::session;
+use front::ast;
 use middle::ir;
 
+/// The number of XMM registers used for passing floating point arguments
+const FLOAT_ARG_REGS: usize = 8;
+
+/// The registers used for passing integer arguments to extern functions
+const INT_ARG_REGS: &[MachineRegister] = &[
+    MachineRegister::RDI,
+    MachineRegister::RSI,
+    MachineRegister::RDX,
+    MachineRegister::RCX,
+    MachineRegister::R8,
+    MachineRegister::R9,
+];
+
 //pub fn translate_call(_: &mut asm::Block,
 //                      _: Ident,
 //                      _: &[ir::Value],
 //                      _: Ident) {
 pub fn translate_call(code: &mut asm::Block, func: Ident, args: &[ir::Value], dst: Ident) {
-    for arg in args {
+    if is_extern(func) {
+        translate_extern_call(code, func, args);
+    } else {
+        let mut xmm = MachineRegister::xmm()[..FLOAT_ARG_REGS].iter();
+
+        for (arg, is_float) in args.iter().zip(float_args(func)) {
+            let reg = if is_float { xmm.next() } else { None };
+
+            match reg {
+                Some(&reg) => pass_in_xmm(code, arg, reg),
+                None => code.emit_instruction(asm::Instruction::new(
+                    Ident::from_str("push"),
+                    vec![translate_value(arg)],
+                )),
+            }
+        }
+
         code.emit_instruction(asm::Instruction::new(
-            Ident::from_str("push"),
-            vec![translate_value(arg)],
+            Ident::from_str("call"),
+            vec![asm::Argument::Label(func)],
         ));
     }
 
-    code.emit_instruction(asm::Instruction::new(
-        Ident::from_str("call"),
-        vec![asm::Argument::Label(func)],
-    ));
     code.emit_instruction(asm::Instruction::new(
         Ident::from_str("mov"),
         vec![
@@ -43,8 +75,163 @@ pub fn translate_call(code: &mut asm::Block, func: Ident, args: &[ir::Value], ds
     ));
 }
 
+/// Store the arguments passed in XMM registers in their stack slots
+///
+/// Emitted in the function prologue, as the function body expects all
+/// arguments in stack slots.
+pub fn translate_args(code: &mut asm::Block, func: Ident, args: &[Ident]) {
+    let xmm = MachineRegister::xmm()[..FLOAT_ARG_REGS].iter();
+    let floats = args
+        .iter()
+        .zip(float_args(func))
+        .filter(|&(_, is_float)| is_float);
+
+    for ((&arg, _), &reg) in floats.zip(xmm) {
+        code.emit_instruction(asm::Instruction::new(
+            Ident::from_str("movsd"),
+            vec![
+                asm::Argument::StackSlot(asm::Register::Virtual(arg)),
+                asm::Argument::Register(asm::Register::Machine(reg)),
+            ],
+        ));
+    }
+}
+
+/// Call an extern function following the System V AMD64 ABI
+///
+/// The ABI requires the stack to be aligned to 16 bytes at the call, which our
+/// own functions don't guarantee. So the stack pointer is aligned here and
+/// restored after the call.
+fn translate_extern_call(code: &mut asm::Block, func: Ident, args: &[ir::Value]) {
+    let machine = |reg| asm::Argument::Register(asm::Register::Machine(reg));
+    let emit = |code: &mut asm::Block, name: &str, args: Vec<asm::Argument>| {
+        code.emit_instruction(asm::Instruction::new(Ident::from_str(name), args))
+    };
+
+    let mut int_regs = INT_ARG_REGS.iter();
+    let mut xmm = MachineRegister::xmm()[..FLOAT_ARG_REGS].iter();
+    let mut in_regs = Vec::new();
+    let mut on_stack = Vec::new();
+
+    for (arg, is_float) in args.iter().zip(float_args(func)) {
+        let reg = if is_float {
+            xmm.next()
+        } else {
+            int_regs.next()
+        };
+
+        match reg {
+            Some(&reg) => in_regs.push((arg, reg)),
+            None => on_stack.push(arg),
+        }
+    }
+
+    // Align the stack and save the old stack pointer on top of it (twice,
+    // so the stack stays aligned)
+    emit(
+        code,
+        "mov",
+        vec![machine(MachineRegister::RAX), machine(MachineRegister::RSP)],
+    );
+    emit(
+        code,
+        "and",
+        vec![machine(MachineRegister::RSP), asm::Argument::Immediate(-16)],
+    );
+    emit(code, "push", vec![machine(MachineRegister::RAX)]);
+    emit(code, "push", vec![machine(MachineRegister::RAX)]);
+
+    // Keep the stack aligned after pushing the stack arguments
+    let padding = on_stack.len() % 2;
+    if padding == 1 {
+        emit(
+            code,
+            "sub",
+            vec![machine(MachineRegister::RSP), asm::Argument::Immediate(8)],
+        );
+    }
+
+    for arg in on_stack.iter().rev() {
+        emit(code, "push", vec![translate_value(arg)]);
+    }
+
+    let mut float_regs = 0;
+    for (arg, reg) in in_regs {
+        if reg.is_xmm() {
+            pass_in_xmm(code, arg, reg);
+            float_regs += 1;
+        } else {
+            emit(code, "mov", vec![machine(reg), translate_value(arg)]);
+        }
+    }
+
+    // Variadic functions (e.g. `printf`) expect the number of XMM registers
+    // used in AL
+    emit(
+        code,
+        "mov",
+        vec![
+            machine(MachineRegister::RAX),
+            asm::Argument::Immediate(float_regs),
+        ],
+    );
+    emit(code, "call", vec![asm::Argument::Label(func)]);
+
+    // Remove the stack arguments and restore the old stack pointer
+    let stack_size = 8 * (on_stack.len() + padding) as i64;
+    if stack_size > 0 {
+        emit(
+            code,
+            "add",
+            vec![
+                machine(MachineRegister::RSP),
+                asm::Argument::Immediate(stack_size),
+            ],
+        );
+    }
+    emit(code, "pop", vec![machine(MachineRegister::RSP)]);
+}
+
 // TODO: pub fn translate_return()
 
+/// Whether a function is an extern function (defined outside of the program)
+fn is_extern(func: Ident) -> bool {
+    matches!(
+        session().symbol_table.lookup_symbol(&func),
+        Some(ast::Symbol::Extern { .. })
+    )
+}
+
+/// Whether each of a function's arguments is a float
+fn float_args(func: Ident) -> Vec<bool> {
+    let (bindings, _) = session()
+        .symbol_table
+        .lookup_function(&func)
+        .unwrap_or_else(|| panic!("no function named {}", func));
+
+    bindings.iter().map(|b| b.ty == ast::Type::F64).collect()
+}
+
+fn pass_in_xmm(code: &mut asm::Block, arg: &ir::Value, reg: MachineRegister) {
+    // Immediates have to go through a general purpose register
+    let src = match *arg {
+        ir::Value::Immediate(ir::Immediate(val)) => {
+            let tmp = asm::Register::Virtual(Ident::from_str("tmp"));
+            code.emit_instruction(asm::Instruction::new(
+                Ident::from_str("mov"),
+                vec![asm::Argument::Register(tmp), asm::Argument::Immediate(val)],
+            ));
+            asm::Argument::Register(tmp)
+        }
+        ref value => translate_value(value),
+    };
+
+    code.emit_instruction(asm::Instruction::new(
+        Ident::from_str("movq"),
+        vec![asm::Argument::Register(asm::Register::Machine(reg)), src],
+    ));
+}
+
 fn translate_value(value: &ir::Value) -> asm::Argument {
     match *value {
         ir::Value::Register(ir::Register::Local(reg)) => {
@@ -55,8 +242,8 @@ fn translate_value(value: &ir::Value) -> asm::Argument {
             asm::Argument::StackSlot(asm::Register::Virtual(reg))
         }
 
-        ir::Value::Immediate(ir::Immediate(val)) => asm::Argument::Immediate(Word::from(val)),
+        ir::Value::Immediate(ir::Immediate(val)) => asm::Argument::Immediate(val),
 
-        ir::Value::Static(..) => unimplemented!(),
+        ir::Value::Static(..) | ir::Value::Indirect(..) => unimplemented!(),
     }
 }
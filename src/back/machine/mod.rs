@@ -1,13 +1,14 @@
 //! The `RusTiny` machine description
 //!
 
+use back::machine::asm::OperandSize;
 use std::fmt;
 
 pub mod cconv;
 #[macro_use]
 pub mod asm;
 
-pub type Word = u64;
+pub type Word = i64;
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum MachineRegister {
@@ -33,6 +34,24 @@ pub enum MachineRegister {
 
     // Needed for artithmetic left shift
     CL,
+
+    // SSE registers used for floating point operations
+    XMM0,
+    XMM1,
+    XMM2,
+    XMM3,
+    XMM4,
+    XMM5,
+    XMM6,
+    XMM7,
+    XMM8,
+    XMM9,
+    XMM10,
+    XMM11,
+    XMM12,
+    XMM13,
+    XMM14,
+    XMM15,
 }
 
 impl MachineRegister {
@@ -56,6 +75,75 @@ impl MachineRegister {
 
         ALL
     }
+
+    /// The SSE registers in numerical order
+    pub fn xmm() -> &'static [MachineRegister] {
+        const XMM: &[MachineRegister] = &[
+            MachineRegister::XMM0,
+            MachineRegister::XMM1,
+            MachineRegister::XMM2,
+            MachineRegister::XMM3,
+            MachineRegister::XMM4,
+            MachineRegister::XMM5,
+            MachineRegister::XMM6,
+            MachineRegister::XMM7,
+            MachineRegister::XMM8,
+            MachineRegister::XMM9,
+            MachineRegister::XMM10,
+            MachineRegister::XMM11,
+            MachineRegister::XMM12,
+            MachineRegister::XMM13,
+            MachineRegister::XMM14,
+            MachineRegister::XMM15,
+        ];
+
+        XMM
+    }
+
+    pub fn is_xmm(self) -> bool {
+        MachineRegister::xmm().contains(&self)
+    }
+
+    /// The name of the register's lower part of the given size
+    /// (`rax` -> `al`, `ax`, `eax`)
+    pub fn sub_register(self, size: OperandSize) -> String {
+        let name = self.to_string();
+
+        match self {
+            MachineRegister::RAX
+            | MachineRegister::RBX
+            | MachineRegister::RCX
+            | MachineRegister::RDX => {
+                let letter = &name[1..2];
+                match size {
+                    OperandSize::Byte => format!("{}l", letter),
+                    OperandSize::Word => format!("{}x", letter),
+                    OperandSize::DWord => format!("e{}x", letter),
+                    OperandSize::QWord => name,
+                }
+            }
+            MachineRegister::RSI
+            | MachineRegister::RDI
+            | MachineRegister::RSP
+            | MachineRegister::RBP => match size {
+                OperandSize::Byte => format!("{}l", &name[1..]),
+                OperandSize::Word => name[1..].to_owned(),
+                OperandSize::DWord => format!("e{}", &name[1..]),
+                OperandSize::QWord => name,
+            },
+            MachineRegister::CL => match size {
+                OperandSize::Byte => name,
+                _ => panic!("cl has no {:?} part", size),
+            },
+            reg if reg.is_xmm() => panic!("{} has no sub-registers", name),
+            _ => match size {
+                OperandSize::Byte => format!("{}b", name),
+                OperandSize::Word => format!("{}w", name),
+                OperandSize::DWord => format!("{}d", name),
+                OperandSize::QWord => name,
+            },
+        }
+    }
 }
 
 impl fmt::Display for MachineRegister {
@@ -78,6 +166,22 @@ impl fmt::Display for MachineRegister {
             MachineRegister::RSP => write!(f, "rsp"),
             MachineRegister::RBP => write!(f, "rbp"),
             MachineRegister::CL => write!(f, "cl"),
+            MachineRegister::XMM0 => write!(f, "xmm0"),
+            MachineRegister::XMM1 => write!(f, "xmm1"),
+            MachineRegister::XMM2 => write!(f, "xmm2"),
+            MachineRegister::XMM3 => write!(f, "xmm3"),
+            MachineRegister::XMM4 => write!(f, "xmm4"),
+            MachineRegister::XMM5 => write!(f, "xmm5"),
+            MachineRegister::XMM6 => write!(f, "xmm6"),
+            MachineRegister::XMM7 => write!(f, "xmm7"),
+            MachineRegister::XMM8 => write!(f, "xmm8"),
+            MachineRegister::XMM9 => write!(f, "xmm9"),
+            MachineRegister::XMM10 => write!(f, "xmm10"),
+            MachineRegister::XMM11 => write!(f, "xmm11"),
+            MachineRegister::XMM12 => write!(f, "xmm12"),
+            MachineRegister::XMM13 => write!(f, "xmm13"),
+            MachineRegister::XMM14 => write!(f, "xmm14"),
+            MachineRegister::XMM15 => write!(f, "xmm15"),
         }
     }
 }
@@ -1,9 +1,12 @@
+mod dot;
 mod instsel;
 #[macro_use]
 mod machine;
 mod regalloc;
 
+pub use self::dot::cfg_dot;
 pub use self::instsel::{compile_rules, select_instructions};
+pub use self::machine::asm::Assembly;
 pub use self::regalloc::allocate_regs;
 
 // IR -> ASM (v)
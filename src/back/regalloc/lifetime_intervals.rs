@@ -17,6 +17,15 @@ pub type Interval = (usize, usize);
 // (block, register) -> [Interval, *]
 pub type LifetimeIntervals = HashMap<(Ident, Register), Vec<Interval>>;
 
+/// Format a register's intervals for debug output (`0..3, 5..6`)
+pub fn format_intervals(intervals: &[Interval]) -> String {
+    intervals
+        .iter()
+        .map(|&(from, to)| format!("{}..{}", from, to))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub fn build_intervals(asm: &Assembly) -> LifetimeIntervals {
     let mut lifetimes = HashMap::new();
     let mut live_in: HashMap<Ident, Vec<Register>> = HashMap::new();
@@ -49,9 +58,10 @@ pub fn build_intervals(asm: &Assembly) -> LifetimeIntervals {
                 .collect();
 
             for phi in phis {
-                live.extend(phi.srcs.iter().map(|src| {
-                    let ir_reg = src.0.reg();
-                    Register::Virtual(ir_reg.ident())
+                // Constant folding might have replaced inputs with immediates
+                live.extend(phi.srcs.iter().filter_map(|src| match src.0 {
+                    ir::Value::Register(ir_reg) => Some(Register::Virtual(ir_reg.ident())),
+                    _ => None,
                 }));
             }
 
@@ -124,10 +134,10 @@ pub fn build_intervals(asm: &Assembly) -> LifetimeIntervals {
             for phi in block.phis() {
                 let reg = Register::Virtual(phi.dst.ident());
 
+                // The phi's value might never be used (e.g. the result of a
+                // `&&` whose value is ignored)
                 if let Some(idx) = live.iter().position(|r| r == &reg) {
                     live.remove(idx);
-                } else {
-                    panic!("Phi output is not live: {}", phi.dst.ident());
                 }
             }
 
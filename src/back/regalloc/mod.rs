@@ -36,21 +36,36 @@
 // TODO: Handle constraints (e.g. div -> CL)
 
 use back::machine::asm::{self, Assembly /*, AssemblyLine, Register*/};
+use driver::session;
 use std::fmt::Write;
-use util;
 
-mod lifetime_intervals;
+pub mod lifetime_intervals;
 
 pub fn allocate_regs(mut asm: Assembly) -> Assembly {
-    let mut s = String::new();
-    write!(s, "{}", asm).unwrap();
-    util::write_file(".debug.asm", &s);
+    session().debug_dump.write("asm-instsel.s", &asm);
 
     let lifetimes = lifetime_intervals::build_intervals(&asm);
 
-    let mut s = String::new();
-    write!(s, "Lifetimes: {:#?}", lifetimes).unwrap();
-    util::write_file(".debug.lifetimes", &s);
+    if session().debug_dump.enabled() {
+        let mut entries: Vec<_> = lifetimes
+            .iter()
+            .map(|(&(block, reg), intervals)| (block.to_string(), reg.to_string(), intervals))
+            .collect();
+        entries.sort();
+
+        let mut s = String::new();
+        for (block, reg, intervals) in entries {
+            writeln!(
+                s,
+                "{} {}: {}",
+                block,
+                reg,
+                lifetime_intervals::format_intervals(intervals)
+            )
+            .unwrap();
+        }
+        session().debug_dump.write("lifetimes.txt", s);
+    }
 
     for func in asm.fns_mut() {
         for bb in func.code_mut() {
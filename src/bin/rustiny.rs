@@ -6,7 +6,7 @@ extern crate rustiny;
 
 use clap::{App, Arg};
 
-use rustiny::driver::{compile_input, CompilationTarget};
+use rustiny::driver::{compile_input, CompilationTarget, Emit, Options};
 use rustiny::util::read_file;
 
 #[cfg(not(test))]
@@ -23,10 +23,35 @@ fn main() {
                 .long("target")
                 .value_name("TYPE")
                 .help("Sets which type of output to generate")
-                .possible_values(&["bin", "asm", "ir"])
+                .possible_values(&["bin", "asm", "ir", "run"])
                 .default_value("bin")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("emit")
+                .long("emit")
+                .value_name("KIND")
+                .help("Emits something other than the target's code")
+                .possible_values(&["cfg-dot"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("debug-dump")
+                .long("debug-dump")
+                .value_name("DIR")
+                .help("Writes the output of every compilation stage to DIR")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("optimize")
+                .short("O")
+                .help("Optimizes the generated code (constant folding)"),
+        )
+        .arg(
+            Arg::with_name("no-bounds-checks")
+                .long("no-bounds-checks")
+                .help("Disables the runtime checks of array indices"),
+        )
         .arg(
             Arg::with_name("output")
                 .short("o")
@@ -51,9 +76,29 @@ fn main() {
         "bin" => CompilationTarget::Bin,
         "asm" => CompilationTarget::Asm,
         "ir" => CompilationTarget::Ir,
+        "run" => CompilationTarget::Run,
         s => panic!(format!("Invalid target: {}", s)),
     };
 
+    let emit = match args.value_of("emit") {
+        None => Emit::Code,
+        Some("cfg-dot") => Emit::CfgDot,
+        Some(s) => panic!("Invalid emit kind: {}", s),
+    };
+
+    let options = Options {
+        bounds_checks: !args.is_present("no-bounds-checks"),
+        optimize: args.is_present("optimize"),
+    };
+
     // Start compilation
-    compile_input(&source, input_file, output_file, target);
+    compile_input(
+        &source,
+        input_file,
+        output_file,
+        target,
+        emit,
+        args.value_of("debug-dump"),
+        options,
+    );
 }
@@ -7,6 +7,9 @@
 //! or showing a snippet of the offending code. The codemap allows to
 //! get the source location of a Span by keeping track of all newlines.
 //!
+//! The codemap also keeps a copy of the source so the code of a line can be
+//! shown (e.g. when a bounds check fails at runtime).
+//!
 //! Should `RusTiny` get support for modules, the codemap would be responsible
 //! to resolve the file name, too.
 //!
@@ -16,8 +19,6 @@
 //! what exactly `Codemap::lines` stores and which offset is 0-based and
 //! which one is 1-based. But hey, it works!
 
-// TODO: Let the codemap own the source string
-
 use std::cell::RefCell;
 use std::ops::{Add, Sub};
 
@@ -66,6 +67,8 @@ pub struct Loc {
 pub struct Codemap {
     /// Mapping of the line number to the start index
     lines: RefCell<Vec<BytePos>>,
+    /// The source code of the file we're compiling
+    source: RefCell<String>,
 }
 
 impl Codemap {
@@ -76,15 +79,37 @@ impl Codemap {
 
         Codemap {
             lines: RefCell::new(lines),
+            source: RefCell::new(String::new()),
         }
     }
 
+    /// Set the source code of the file we're compiling
+    pub fn set_source(&self, source: &str) {
+        *self.source.borrow_mut() = source.to_owned();
+    }
+
+    /// Get the source code of a (1-based) line without the line break
+    pub fn line(&self, line: u32) -> String {
+        let source = self.source.borrow();
+        source
+            .lines()
+            .nth(line as usize - 1)
+            .unwrap_or("")
+            .to_owned()
+    }
+
+    /// The number of lines registered so far
+    pub fn line_count(&self) -> usize {
+        self.lines.borrow().len()
+    }
+
     /// Register the beginning of a new line at a given offset
     pub fn new_line(&self, pos: BytePos) {
         let mut lines = self.lines.borrow_mut();
         let line_count = lines.len();
 
-        assert!(line_count == 0 || (lines[line_count - 1] < pos));
+        // A newline at offset 0 starts at the same offset as the first line
+        assert!(line_count == 0 || (lines[line_count - 1] <= pos));
         lines.push(pos)
     }
 
@@ -0,0 +1,59 @@
+//! Debug dumps
+//!
+//! # Motivation
+//!
+//! When hunting a miscompilation it helps to look at the output of every
+//! compilation stage (`--debug-dump=<dir>`). Every stage writes its result
+//! into the dump directory, numbered in the order they were written.
+//!
+//! Nothing is written unless a dump directory has been set.
+
+use driver::session;
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+use util::write_file;
+
+pub struct DebugDump {
+    dir: RefCell<Option<PathBuf>>,
+    count: Cell<u32>,
+}
+
+impl DebugDump {
+    pub fn new() -> DebugDump {
+        DebugDump {
+            dir: RefCell::new(None),
+            count: Cell::new(0),
+        }
+    }
+
+    /// Start writing dumps to the given directory
+    pub fn enable(&self, dir: &str) {
+        if let Err(err) = fs::create_dir_all(dir) {
+            fatal!("Can't create {}: {}", dir, err);
+            session().abort()
+        }
+
+        *self.dir.borrow_mut() = Some(PathBuf::from(dir));
+    }
+
+    /// Whether dumps are written at all
+    ///
+    /// Used to skip generating dumps that are expensive to produce.
+    pub fn enabled(&self) -> bool {
+        self.dir.borrow().is_some()
+    }
+
+    /// Write the output of a stage (if enabled)
+    ///
+    /// `name` is the file name without the sequence number, e.g. `ast.rs`.
+    pub fn write<T: Display>(&self, name: &str, contents: T) {
+        if let Some(ref dir) = *self.dir.borrow() {
+            self.count.set(self.count.get() + 1);
+
+            let path = dir.join(format!("{:02}-{}", self.count.get(), name));
+            write_file(&path.to_string_lossy(), &contents.to_string());
+        }
+    }
+}
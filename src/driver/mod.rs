@@ -5,26 +5,59 @@
 //! The driver is responsible for coordinating all steps of compilation.
 
 use back;
+use driver::codemap::BytePos;
 use front;
+use front::ast::pretty::PrettyPrinter;
 use middle;
 use std::fmt::Write;
+use std::process::{self, Command};
 use util::write_file;
 
 pub use self::session::session;
 
 pub mod codemap;
+mod debug_dump;
 mod error;
 pub mod interner;
 mod session;
 pub mod symbol_table;
+pub mod type_table;
 
 #[derive(Clone, Copy, Debug, PartialEq, RustcDecodable)]
 pub enum CompilationTarget {
+    Run,
     Ir,
     Asm,
     Bin,
 }
 
+/// The kind of output to generate for the compilation target
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Emit {
+    /// The target's code
+    Code,
+    /// The control flow graph of the target's code as Graphviz digraphs
+    CfgDot,
+}
+
+/// Switches that change how a program is compiled
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Check array indices at runtime
+    pub bounds_checks: bool,
+    /// Run the optimization passes on the IR
+    pub optimize: bool,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            bounds_checks: true,
+            optimize: false,
+        }
+    }
+}
+
 macro_rules! print_or_write {
     ($output_file:expr, $s:expr) => {
         if let Some(output_file) = $output_file {
@@ -37,42 +70,102 @@ macro_rules! print_or_write {
     };
 }
 
+/// Lex the source on its own and dump the resulting tokens
+fn dump_tokens(source: &str, input_file: &str) {
+    let mut lexer = front::Lexer::new(source, input_file);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = lexer.next_token();
+        tokens.push((token.value, token.span));
+
+        if token.value == front::Token::EOF {
+            break;
+        }
+    }
+
+    let mut s = String::new();
+    for (token, span) in tokens {
+        let loc = session().codemap.resolve(BytePos(span.pos));
+        writeln!(s, "{}:{}: {}", loc.line, loc.col, token).unwrap();
+    }
+
+    session().debug_dump.write("tokens.txt", s);
+}
+
 /// The main entry point for compiling a file
 pub fn compile_input(
     source: &str,
     input_file: &str,
     output_file: Option<&str>,
     target: CompilationTarget,
+    emit: Emit,
+    debug_dump: Option<&str>,
+    options: Options,
 ) {
     // --- Front end ------------------------------------------------------------
     // Set up
     front::setup();
+    session().options.set(options);
+    session().codemap.set_source(source);
+
+    if let Some(dir) = debug_dump {
+        session().debug_dump.enable(dir);
+    }
 
     // Phase 1: Lexical & syntactical analysis
+    if session().debug_dump.enabled() {
+        dump_tokens(source, input_file);
+    }
+
     let lexer = front::Lexer::new(source, input_file);
     let mut parser = front::Parser::new(lexer);
     let ast = parser.parse();
 
-    // Phase 2: Analysis passes (semantic checking, type checking)
+    if session().debug_dump.enabled() {
+        let mut pretty = Vec::new();
+        PrettyPrinter::print(&ast, &mut pretty);
+        session()
+            .debug_dump
+            .write("ast.rs", String::from_utf8_lossy(&pretty));
+    }
+
+    // Phase 2: Analysis passes (semantic checking, type checking, constant
+    // evaluation)
     front::semantic_checks(&ast);
     front::type_check(&ast);
+    front::eval_constants(&ast);
 
     // --- Middle end -----------------------------------------------------------
     // Phase 3: Intermediate code generation
-    let ir = middle::ir::translate(&ast);
+    let mut ir = middle::ir::translate(&ast);
+    session().debug_dump.write("ir-translate.ir", &ir);
+
+    // Phase 4: Optimization
+    if options.optimize {
+        middle::fold_constants(&mut ir);
+        session().debug_dump.write("ir-const-folding.ir", &ir);
+    }
 
     if target == CompilationTarget::Ir {
-        print_or_write!(output_file, ir);
+        match emit {
+            Emit::Code => print_or_write!(output_file, ir),
+            Emit::CfgDot => print_or_write!(output_file, middle::ir::cfg_dot(&ir)),
+        }
         return;
     }
 
+    if target == CompilationTarget::Run {
+        // Execute the program in the IR interpreter instead of compiling it
+        let exit_code = middle::interp::run(&ir);
+        process::exit(exit_code as i32);
+    }
+
     //    use util;
     //    let mut s = String::new();
     //    write!(s, "{}", ir).unwrap();
     //    util::write_file(".debug.ir", &s);
 
-    // Phase 4: Optimization
-
     // --- Back end -------------------------------------------------------------
 
     // Phase 5: Machine code generation
@@ -80,14 +173,61 @@ pub fn compile_input(
 
     let assembly = back::select_instructions(&ir);
 
+    if emit == Emit::CfgDot {
+        print_or_write!(output_file, back::cfg_dot(&assembly));
+        return;
+    }
+
     // Phase 6: Register allocation
     let assembly = back::allocate_regs(assembly);
 
     // Phase 7: Assembly optimization
 
-    //    if target == CompilationTarget::Asm {
-    print_or_write!(output_file, assembly);
-    //    }
-
     // Phase 8: Linking
+    match (target, output_file) {
+        (CompilationTarget::Bin, Some(output_file)) => link(&assembly, output_file),
+        (CompilationTarget::Bin, None) => {
+            fatal!("A binary needs an output file, use `-o` to set one");
+            session().abort()
+        }
+        _ => print_or_write!(output_file, assembly),
+    }
+}
+
+/// Assemble the program and link it against libc (so extern functions like
+/// `putchar` can be called)
+///
+/// The C compiler takes care of finding the libc and the startup code that
+/// calls `main`. The assembly is kept next to the binary.
+fn link(assembly: &back::Assembly, output_file: &str) {
+    // The register allocator doesn't assign machine registers yet, so only
+    // programs that don't need any registers can be assembled
+    if assembly.has_virtual_registers() {
+        fatal!(
+            "Can't link the program: register allocation isn't implemented yet \
+             (use `-t asm` to get the assembly)"
+        );
+        session().abort()
+    }
+
+    let asm_file = format!("{}.s", output_file);
+    write_file(&asm_file, &assembly.to_string());
+
+    let output = Command::new("cc")
+        .arg("-o")
+        .arg(output_file)
+        .arg(&asm_file)
+        .output()
+        .unwrap_or_else(|err| {
+            fatal!("Can't run the linker: {}", err);
+            session().abort()
+        });
+
+    if !output.status.success() {
+        fatal!(
+            "Linking failed:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        );
+        session().abort()
+    }
 }
@@ -17,10 +17,13 @@
 //! alternatives IMO.
 
 use driver::codemap::Codemap;
+use driver::debug_dump::DebugDump;
 use driver::error::{self, HasSourceLocation};
 use driver::interner::Interner;
 use driver::symbol_table::SymbolTable;
-use std::cell::RefCell;
+use driver::type_table::TypeTable;
+use driver::Options;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 /// The current compiling session
@@ -29,6 +32,9 @@ pub struct Session {
     pub interner: Interner,
     pub errors: RefCell<bool>,
     pub symbol_table: SymbolTable,
+    pub type_table: TypeTable,
+    pub debug_dump: DebugDump,
+    pub options: Cell<Options>,
 }
 
 impl Session {
@@ -64,7 +70,10 @@ pub fn session() -> Rc<Session> {
             codemap: Codemap::new(),
             interner: Interner::new(),
             errors: RefCell::new(false),
-            symbol_table: SymbolTable::new()
+            symbol_table: SymbolTable::new(),
+            type_table: TypeTable::new(),
+            debug_dump: DebugDump::new(),
+            options: Cell::new(Options::default())
         })
     };
 
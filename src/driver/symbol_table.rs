@@ -4,9 +4,13 @@
 //!
 //! ## Symbol table
 //!
-//! In `RusTiny` a symbol is either a function, a constant or a static variable.
+//! In `RusTiny` a symbol is either a function, an extern function, a constant
+//! or a static variable.
 //! The symbol table maps a symbol's name to its value.
 //!
+//! Struct declarations live in a separate namespace for types, so a struct
+//! may have the same name as a function.
+//!
 //! ## Scopes table
 //!
 //! A scope is the part of the program where a variable is valid. Each block
@@ -31,6 +35,7 @@ use util::TryInsert;
 pub struct SymbolTable {
     scopes: RefCell<HashMap<ast::NodeId, BlockScope>>,
     symbols: RefCell<HashMap<Ident, ast::Symbol>>,
+    types: RefCell<HashMap<Ident, ast::Symbol>>,
 }
 
 impl<'a> SymbolTable {
@@ -38,6 +43,7 @@ impl<'a> SymbolTable {
         SymbolTable {
             scopes: RefCell::new(HashMap::new()),
             symbols: RefCell::new(HashMap::new()),
+            types: RefCell::new(HashMap::new()),
         }
     }
 
@@ -49,6 +55,14 @@ impl<'a> SymbolTable {
             .map_err(|()| "the symbol already exists")
     }
 
+    /// Register a new type (a struct declaration)
+    pub fn register_type(&self, name: Ident, symbol: ast::Symbol) -> Result<(), &'static str> {
+        let mut types = self.types.borrow_mut();
+        types
+            .try_insert(name, symbol)
+            .map_err(|()| "the type already exists")
+    }
+
     /// Register a new scope
     pub fn register_scope(&self, scope: ast::NodeId) -> Result<(), &'static str> {
         let mut scopes = self.scopes.borrow_mut();
@@ -65,7 +79,7 @@ impl<'a> SymbolTable {
     pub fn register_variable(
         &self,
         scope: ast::NodeId,
-        binding: &ast::Binding,
+        binding: &ast::Node<ast::Binding>,
     ) -> Result<(), &'static str> {
         let mut scopes = self.scopes.borrow_mut();
         let vars = &mut scopes
@@ -79,6 +93,8 @@ impl<'a> SymbolTable {
                 ty: binding.ty,
                 reg: None,
                 slot: None,
+                mutable: binding.mutable,
+                decl: binding.span,
             },
         )
         .map_err(|()| "the variable already exists")
@@ -100,54 +116,108 @@ impl<'a> SymbolTable {
         symbols.get(name).cloned() // FIXME: Without clone?
     }
 
+    /// Replace the initializer of a static or constant with its value
+    /// computed at compile time
+    ///
+    /// # Panics
+    ///
+    /// Panics when there's no static or constant with this name
+    pub fn set_constant_value(&self, name: &Ident, val: ast::Value) {
+        let mut symbols = self.symbols.borrow_mut();
+        match symbols.get_mut(name) {
+            Some(&mut ast::Symbol::Static { ref mut value, .. })
+            | Some(&mut ast::Symbol::Constant { ref mut value, .. }) => {
+                // Keep the node id so the recorded type stays valid
+                ***value = ast::Expression::Literal { val };
+            }
+            _ => panic!("no static or constant named {}", name),
+        }
+    }
+
     /// Look up a function's argument types and the return type
+    ///
+    /// Works for both functions defined in the program and extern functions.
     pub fn lookup_function(
         &self,
         name: &Ident,
     ) -> Option<(Vec<ast::Node<ast::Binding>>, ast::Type)> {
         let symbols = self.symbols.borrow();
-        symbols.get(name).and_then(|symbol| {
-            if let ast::Symbol::Function {
+        symbols.get(name).and_then(|symbol| match *symbol {
+            ast::Symbol::Function {
+                ref bindings,
+                ref ret_ty,
+                ..
+            }
+            | ast::Symbol::Extern {
                 ref bindings,
                 ref ret_ty,
                 ..
-            } = *symbol
-            {
-                Some((bindings.to_vec(), *ret_ty))
+            } => Some((bindings.to_vec(), *ret_ty)),
+            _ => None,
+        })
+    }
+
+    /// Look up a struct's fields
+    pub fn lookup_struct(&self, name: &Ident) -> Option<Vec<ast::Node<ast::Binding>>> {
+        let types = self.types.borrow();
+        types.get(name).and_then(|symbol| {
+            if let ast::Symbol::Struct { ref fields, .. } = *symbol {
+                Some(fields.to_vec())
             } else {
                 None
             }
         })
     }
 
-    /// Look up the type of a variable
-    pub fn resolve_variable(&self, mut scope: ast::NodeId, name: &Ident) -> Option<Variable> {
+    /// Look up the variable a name refers to
+    pub fn resolve_variable(
+        &self,
+        scope: ast::NodeId,
+        name: &ast::Node<Ident>,
+    ) -> Option<Variable> {
         // First, look in the current block and its parents
-        loop {
-            if let Some(var) = self.lookup_variable(scope, name) {
-                return Some(var);
-            }
-
-            if let Some(parent) = self.parent_scope(scope) {
-                // Continue searching in the parent scope
-                scope = parent
-            } else {
-                break; // No more parent scopes, search in statics/consts
-            }
+        if let Some(var) = self.resolve_local(scope, name) {
+            return Some(var);
         }
 
         // Look up in static/const symbols
         match self.lookup_symbol(name) {
-            Some(ast::Symbol::Static { ref binding, .. })
-            | Some(ast::Symbol::Constant { ref binding, .. }) => {
-                return Some(Variable {
-                    ty: binding.ty,
-                    reg: None,
-                    slot: None,
-                })
+            Some(ast::Symbol::Static { ref binding, .. }) => Some(Variable {
+                ty: binding.ty,
+                reg: None,
+                slot: None,
+                mutable: true,
+                decl: binding.span,
+            }),
+            Some(ast::Symbol::Constant { ref binding, .. }) => Some(Variable {
+                ty: binding.ty,
+                reg: None,
+                slot: None,
+                mutable: false,
+                decl: binding.span,
+            }),
+            Some(_) | None => None, // Variable not found or refers to a function
+        }
+    }
+
+    /// Look up a local variable or argument in a scope and its parents
+    ///
+    /// Only variables declared before the name are visible. A `let` further
+    /// down the block doesn't shadow a variable of the parent scopes yet.
+    pub fn resolve_local(
+        &self,
+        mut scope: ast::NodeId,
+        name: &ast::Node<Ident>,
+    ) -> Option<Variable> {
+        loop {
+            let var = self.lookup_variable(scope, name);
+            if let Some(var) = var.filter(|var| var.decl.pos <= name.span.pos) {
+                return Some(var);
             }
-            Some(_) | None => return None, // Variable not found or refers to a function
-        };
+
+            // Continue searching in the parent scope
+            scope = self.parent_scope(scope)?;
+        }
     }
 
     /// Set the parent of a scope
@@ -173,6 +243,24 @@ impl<'a> SymbolTable {
         scopes[&scope].parent
     }
 
+    /// Set the type of a variable whose type has been inferred
+    ///
+    /// # Panics
+    ///
+    /// Panics when the scope or variable doesn't exist
+    pub fn set_type(&self, scope: ast::NodeId, name: &Ident, ty: ast::Type) {
+        let mut scopes = self.scopes.borrow_mut();
+        let scope = scopes
+            .get_mut(&scope)
+            .unwrap_or_else(|| panic!("unregistered scope: {:?}", scope));
+        let var = scope
+            .vars
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("unregistered variable: {}", name));
+
+        var.ty = ty;
+    }
+
     /// Set the register of a variable
     ///
     /// # Panics
@@ -187,6 +275,8 @@ impl<'a> SymbolTable {
             ty: ast::Type::Unit,
             reg: None,
             slot: None,
+            mutable: false,
+            decl: ast::EMPTY_SPAN,
         });
 
         var.reg = Some(reg);
@@ -206,6 +296,8 @@ impl<'a> SymbolTable {
             ty: ast::Type::Unit,
             reg: None,
             slot: None,
+            mutable: false,
+            decl: ast::EMPTY_SPAN,
         });
 
         var.slot = Some(reg);
@@ -236,4 +328,8 @@ pub struct Variable {
     /// Not defined for static variables and constants, therefore an Option
     pub reg: Option<ir::Register>,
     pub slot: Option<ir::Register>,
+
+    /// front: Whether the variable may be assigned to and where it's declared
+    pub mutable: bool,
+    pub decl: ast::Span,
 }
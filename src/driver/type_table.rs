@@ -0,0 +1,47 @@
+//! The type table
+//!
+//! # Motivation
+//!
+//! The type checker determines the type of every expression and block. Later
+//! phases need these types too (e.g. to pick the instructions for sized
+//! integers and floats or to compute the memory layout of arrays and structs),
+//! so instead of re-deriving them, we keep the type checker's results around
+//! in this side table. It maps the node id of an expression or a block to its
+//! type.
+
+use front::ast;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct TypeTable {
+    types: RefCell<HashMap<ast::NodeId, ast::Type>>,
+}
+
+impl TypeTable {
+    pub fn new() -> TypeTable {
+        TypeTable {
+            types: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Record the type of a node
+    pub fn record(&self, id: ast::NodeId, ty: ast::Type) {
+        self.types.borrow_mut().insert(id, ty);
+    }
+
+    /// Look up the type of a node, if it has been type checked
+    pub fn lookup(&self, id: ast::NodeId) -> Option<ast::Type> {
+        self.types.borrow().get(&id).cloned()
+    }
+
+    /// Get the type of an expression or a block
+    ///
+    /// # Panics
+    ///
+    /// Panics when the node hasn't been type checked
+    pub fn type_of<T>(&self, node: &ast::Node<T>) -> ast::Type {
+        self.lookup(node.id)
+            .unwrap_or_else(|| panic!("no type recorded for node {:?}", node.id))
+    }
+}
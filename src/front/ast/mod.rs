@@ -8,7 +8,7 @@ pub mod visit;
 
 use driver::interner::Ident;
 use driver::session;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::ops::{Add, Deref, DerefMut};
 use std::str::FromStr;
@@ -19,9 +19,168 @@ use std::str::FromStr;
 pub enum Type {
     Bool,
     Int,
+    U8,
+    I32,
+    U64,
+    F64,
     Char,
+    /// The type of string literals. Strings can only be passed to the
+    /// `print_str` intrinsic.
+    Str,
     Unit,
-    Err, // Special type used for expressions with type errors
+    Array(ArrayType),
+    Struct(StructType),
+    Err,   // Special type used for expressions with type errors
+    Infer, // Placeholder for bindings without a type annotation
+}
+
+/// A fixed-size array type (`[int; 3]`)
+///
+/// The element type and the length are stored in a thread local table so
+/// `Type` can stay `Copy`. As every combination is stored only once, two
+/// array types are equal if their ids are equal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ArrayType(usize);
+
+impl ArrayType {
+    fn with_table<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Vec<(Type, u32)>) -> R,
+    {
+        thread_local! {
+            static ARRAY_TYPES: RefCell<Vec<(Type, u32)>> = const { RefCell::new(Vec::new()) }
+        };
+
+        ARRAY_TYPES.with(|t| f(&mut t.borrow_mut()))
+    }
+
+    /// The type of the array's elements
+    pub fn elem(self) -> Type {
+        ArrayType::with_table(|t| t[self.0].0)
+    }
+
+    /// The number of elements
+    pub fn length(self) -> u32 {
+        ArrayType::with_table(|t| t[self.0].1)
+    }
+}
+
+/// A struct type (`Point`)
+///
+/// Struct types are referred to by name. The fields are stored in the
+/// symbol table along with the struct's declaration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StructType(pub Ident);
+
+impl StructType {
+    pub fn name(self) -> Ident {
+        self.0
+    }
+
+    /// The struct's fields in declaration order
+    ///
+    /// # Panics
+    ///
+    /// Panics when the struct hasn't been declared
+    pub fn fields(self) -> Vec<Node<Binding>> {
+        session()
+            .symbol_table
+            .lookup_struct(&self.0)
+            .unwrap_or_else(|| panic!("no struct named {}", self.0))
+    }
+
+    /// The name of the function that implements a method (`Type$method`)
+    pub fn mangle_method(self, method: Ident) -> Ident {
+        Ident::from_str(&format!("{}${}", self.0, method))
+    }
+
+    /// Look up a field's type and its offset from the start of the struct
+    /// (in stack slots)
+    pub fn field(self, name: Ident) -> Option<(Type, u32)> {
+        let mut offset = 0;
+
+        for field in self.fields() {
+            if *field.name == name {
+                return Some((field.ty, offset));
+            }
+
+            offset += field.ty.slots();
+        }
+
+        None
+    }
+}
+
+impl Type {
+    /// Get the type of an array with `len` elements of type `elem`
+    pub fn array(elem: Type, len: u32) -> Type {
+        ArrayType::with_table(|t| {
+            let id = match t.iter().position(|&a| a == (elem, len)) {
+                Some(id) => id,
+                None => {
+                    t.push((elem, len));
+                    t.len() - 1
+                }
+            };
+
+            Type::Array(ArrayType(id))
+        })
+    }
+
+    /// The number of stack slots a value of this type occupies
+    pub fn slots(&self) -> u32 {
+        match *self {
+            Type::Array(arr) => arr.elem().slots() * arr.length(),
+            Type::Struct(s) => s.fields().iter().map(|f| f.ty.slots()).sum(),
+            _ => 1,
+        }
+    }
+
+    /// Whether values of this type live in memory instead of a register
+    /// (arrays and structs)
+    pub fn is_aggregate(&self) -> bool {
+        matches!(*self, Type::Array(..) | Type::Struct(..))
+    }
+
+    /// Whether this is one of the integer types (`int`, `u8`, `i32`, `u64`)
+    pub fn is_integer(&self) -> bool {
+        matches!(*self, Type::Int | Type::U8 | Type::I32 | Type::U64)
+    }
+
+    /// Whether arithmetic and comparisons can be applied to values of this
+    /// type (the integer types and `f64`)
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || *self == Type::F64
+    }
+
+    /// Whether this is a signed integer type
+    pub fn is_signed(&self) -> bool {
+        matches!(*self, Type::Int | Type::I32)
+    }
+
+    /// The number of bits of integer types that are narrower than a machine
+    /// word
+    ///
+    /// All values occupy a full word at runtime. Values of narrow types are
+    /// kept sign-extended (signed types) or zero-extended (unsigned types).
+    pub fn narrow_bits(&self) -> Option<u32> {
+        match *self {
+            Type::U8 => Some(8),
+            Type::I32 => Some(32),
+            _ => None,
+        }
+    }
+
+    /// The smallest and the largest value of an integer type
+    pub fn int_range(&self) -> (i128, i128) {
+        match *self {
+            Type::Int => (i128::from(i64::MIN), i128::from(i64::MAX)),
+            Type::U8 => (0, i128::from(u8::MAX)),
+            Type::I32 => (i128::from(i32::MIN), i128::from(i32::MAX)),
+            Type::U64 => (0, i128::from(u64::MAX)),
+            _ => panic!("{} is not an integer type", self),
+        }
+    }
 }
 
 impl FromStr for Type {
@@ -30,6 +189,10 @@ impl FromStr for Type {
         match s {
             "bool" => Ok(Type::Bool),
             "int" => Ok(Type::Int),
+            "u8" => Ok(Type::U8),
+            "i32" => Ok(Type::I32),
+            "u64" => Ok(Type::U64),
+            "f64" => Ok(Type::F64),
             "char" => Ok(Type::Char),
             _ => Err(()),
         }
@@ -39,8 +202,16 @@ impl FromStr for Type {
 #[derive(Copy, Clone, Debug)]
 pub enum Value {
     Bool(bool),
-    Int(u32),
+    /// An integer along with its spelling in the source (`0xFF`) if it's a
+    /// literal. Negative literals are spelled without their sign.
+    ///
+    /// Literals aren't typed yet, so the value has to hold any `u64` as well
+    /// as any negative `int`. The type checker makes sure it fits.
+    Int(i128, Option<Ident>),
+    Float(f64),
     Char(char),
+    /// A string literal, stored unescaped
+    Str(Ident),
 }
 
 impl Value {
@@ -48,15 +219,21 @@ impl Value {
         match *self {
             Value::Bool(..) => Type::Bool,
             Value::Int(..) => Type::Int,
+            Value::Float(..) => Type::F64,
             Value::Char(..) => Type::Char,
+            Value::Str(..) => Type::Str,
         }
     }
 
-    pub fn as_u32(&self) -> u32 {
+    pub fn as_i64(&self) -> i64 {
         match *self {
-            Value::Bool(b) => b as u32,
-            Value::Int(i) => i,
-            Value::Char(c) => c as u32,
+            Value::Bool(b) => i64::from(b),
+            // `u64`s above `i64::MAX` are stored as their bit pattern
+            Value::Int(i, _) => i as i64,
+            // Floats are stored as their bit pattern
+            Value::Float(f) => f.to_bits() as i64,
+            Value::Char(c) => i64::from(u32::from(c)),
+            Value::Str(..) => panic!("a string literal has no integer representation"),
         }
     }
 }
@@ -265,34 +442,79 @@ pub enum Symbol {
         bindings: Vec<Node<Binding>>,
         ret_ty: Type,
         body: Box<Node<Block>>,
+        docs: Vec<Doc>,
+    },
+
+    /// A function that is defined outside of the program (e.g. in libc).
+    /// Calls are checked against its signature, but it has no body.
+    Extern {
+        name: Node<Ident>,
+        bindings: Vec<Node<Binding>>,
+        ret_ty: Type,
+        docs: Vec<Doc>,
     },
 
     /// A static value (can be modified at runtime)
     Static {
         binding: Box<Node<Binding>>,
         value: Box<Node<Expression>>,
+        docs: Vec<Doc>,
     },
 
     /// A constant value. Usages will be replaced with the value at compilation time
     Constant {
         binding: Box<Node<Binding>>,
         value: Box<Node<Expression>>,
+        docs: Vec<Doc>,
+    },
+
+    /// A struct declaration
+    Struct {
+        name: Node<Ident>,
+        fields: Vec<Node<Binding>>,
+        docs: Vec<Doc>,
+    },
+
+    /// The methods of a struct. Each method is a `Symbol::Function` whose
+    /// first argument is `self`.
+    Impl {
+        name: Node<Ident>,
+        methods: Vec<Node<Symbol>>,
+        docs: Vec<Doc>,
     },
 }
 
 impl Symbol {
     pub fn get_ident(&self) -> Ident {
         match *self {
-            Symbol::Function { ref name, .. } => **name,
+            Symbol::Function { ref name, .. }
+            | Symbol::Extern { ref name, .. }
+            | Symbol::Struct { ref name, .. }
+            | Symbol::Impl { ref name, .. } => **name,
             Symbol::Static { ref binding, .. } | Symbol::Constant { ref binding, .. } => {
                 *binding.name
             }
         }
     }
 
+    /// The doc comments in front of the symbol
+    pub fn docs(&self) -> &[Doc] {
+        match *self {
+            Symbol::Function { ref docs, .. }
+            | Symbol::Extern { ref docs, .. }
+            | Symbol::Static { ref docs, .. }
+            | Symbol::Constant { ref docs, .. }
+            | Symbol::Struct { ref docs, .. }
+            | Symbol::Impl { ref docs, .. } => docs,
+        }
+    }
+
     pub fn get_value(&self) -> &Expression {
         match *self {
             Symbol::Function { .. } => panic!("Symbol::get_value called on function"),
+            Symbol::Extern { .. } => panic!("Symbol::get_value called on extern function"),
+            Symbol::Struct { .. } => panic!("Symbol::get_value called on struct"),
+            Symbol::Impl { .. } => panic!("Symbol::get_value called on impl"),
             Symbol::Static { ref value, .. } | Symbol::Constant { ref value, .. } => value,
         }
     }
@@ -301,7 +523,11 @@ impl Symbol {
     pub fn clone_stripped(&self) -> Symbol {
         let mut clone = (*self).clone();
         match clone {
-            Symbol::Static { .. } | Symbol::Constant { .. } => {}
+            Symbol::Extern { .. }
+            | Symbol::Static { .. }
+            | Symbol::Constant { .. }
+            | Symbol::Struct { .. }
+            | Symbol::Impl { .. } => {}
             Symbol::Function { ref mut body, .. } => {
                 *body = Box::new(Node::dummy(Block {
                     stmts: vec![],
@@ -314,6 +540,22 @@ impl Symbol {
     }
 }
 
+/// A doc comment (`/// ...` or `//! ...`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Doc {
+    pub kind: DocKind,
+    /// The text after the `///` or `//!`
+    pub text: Ident,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DocKind {
+    /// `/// ...`
+    Outer,
+    /// `//! ...`
+    Inner,
+}
+
 /// A block of statements (e.g. function body, if body, ...)
 #[derive(Clone, Debug)]
 pub struct Block {
@@ -326,6 +568,8 @@ pub struct Block {
 pub struct Binding {
     pub ty: Type,
     pub name: Node<Ident>,
+    /// Whether the binding was declared as `mut` and may be assigned to
+    pub mutable: bool,
 }
 
 /// A declaration or an expression terminated with a semicolon
@@ -340,6 +584,27 @@ pub enum Statement {
     },
 }
 
+/// An arm of a `match` expression (`1 | 2 => { ... }`)
+///
+/// Arms whose body is a plain expression are stored with a block containing
+/// just that expression.
+#[derive(Clone, Debug)]
+pub struct Arm {
+    pub patterns: Vec<Node<Pattern>>,
+    pub body: Box<Node<Block>>,
+}
+
+/// A pattern in a `match` arm
+#[derive(Clone, Copy, Debug)]
+pub enum Pattern {
+    /// A single value (`1`, `'a'`, `true`)
+    Literal(Value),
+    /// An inclusive range of values (`'a'..='z'`)
+    Range(Value, Value),
+    /// Matches every value (`_`)
+    Wildcard,
+}
+
 /// An expression. The real 'meat' of the language.
 ///
 /// The difference to statements is that they aren't terminated by a semicolon
@@ -395,6 +660,12 @@ pub enum Expression {
         item: Box<Node<Expression>>,
     },
 
+    /// A conversion between primitive types (`a as u8`)
+    Cast {
+        expr: Box<Node<Expression>>,
+        ty: Type,
+    },
+
     /// A conditional with an optional `else` branch
     If {
         cond: Box<Node<Expression>>,
@@ -402,19 +673,79 @@ pub enum Expression {
         altern: Option<Box<Node<Block>>>,
     },
 
+    /// Compare a value with the arms' patterns and evaluate the body of the
+    /// first arm that matches
+    Match {
+        expr: Box<Node<Expression>>,
+        arms: Vec<Arm>,
+    },
+
+    /// An array literal (`[1, 2, 3]`)
+    Array { elems: Vec<Node<Expression>> },
+
+    /// An array literal repeating a value (`[0; 3]`)
+    ArrayRepeat {
+        elem: Box<Node<Expression>>,
+        len: u32,
+    },
+
+    /// Access to an array element (`a[i]`)
+    Index {
+        array: Box<Node<Expression>>,
+        index: Box<Node<Expression>>,
+    },
+
+    /// A struct literal (`Point { x: 1, y: 2 }`)
+    Struct {
+        name: Node<Ident>,
+        fields: Vec<(Node<Ident>, Node<Expression>)>,
+    },
+
+    /// Access to a struct's field (`p.x`)
+    Field {
+        object: Box<Node<Expression>>,
+        field: Node<Ident>,
+    },
+
     /// A while loop
     While {
         cond: Box<Node<Expression>>,
         body: Box<Node<Block>>,
+        label: Option<Node<Ident>>,
+    },
+
+    /// A loop over a range of integers (`for i in 0..10`, `for i in 1..=10`)
+    ///
+    /// As `RusTiny` doesn't have iterators, ranges are the only thing we can
+    /// loop over. The range's end is exclusive unless `inclusive` is set.
+    For {
+        var: Box<Node<Binding>>,
+        start: Box<Node<Expression>>,
+        end: Box<Node<Expression>>,
+        inclusive: bool,
+        body: Box<Node<Block>>,
+        label: Option<Node<Ident>>,
     },
 
-    /// Break out of a loop
-    Break,
+    /// A loop that runs until it's left with `break` or `return`
+    ///
+    /// The loop evaluates to the value passed to `break` (`break 42`).
+    Loop {
+        body: Box<Node<Block>>,
+        label: Option<Node<Ident>>,
+    },
+
+    /// Break out of the innermost loop or the loop with the given label,
+    /// optionally with the value a `loop` evaluates to
+    Break {
+        label: Option<Node<Ident>>,
+        value: Option<Box<Node<Expression>>>,
+    },
+
+    /// Continue with the next iteration of the innermost loop or the loop
+    /// with the given label
+    Continue { label: Option<Node<Ident>> },
 
-    //For  // There is currently no `for` loop! Rust uses `for x in iterator`, but
-    // as RusTiny doesn't have iterators (or structs for that matter), that
-    // wouldn't make much sense. Having a classical C-style for loop on the
-    // other hand would be useful but can be abused much more...
     /// An expression without any content
     Unit,
 }
@@ -451,7 +782,15 @@ impl<T: fmt::Display> fmt::Display for Node<T> {
 
 impl fmt::Display for Binding {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}: {}", self.name, self.ty)
+        if self.mutable {
+            write!(f, "mut ")?;
+        }
+
+        if self.ty == Type::Infer {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}: {}", self.name, self.ty)
+        }
     }
 }
 
@@ -462,9 +801,17 @@ impl fmt::Display for Type {
         match *self {
             Bool => write!(f, "bool"),
             Int => write!(f, "int"),
+            U8 => write!(f, "u8"),
+            I32 => write!(f, "i32"),
+            U64 => write!(f, "u64"),
+            F64 => write!(f, "f64"),
             Char => write!(f, "char"),
+            Str => write!(f, "str"),
             Unit => write!(f, "()"),
+            Array(arr) => write!(f, "[{}; {}]", arr.elem(), arr.length()),
+            Struct(s) => write!(f, "{}", s.name()),
             Err => write!(f, "[type error]"),
+            Infer => write!(f, "_"),
         }
     }
 }
@@ -475,8 +822,20 @@ impl fmt::Display for Value {
 
         match *self {
             Bool(b) => write!(f, "{}", b),
-            Int(i) => write!(f, "{}", i),
+            Int(i, _) => write!(f, "{}", i),
+            // Always print the decimal point (`1.0` instead of `1`)
+            Float(x) => write!(f, "{:?}", x),
             Char(c) => write!(f, "{}", c),
+            Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl fmt::Display for Doc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            DocKind::Outer => write!(f, "///{}", self.text),
+            DocKind::Inner => write!(f, "//!{}", self.text),
         }
     }
 }
@@ -39,28 +39,56 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
             Symbol::Static {
                 ref binding,
                 ref value,
-            } => self.print_static(binding, value),
+                ref docs,
+            } => self.print_static(binding, value, docs),
             Symbol::Constant {
                 ref binding,
                 ref value,
-            } => self.print_constant(binding, value),
+                ref docs,
+            } => self.print_constant(binding, value, docs),
             Symbol::Function {
                 ref name,
                 ref bindings,
                 ref ret_ty,
                 ref body,
-            } => self.print_function(name, bindings, ret_ty, body),
+                ref docs,
+            } => self.print_function(name, bindings, ret_ty, body, docs),
+            Symbol::Extern {
+                ref name,
+                ref bindings,
+                ref ret_ty,
+                ref docs,
+            } => self.print_extern(name, bindings, ret_ty, docs),
+            Symbol::Struct {
+                ref name,
+                ref fields,
+                ref docs,
+            } => self.print_struct(name, fields, docs),
+            Symbol::Impl {
+                ref name,
+                ref methods,
+                ref docs,
+            } => self.print_impl(name, methods, docs),
+        }
+    }
+
+    fn print_docs(&mut self, docs: &[Doc]) {
+        for doc in docs {
+            self.print_indent();
+            writeln!(self.out, "{}", doc).ok();
         }
     }
 
-    fn print_static(&mut self, binding: &Binding, value: &Expression) {
-        write!(self.out, "static {:?} = ", binding).ok();
+    fn print_static(&mut self, binding: &Binding, value: &Expression, docs: &[Doc]) {
+        self.print_docs(docs);
+        write!(self.out, "static {} = ", binding).ok();
         self.print_expression(value);
         writeln!(self.out, ";").ok();
     }
 
-    fn print_constant(&mut self, binding: &Binding, value: &Expression) {
-        write!(self.out, "const {:?} = ", binding).ok();
+    fn print_constant(&mut self, binding: &Binding, value: &Expression, docs: &[Doc]) {
+        self.print_docs(docs);
+        write!(self.out, "const {} = ", binding).ok();
         self.print_expression(value);
         writeln!(self.out, ";").ok();
     }
@@ -71,21 +99,81 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
         bindings: &[Node<Binding>],
         ret_ty: &Type,
         body: &Block,
+        docs: &[Doc],
     ) {
         writeln!(self.out).ok();
+        self.print_docs(docs);
+        self.print_indent();
         write!(
             &mut self.out,
-            "fn {}({}) -> {:?} ",
+            "fn {}({}) ",
             name,
             bindings
                 .iter()
-                .map(|b| format!("{:?}", b))
+                .map(|b| match &**b.name {
+                    // A method's `self` argument doesn't have a written type
+                    "self" if b.mutable => "mut self".to_string(),
+                    "self" => "self".to_string(),
+                    _ => b.to_string(),
+                })
                 .collect::<Vec<_>>()
-                .join(", "),
-            ret_ty
+                .join(", ")
         )
         .ok();
-        self.print_block(body)
+        if *ret_ty != Type::Unit {
+            write!(&mut self.out, "-> {} ", ret_ty).ok();
+        }
+        self.print_block(body);
+        writeln!(self.out).ok();
+    }
+
+    fn print_extern(
+        &mut self,
+        name: &str,
+        bindings: &[Node<Binding>],
+        ret_ty: &Type,
+        docs: &[Doc],
+    ) {
+        self.print_docs(docs);
+        write!(
+            &mut self.out,
+            "extern fn {}({})",
+            name,
+            bindings
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .ok();
+        if *ret_ty != Type::Unit {
+            write!(&mut self.out, " -> {}", ret_ty).ok();
+        }
+        writeln!(self.out, ";").ok();
+    }
+
+    fn print_struct(&mut self, name: &str, fields: &[Node<Binding>], docs: &[Doc]) {
+        writeln!(self.out).ok();
+        self.print_docs(docs);
+        writeln!(self.out, "struct {} {{", name).ok();
+        for field in fields {
+            writeln!(self.out, "    {},", field).ok();
+        }
+        writeln!(self.out, "}}").ok();
+    }
+
+    fn print_impl(&mut self, name: &str, methods: &[Node<Symbol>], docs: &[Doc]) {
+        writeln!(self.out).ok();
+        self.print_docs(docs);
+        write!(self.out, "impl {} {{", name).ok();
+
+        self.indent += 1;
+        for method in methods {
+            self.print_symbol(method);
+        }
+        self.indent -= 1;
+
+        writeln!(self.out, "}}").ok();
     }
 
     fn print_block(&mut self, block: &Block) {
@@ -119,7 +207,7 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
                 ref binding,
                 ref value,
             } => {
-                write!(&mut self.out, "let {:?} = ", binding).ok();
+                write!(&mut self.out, "let {} = ", binding).ok();
                 self.print_expression(value);
                 writeln!(self.out, ";").ok();
             }
@@ -130,6 +218,38 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
         }
     }
 
+    fn print_literal(&mut self, val: &Value) {
+        match *val {
+            Value::Char(c) => write!(&mut self.out, "'{}'", c.escape_default()),
+            Value::Str(s) => write!(&mut self.out, "\"{}\"", s.escape_default()),
+            // Keep the spelling of integer literals (`0xFF`, `1_000`)
+            Value::Int(i, Some(spelling)) if i < 0 => write!(&mut self.out, "-{}", spelling),
+            Value::Int(_, Some(spelling)) => write!(&mut self.out, "{}", spelling),
+            Value::Bool(..) | Value::Int(..) | Value::Float(..) => write!(&mut self.out, "{}", val),
+        }
+        .ok();
+    }
+
+    fn print_pattern(&mut self, pattern: &Pattern) {
+        match *pattern {
+            Pattern::Literal(ref val) => self.print_literal(val),
+            Pattern::Range(ref lo, ref hi) => {
+                self.print_literal(lo);
+                write!(&mut self.out, "..=").ok();
+                self.print_literal(hi);
+            }
+            Pattern::Wildcard => {
+                write!(&mut self.out, "_").ok();
+            }
+        }
+    }
+
+    fn print_label(&mut self, label: &Option<Node<Ident>>) {
+        if let Some(ref label) = *label {
+            write!(&mut self.out, "'{}: ", label).ok();
+        }
+    }
+
     fn print_expression(&mut self, expr: &Expression) {
         match *expr {
             Expression::Group(ref expr) => {
@@ -140,9 +260,11 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
             Expression::Call { ref func, ref args } => {
                 self.print_expression(func);
                 write!(&mut self.out, "(").ok();
-                for arg in args {
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(&mut self.out, ", ").ok();
+                    }
                     self.print_expression(arg);
-                    write!(&mut self.out, ", ").ok();
                 }
                 write!(&mut self.out, ")").ok();
             }
@@ -152,18 +274,65 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
                 ref rhs,
             } => {
                 self.print_expression(lhs);
-                write!(&mut self.out, " {:?} ", op).ok();
+                write!(&mut self.out, " {} ", op).ok();
                 self.print_expression(rhs);
             }
             Expression::Prefix { ref op, ref item } => {
-                write!(&mut self.out, "{:?}", op).ok();
+                write!(&mut self.out, "{}", op).ok();
                 self.print_expression(item);
             }
-            Expression::Literal { ref val } => {
-                write!(&mut self.out, "{:?}", val).ok();
+            Expression::Cast { ref expr, ref ty } => {
+                self.print_expression(expr);
+                write!(&mut self.out, " as {}", ty).ok();
             }
+            Expression::Literal { ref val } => self.print_literal(val),
             Expression::Variable { ref name } => {
-                write!(&mut self.out, "{:?}", name).ok();
+                write!(&mut self.out, "{}", name).ok();
+            }
+            Expression::Array { ref elems } => {
+                write!(&mut self.out, "[").ok();
+                for (i, elem) in elems.iter().enumerate() {
+                    if i > 0 {
+                        write!(&mut self.out, ", ").ok();
+                    }
+                    self.print_expression(elem);
+                }
+                write!(&mut self.out, "]").ok();
+            }
+            Expression::ArrayRepeat { ref elem, len } => {
+                write!(&mut self.out, "[").ok();
+                self.print_expression(elem);
+                write!(&mut self.out, "; {}]", len).ok();
+            }
+            Expression::Index {
+                ref array,
+                ref index,
+            } => {
+                self.print_expression(array);
+                write!(&mut self.out, "[").ok();
+                self.print_expression(index);
+                write!(&mut self.out, "]").ok();
+            }
+            Expression::Struct {
+                ref name,
+                ref fields,
+            } => {
+                write!(&mut self.out, "{} {{ ", name).ok();
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(&mut self.out, ", ").ok();
+                    }
+                    write!(&mut self.out, "{}: ", field).ok();
+                    self.print_expression(value);
+                }
+                write!(&mut self.out, " }}").ok();
+            }
+            Expression::Field {
+                ref object,
+                ref field,
+            } => {
+                self.print_expression(object);
+                write!(&mut self.out, ".{}", field).ok();
             }
             Expression::If {
                 ref cond,
@@ -176,15 +345,66 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
                 self.print_block(conseq);
                 if let Some(ref b) = *altern {
                     write!(&mut self.out, " else ").ok();
-                    self.print_block(b);
+                    match **b.expr {
+                        // An `else` block containing nothing but an `if`
+                        // comes from an `else if`
+                        Expression::If { .. } if b.stmts.is_empty() => {
+                            self.print_expression(&b.expr)
+                        }
+                        _ => self.print_block(b),
+                    }
                 }
             }
-            Expression::While { ref cond, ref body } => {
+            Expression::Match { ref expr, ref arms } => {
+                write!(&mut self.out, "match ").ok();
+                self.print_expression(expr);
+                writeln!(&mut self.out, " {{").ok();
+
+                self.indent += 1;
+                for arm in arms {
+                    self.print_indent();
+                    for (i, pattern) in arm.patterns.iter().enumerate() {
+                        if i > 0 {
+                            write!(&mut self.out, " | ").ok();
+                        }
+                        self.print_pattern(pattern);
+                    }
+                    write!(&mut self.out, " => ").ok();
+                    self.print_block(&arm.body);
+                    writeln!(&mut self.out).ok();
+                }
+                self.indent -= 1;
+
+                self.print_indent();
+                write!(&mut self.out, "}}").ok();
+            }
+            Expression::While {
+                ref cond,
+                ref body,
+                ref label,
+            } => {
+                self.print_label(label);
                 write!(&mut self.out, "while ").ok();
                 self.print_expression(cond);
                 write!(&mut self.out, " ").ok();
                 self.print_block(body);
             }
+            Expression::For {
+                ref var,
+                ref start,
+                ref end,
+                inclusive,
+                ref body,
+                ref label,
+            } => {
+                self.print_label(label);
+                write!(&mut self.out, "for {} in ", var.name).ok();
+                self.print_expression(start);
+                write!(&mut self.out, "{}", if inclusive { "..=" } else { ".." }).ok();
+                self.print_expression(end);
+                write!(&mut self.out, " ").ok();
+                self.print_block(body);
+            }
             Expression::Assign { ref lhs, ref rhs } => {
                 self.print_expression(lhs);
                 write!(&mut self.out, " = ").ok();
@@ -196,15 +416,43 @@ impl<'a, W: Write> PrettyPrinter<'a, W> {
                 ref rhs,
             } => {
                 self.print_expression(lhs);
-                write!(&mut self.out, " {:?}= ", op).ok();
+                write!(&mut self.out, " {}= ", op).ok();
                 self.print_expression(rhs);
             }
-            Expression::Break => {
+            Expression::Loop {
+                ref body,
+                ref label,
+            } => {
+                self.print_label(label);
+                write!(&mut self.out, "loop ").ok();
+                self.print_block(body);
+            }
+            Expression::Break {
+                ref label,
+                ref value,
+            } => {
                 write!(&mut self.out, "break").ok();
+                if let Some(ref label) = *label {
+                    write!(&mut self.out, " '{}", label).ok();
+                }
+                if let Some(ref value) = *value {
+                    write!(&mut self.out, " ").ok();
+                    self.print_expression(value);
+                }
+            }
+            Expression::Continue { ref label } => {
+                write!(&mut self.out, "continue").ok();
+                if let Some(ref label) = *label {
+                    write!(&mut self.out, " '{}", label).ok();
+                }
             }
             Expression::Return { ref val } => {
-                write!(&mut self.out, "return ").ok();
-                self.print_expression(val);
+                write!(&mut self.out, "return").ok();
+                if let Expression::Unit = ***val {
+                } else {
+                    write!(&mut self.out, " ").ok();
+                    self.print_expression(val);
+                }
             }
             Expression::Unit => {
                 write!(&mut self.out, "()").ok();
@@ -62,15 +62,25 @@ where
     V: Visitor<'v>,
 {
     match **symbol {
-        // FIXME: Visit value too?
-        Symbol::Static { ref binding, .. } | Symbol::Constant { ref binding, .. } => {
+        Symbol::Static {
+            ref binding,
+            ref value,
+            ..
+        }
+        | Symbol::Constant {
+            ref binding,
+            ref value,
+            ..
+        } => {
             visitor.visit_binding(binding);
+            visitor.visit_expression(value);
         }
         Symbol::Function {
             ref name,
             ref bindings,
             ref ret_ty,
             ref body,
+            ..
         } => {
             visitor.visit_ident(name);
             for binding in bindings {
@@ -79,6 +89,38 @@ where
             visitor.visit_type(ret_ty);
             visitor.visit_block(body);
         }
+        Symbol::Extern {
+            ref name,
+            ref bindings,
+            ref ret_ty,
+            ..
+        } => {
+            visitor.visit_ident(name);
+            for binding in bindings {
+                visitor.visit_binding(binding);
+            }
+            visitor.visit_type(ret_ty);
+        }
+        Symbol::Struct {
+            ref name,
+            ref fields,
+            ..
+        } => {
+            visitor.visit_ident(name);
+            for field in fields {
+                visitor.visit_binding(field);
+            }
+        }
+        Symbol::Impl {
+            ref name,
+            ref methods,
+            ..
+        } => {
+            visitor.visit_ident(name);
+            for method in methods {
+                visitor.visit_symbol(method);
+            }
+        }
     }
 }
 
@@ -146,6 +188,9 @@ where
         Expression::Prefix { ref item, .. } => {
             visitor.visit_expression(item);
         }
+        Expression::Cast { ref expr, .. } => {
+            visitor.visit_expression(expr);
+        }
         Expression::If {
             ref cond,
             ref conseq,
@@ -157,10 +202,68 @@ where
                 visitor.visit_block(else_block);
             }
         }
-        Expression::While { ref cond, ref body } => {
+        Expression::Match { ref expr, ref arms } => {
+            visitor.visit_expression(expr);
+            for arm in arms {
+                visitor.visit_block(&arm.body);
+            }
+        }
+        Expression::Array { ref elems } => {
+            for elem in elems {
+                visitor.visit_expression(elem);
+            }
+        }
+        Expression::ArrayRepeat { ref elem, .. } => visitor.visit_expression(elem),
+        Expression::Struct {
+            ref name,
+            ref fields,
+        } => {
+            visitor.visit_ident(name);
+            for (field, value) in fields {
+                visitor.visit_ident(field);
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Field {
+            ref object,
+            ref field,
+        } => {
+            visitor.visit_expression(object);
+            visitor.visit_ident(field);
+        }
+        Expression::Index {
+            ref array,
+            ref index,
+        } => {
+            visitor.visit_expression(array);
+            visitor.visit_expression(index);
+        }
+        Expression::While {
+            ref cond, ref body, ..
+        } => {
             visitor.visit_expression(cond);
             visitor.visit_block(body);
         }
-        Expression::Literal { .. } | Expression::Break | Expression::Unit => {}
+        Expression::For {
+            ref var,
+            ref start,
+            ref end,
+            ref body,
+            ..
+        } => {
+            visitor.visit_binding(var);
+            visitor.visit_expression(start);
+            visitor.visit_expression(end);
+            visitor.visit_block(body);
+        }
+        Expression::Loop { ref body, .. } => {
+            visitor.visit_block(body);
+        }
+        Expression::Break { ref value, .. } => {
+            if let Some(ref value) = *value {
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Literal { .. } | Expression::Continue { .. } | Expression::Unit => {}
     }
 }
@@ -0,0 +1,296 @@
+//! Compile-time evaluation of constant expressions
+//!
+//! Statics and constants may be initialized with expressions made of
+//! literals, other constants, arithmetic, comparisons and `if`s on constant
+//! conditions. We evaluate these after type checking (using the recorded
+//! types) and replace the initializers in the symbol table with the computed
+//! values. Thus, later phases only ever see literals.
+//!
+//! Integer arithmetic is checked: results that don't fit into the
+//! expression's type and divisions by zero are reported as errors instead
+//! of wrapping around or trapping at runtime.
+
+use driver::interner::Ident;
+use driver::session;
+use driver::symbol_table::SymbolTable;
+use driver::type_table::TypeTable;
+use front::ast::*;
+use std::collections::HashMap;
+
+/// How far the evaluation of a static or constant has come
+#[derive(Copy, Clone)]
+enum State {
+    /// We're evaluating the initializer right now. Running into the constant
+    /// again means that it depends on itself.
+    InProgress,
+    /// The computed value or `None` if the evaluation failed
+    Done(Option<Value>),
+}
+
+struct ConstEval<'a> {
+    sytbl: &'a SymbolTable,
+    types: &'a TypeTable,
+    states: HashMap<Ident, State>,
+}
+
+impl<'a> ConstEval<'a> {
+    fn new(sytbl: &'a SymbolTable, types: &'a TypeTable) -> ConstEval<'a> {
+        ConstEval {
+            sytbl,
+            types,
+            states: HashMap::new(),
+        }
+    }
+
+    /// Evaluate the initializer of a static or constant and store the result
+    /// in the symbol table
+    fn eval_symbol(&mut self, name: Ident, value: &Node<Expression>) -> Option<Value> {
+        if let Some(State::Done(val)) = self.states.get(&name).cloned() {
+            return val;
+        }
+
+        self.states.insert(name, State::InProgress);
+        let val = self.eval(value);
+        self.states.insert(name, State::Done(val));
+
+        if let Some(val) = val {
+            self.sytbl.set_constant_value(&name, val);
+        }
+
+        val
+    }
+
+    /// Evaluate an expression, returns `None` if an error has been reported
+    fn eval(&mut self, expr: &Node<Expression>) -> Option<Value> {
+        match **expr {
+            Expression::Literal { val } => Some(val),
+            Expression::Variable { ref name } => self.eval_variable(name),
+            Expression::Group(ref inner) => self.eval(inner),
+            Expression::Infix {
+                op,
+                ref lhs,
+                ref rhs,
+            } => self.eval_infix(op, lhs, rhs, expr),
+            Expression::Prefix { op, ref item } => self.eval_prefix(op, item, expr),
+            Expression::If {
+                ref cond,
+                ref conseq,
+                ref altern,
+            } => match self.eval(cond)? {
+                Value::Bool(true) => self.eval_block(conseq),
+                Value::Bool(false) => {
+                    // The type checker makes sure there's an `else` as
+                    // constants can't be `()`
+                    let altern = altern.as_ref().expect("`if` without `else` in a constant");
+                    self.eval_block(altern)
+                }
+                val => panic!("`if` condition is not a bool: {:?}", val),
+            },
+            _ => {
+                fatal_at!("this expression can't be evaluated at compile time"; expr);
+                None
+            }
+        }
+    }
+
+    fn eval_block(&mut self, block: &Node<Block>) -> Option<Value> {
+        if let Some(stmt) = block.stmts.first() {
+            fatal_at!("statements are not allowed in constant expressions"; stmt);
+            return None;
+        }
+
+        self.eval(&block.expr)
+    }
+
+    fn eval_variable(&mut self, name: &Node<Ident>) -> Option<Value> {
+        match self.sytbl.lookup_symbol(name) {
+            Some(Symbol::Constant { ref value, .. }) => match self.states.get(name).cloned() {
+                Some(State::InProgress) => {
+                    fatal_at!("cycle detected when evaluating constant `{}`", name; name);
+                    None
+                }
+                Some(State::Done(val)) => val,
+                None => self.eval_symbol(**name, value),
+            },
+            Some(Symbol::Static { .. }) => {
+                fatal_at!("cannot use static `{}` in a constant expression", name; name);
+                None
+            }
+            _ => panic!("`{}` is neither a static nor a constant", name),
+        }
+    }
+
+    fn eval_infix(
+        &mut self,
+        op: BinOp,
+        lhs: &Node<Expression>,
+        rhs: &Node<Expression>,
+        expr: &Node<Expression>,
+    ) -> Option<Value> {
+        let lhs_val = self.eval(lhs)?;
+
+        // `&&` and `||` only evaluate the right-hand side if needed
+        match (op, lhs_val) {
+            (BinOp::And, Value::Bool(false)) => return Some(Value::Bool(false)),
+            (BinOp::Or, Value::Bool(true)) => return Some(Value::Bool(true)),
+            (BinOp::And, _) | (BinOp::Or, _) => return self.eval(rhs),
+            _ => {}
+        }
+
+        let rhs_val = self.eval(rhs)?;
+
+        match (lhs_val, rhs_val) {
+            (Value::Int(..), Value::Int(..)) => {
+                let ty = self.types.type_of(lhs);
+                let a = int_value(lhs_val);
+                let b = int_value(rhs_val);
+
+                if op.get_type() == BinOpType::Comparison {
+                    return Some(Value::Bool(compare(op, a, b)));
+                }
+
+                let result = match op {
+                    BinOp::Add => a.checked_add(b),
+                    BinOp::Sub => a.checked_sub(b),
+                    BinOp::Mul => a.checked_mul(b),
+                    BinOp::Div | BinOp::Mod if b == 0 => {
+                        fatal_at!("attempt to divide by zero in a constant expression"; expr);
+                        return None;
+                    }
+                    BinOp::Div => a.checked_div(b),
+                    BinOp::Mod => a.checked_rem(b),
+                    BinOp::Pow if b < 0 || b > i128::from(u32::MAX) => None,
+                    BinOp::Pow => a.checked_pow(b as u32),
+                    BinOp::Shl | BinOp::Shr if b < 0 || b >= i128::from(bits(ty)) => None,
+                    // Bits shifted out on the left are lost
+                    BinOp::Shl => Some(truncate(((a as u128) << b) as i128, ty)),
+                    BinOp::Shr => Some(a >> b),
+                    BinOp::BitAnd => Some(a & b),
+                    BinOp::BitOr => Some(a | b),
+                    BinOp::BitXor => Some(a ^ b),
+                    _ => panic!("invalid integer operation: {}", op),
+                };
+
+                self.int_result(result, expr)
+            }
+            (Value::Float(a), Value::Float(b)) => Some(match op {
+                BinOp::Add => Value::Float(a + b),
+                BinOp::Sub => Value::Float(a - b),
+                BinOp::Mul => Value::Float(a * b),
+                BinOp::Div => Value::Float(a / b),
+                _ if op.get_type() == BinOpType::Comparison => Value::Bool(compare(op, a, b)),
+                _ => panic!("invalid float operation: {}", op),
+            }),
+            (Value::Bool(a), Value::Bool(b)) => Some(Value::Bool(match op {
+                BinOp::BitAnd => a & b,
+                BinOp::BitOr => a | b,
+                BinOp::BitXor => a ^ b,
+                _ => panic!("invalid bool operation: {}", op),
+            })),
+            (lhs, rhs) => panic!("invalid operands: {:?} {} {:?}", lhs, op, rhs),
+        }
+    }
+
+    fn eval_prefix(
+        &mut self,
+        op: UnOp,
+        item: &Node<Expression>,
+        expr: &Node<Expression>,
+    ) -> Option<Value> {
+        let val = self.eval(item)?;
+
+        match (op, val) {
+            (UnOp::Neg, Value::Int(..)) => {
+                let a = int_value(val);
+                self.int_result(Some(-a), expr)
+            }
+            (UnOp::Neg, Value::Float(f)) => Some(Value::Float(-f)),
+            (UnOp::Not, Value::Bool(b)) => Some(Value::Bool(!b)),
+            (UnOp::Not, Value::Int(..)) => {
+                let ty = self.types.type_of(item);
+                let a = int_value(val);
+                self.int_result(Some(truncate(!a, ty)), expr)
+            }
+            (op, val) => panic!("invalid operand: {}{:?}", op, val),
+        }
+    }
+
+    /// Check that the result of an integer operation fits into the
+    /// expression's type
+    fn int_result(&self, result: Option<i128>, expr: &Node<Expression>) -> Option<Value> {
+        let ty = self.types.type_of(expr);
+        let (min, max) = ty.int_range();
+
+        match result {
+            Some(i) if min <= i && i <= max => Some(Value::Int(i, None)),
+            _ => {
+                fatal_at!("arithmetic overflow in a constant expression of type `{}`", ty; expr);
+                None
+            }
+        }
+    }
+}
+
+/// The value of an integer
+fn int_value(val: Value) -> i128 {
+    match val {
+        Value::Int(i, _) => i,
+        _ => panic!("{:?} is not an integer", val),
+    }
+}
+
+/// The number of bits of an integer type
+fn bits(ty: Type) -> u32 {
+    ty.narrow_bits().unwrap_or(64)
+}
+
+/// Cut an integer down to the bits of its type (keeping the sign of signed
+/// types)
+fn truncate(i: i128, ty: Type) -> i128 {
+    let bits = bits(ty);
+    let unsigned = (i as u128) & ((1 << bits) - 1);
+
+    if ty.is_signed() && unsigned >> (bits - 1) == 1 {
+        unsigned as i128 - (1 << bits)
+    } else {
+        unsigned as i128
+    }
+}
+
+fn compare<T: PartialOrd>(op: BinOp, a: T, b: T) -> bool {
+    match op {
+        BinOp::EqEq => a == b,
+        BinOp::Ne => a != b,
+        BinOp::Lt => a < b,
+        BinOp::Le => a <= b,
+        BinOp::Ge => a >= b,
+        BinOp::Gt => a > b,
+        _ => panic!("not a comparison: {}", op),
+    }
+}
+
+/// Evaluate the initializers of all statics and constants
+pub fn run(program: &[Node<Symbol>]) {
+    let session = session();
+    let mut eval = ConstEval::new(&session.symbol_table, &session.type_table);
+
+    for symbol in program {
+        match **symbol {
+            Symbol::Static {
+                ref binding,
+                ref value,
+                ..
+            }
+            | Symbol::Constant {
+                ref binding,
+                ref value,
+                ..
+            } => {
+                eval.eval_symbol(*binding.name, value);
+            }
+            _ => {}
+        }
+    }
+
+    session.abort_if_errors();
+}
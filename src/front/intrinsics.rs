@@ -0,0 +1,46 @@
+//! Intrinsics: functions that are built into the compiler
+//!
+//! Intrinsics are called like regular functions but aren't declared in the
+//! program. Instead, the back ends implement them directly. A function
+//! declared in the program shadows an intrinsic with the same name.
+
+use driver::interner::Ident;
+use driver::session;
+use front::ast::Type;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Intrinsic {
+    /// `print_str("...")`: write a string literal to stdout
+    PrintStr,
+}
+
+impl Intrinsic {
+    /// Look up the intrinsic a call to `name` refers to
+    ///
+    /// Returns `None` if there's no such intrinsic or if the program declares
+    /// a symbol with this name.
+    pub fn lookup(name: Ident) -> Option<Intrinsic> {
+        if session().symbol_table.lookup_symbol(&name).is_some() {
+            return None;
+        }
+
+        match &*name {
+            "print_str" => Some(Intrinsic::PrintStr),
+            _ => None,
+        }
+    }
+
+    /// The types of the intrinsic's arguments
+    pub fn arg_types(self) -> &'static [Type] {
+        match self {
+            Intrinsic::PrintStr => &[Type::Str],
+        }
+    }
+
+    /// The intrinsic's return type
+    pub fn ret_ty(self) -> Type {
+        match self {
+            Intrinsic::PrintStr => Type::Unit,
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use driver::codemap::{BytePos, Loc};
 use driver::interner::Ident;
 use driver::session;
-use front::ast::{BinOp, Spanned, UnOp};
+use front::ast::{BinOp, Doc, DocKind, Span, Spanned, UnOp};
 use front::tokens::{lookup_keyword, Token};
 use std::borrow::ToOwned;
 use std::str::CharIndices;
@@ -85,6 +85,11 @@ impl<'a> Lexer<'a> {
         debug!("Moved on to {:?}", self.curr)
     }
 
+    /// The character after the current one
+    fn nextc(&self) -> Option<char> {
+        self.source[self.pos..].chars().nth(1)
+    }
+
     /// An escaped representation of the current character
     fn curr_escaped(&self) -> String {
         match self.curr {
@@ -155,60 +160,294 @@ impl<'a> Lexer<'a> {
         self.eat_all(|c| *c != '\n');
     }
 
+    /// Tokenize a line comment. Doc comments (`/// ...` and `//! ...`) are
+    /// kept, other comments are skipped.
+    fn tokenize_line_comment(&mut self) -> Option<Token> {
+        // `////` starts a regular comment
+        let kind = match (self.curr, self.nextc()) {
+            (Some('/'), next) if next != Some('/') => DocKind::Outer,
+            (Some('!'), _) => DocKind::Inner,
+            _ => {
+                self.skip_comment();
+                return None;
+            }
+        };
+        self.bump(); // '/' or '!' matched, move on
+
+        let text = self.collect(|c| *c != '\n');
+        Some(Token::DocComment(Doc {
+            kind,
+            text: Ident::from_str(text),
+        }))
+    }
+
+    /// Skip over a block comment. Block comments may be nested.
+    fn skip_block_comment(&mut self, start: Loc) {
+        // The opening `/*` has been matched already
+        let mut depth = 1;
+
+        while depth > 0 {
+            match (self.curr, self.nextc()) {
+                (Some('/'), Some('*')) => {
+                    self.bump();
+                    depth += 1;
+                }
+                (Some('*'), Some('/')) => {
+                    self.bump();
+                    depth -= 1;
+                }
+                (Some('\n'), _) => self.new_line(),
+                (Some(_), _) => {}
+                (None, _) => {
+                    fatal_at!("unterminated block comment"; start);
+                    session().abort()
+                }
+            }
+
+            self.bump();
+        }
+    }
+
     /// Tokenize an identifier
     fn tokenize_ident(&mut self) -> Token {
         debug!("tokenizing an ident");
 
         let ident = self.collect(|c| c.is_alphabetic() || c.is_numeric() || *c == '_');
 
-        // Check whether it's a keyword or an identifier
+        // Check whether it's a keyword, a wildcard or an identifier
         if let Some(kw) = lookup_keyword(ident) {
             Token::Keyword(kw)
+        } else if ident == "_" {
+            Token::Underscore
         } else {
             Token::Ident(Ident::from_str(ident))
         }
     }
 
-    /// Tokenize an integer
-    fn tokenize_integer(&mut self) -> Token {
+    /// Tokenize an integer or a float literal (`1.5`)
+    ///
+    /// Integers may be written in hex (`0xFF`), octal (`0o77`) or binary
+    /// (`0b1010`) and may contain underscores (`1_000`).
+    fn tokenize_number(&mut self) -> Token {
         debug!("tokenizing a digit");
 
-        let integer_str = self.collect(|c| c.is_numeric());
-        let integer = match integer_str.parse() {
+        let lo = self.pos;
+
+        let radix = match (self.curr, self.nextc()) {
+            (Some('0'), Some('x')) => 16,
+            (Some('0'), Some('o')) => 8,
+            (Some('0'), Some('b')) => 2,
+            _ => 10,
+        };
+
+        let digits = if radix == 10 {
+            self.collect(|c| c.is_ascii_digit() || *c == '_')
+        } else {
+            self.bump();
+            self.bump();
+
+            // Collect invalid digits too so we can report them
+            self.collect(|c| c.is_ascii_alphanumeric() || *c == '_')
+        };
+
+        // A float literal needs digits after the decimal point. This keeps
+        // `1..2` and `a.0` intact.
+        if radix == 10 && self.curr == Some('.') && self.nextc().is_some_and(|c| c.is_ascii_digit())
+        {
+            self.bump();
+            self.eat_all(|c| c.is_ascii_digit() || *c == '_');
+
+            let float_str = &self.source[lo..self.pos];
+            return match float_str.replace('_', "").parse::<f64>() {
+                Ok(f) => Token::Float(f.to_bits()),
+                Err(_) => self.fatal(format!("invalid float literal: `{}`", float_str)),
+            };
+        }
+
+        let spelling = &self.source[lo..self.pos];
+        let span = Span {
+            pos: lo as u32,
+            len: (self.pos - lo) as u32,
+        };
+
+        let digits = digits.replace('_', "");
+        if digits.is_empty() {
+            fatal_at!("no valid digits found for number: `{}`", spelling; span);
+            session().abort()
+        }
+        if !digits.chars().all(|c| c.is_digit(radix)) {
+            fatal_at!("invalid digit for a base {} literal: `{}`", radix, spelling; span);
+            session().abort()
+        }
+
+        let integer = match u64::from_str_radix(&digits, radix) {
             Ok(i) => i,
-            Err(_) => self.fatal(format!("invalid integer: `{}`", integer_str)),
+            Err(_) => {
+                fatal_at!("integer literal is too large: `{}`", spelling; span);
+                session().abort()
+            }
+        };
+
+        Token::Int(integer, Ident::from_str(spelling))
+    }
+
+    /// Whether the quote we're at starts a label (`'a`) and not a char
+    /// literal (`'a'`)
+    fn at_label(&self) -> bool {
+        let mut chars = self.source[self.pos..].chars().skip(1);
+
+        match (chars.next(), chars.next()) {
+            (Some(c), next) => (c.is_alphabetic() || c == '_') && next != Some('\''),
+            (None, _) => false,
+        }
+    }
+
+    /// Tokenize a loop label (`'outer`)
+    fn tokenize_label(&mut self) -> Token {
+        debug!("tokenizing a label");
+
+        self.bump(); // '\'' matched, move on
+
+        let label = self.collect(|c| c.is_alphabetic() || c.is_numeric() || *c == '_');
+        Token::Label(Ident::from_str(label))
+    }
+
+    /// Read an escape sequence starting at the current backslash
+    ///
+    /// Supports `\n`, `\r`, `\t`, `\\`, `\0`, `\'`, `\"`, `\x41` (up to
+    /// `\x7F`) and `\u{..}`.
+    fn read_escape(&mut self) -> char {
+        self.bump(); // '\\' matched, move on
+
+        let c = match self.curr {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('\\') => '\\',
+            Some('0') => '\0',
+            Some('\'') => '\'',
+            Some('"') => '"',
+            Some('x') => {
+                self.bump();
+                return self.read_hex_escape();
+            }
+            Some('u') => {
+                self.bump();
+                return self.read_unicode_escape();
+            }
+            Some(c) => self.fatal(format!(
+                "unsupported or invalid escape sequence: \\{}",
+                c.escape_default()
+            )),
+            None => self.fatal("expected escaped char, found EOF"),
         };
+        self.bump(); // Matched the escaped character, move along
 
-        Token::Int(integer)
+        c
     }
 
-    /// Tokenize a character. Correctly handles escaped newlines and escaped single quotes
+    /// Read the two hex digits of a `\x41` escape
+    fn read_hex_escape(&mut self) -> char {
+        let mut value = 0;
+        for _ in 0..2 {
+            match self.curr.and_then(|c| c.to_digit(16)) {
+                Some(digit) => value = value * 16 + digit,
+                None => self.fatal("invalid hex escape: expected two hex digits"),
+            }
+            self.bump();
+        }
+
+        if value > 0x7F {
+            self.fatal(format!("out of range hex escape: `\\x{:X}`", value))
+        }
+
+        value as u8 as char
+    }
+
+    /// Read the braced code point of a `\u{..}` escape
+    fn read_unicode_escape(&mut self) -> char {
+        self.expect('{');
+
+        let digits = self.collect(|c| c.is_ascii_hexdigit());
+        if digits.is_empty() || digits.len() > 6 {
+            self.fatal("invalid unicode escape: expected 1 to 6 hex digits")
+        }
+
+        let value = u32::from_str_radix(digits, 16).unwrap();
+        let c = std::char::from_u32(value).unwrap_or_else(|| {
+            self.fatal(format!(
+                "invalid unicode character escape: `\\u{{{}}}`",
+                digits
+            ))
+        });
+
+        self.expect('}');
+
+        c
+    }
+
+    /// Tokenize a character. Handles all escape sequences of `read_escape`
     fn tokenize_char(&mut self) -> Token {
         debug!("tokenizing a char");
 
         self.bump(); // '\'' matched, move on
 
-        let c = self.curr.unwrap_or_else(|| {
-            self.fatal("expected a char, found EOF");
-        });
-        let tok = if c == '\\' {
-            // Escaped char, let's take a look on one more char
-            self.bump();
-            match self.curr {
-                Some('n') => Token::Char('\n'),
-                Some('\'') => Token::Char('\''),
-                Some(c) => self.fatal(format!("unsupported or invalid escape sequence: \\{}", c)),
-                None => self.fatal("expected escaped char, found EOF"),
+        let c = match self.curr {
+            Some('\\') => self.read_escape(),
+            Some(c) => {
+                self.bump(); // Matched a character, move along
+                c
             }
-        } else {
-            Token::Char(c)
+            None => self.fatal("expected a char, found EOF"),
         };
-        self.bump(); // Matched a (possibly escaped) character, move along
 
         // Match closing quote
         self.expect('\'');
 
-        tok
+        Token::Char(c)
+    }
+
+    /// Tokenize a string literal. The token holds the unescaped contents.
+    fn tokenize_str(&mut self) -> Token {
+        debug!("tokenizing a string");
+
+        let start = self.get_source();
+        self.bump(); // '"' matched, move on
+
+        let mut contents = String::new();
+        loop {
+            match self.curr {
+                Some('"') => break,
+                Some('\\') => contents.push(self.read_escape()),
+                Some(c) => {
+                    if c == '\n' {
+                        self.new_line();
+                    }
+
+                    contents.push(c);
+                    self.bump();
+                }
+                None => {
+                    fatal_at!("unterminated string literal"; start);
+                    session().abort()
+                }
+            }
+        }
+        self.bump(); // Matched the closing quote, move along
+
+        Token::Str(Ident::from_str(&contents))
+    }
+
+    /// Register the newline we're at with the codemap
+    fn new_line(&mut self) {
+        self.lineno += 1;
+        //let offset = if self.nextch() == Some('\r') { 2 } else { 1 };
+
+        // If the source has been lexed before (e.g. to dump the
+        // tokens), the codemap already knows this line
+        if session().codemap.line_count() < self.lineno {
+            session().codemap.new_line(BytePos(self.pos as u32))
+        }
     }
 
     /// Read the next token and return it
@@ -255,8 +494,12 @@ impl<'a> Lexer<'a> {
             '*' => emit!(self, next: '*' => Token::BinOp(BinOp::Pow);
                                default: Token::BinOp(BinOp::Mul)),
 
-            '/' => emit!(self, next: '/' => { self.skip_comment(); return None };
-                               default: Token::BinOp(BinOp::Div)),
+            '/' => {
+                let start = self.get_source();
+                emit!(self, next: '/' => self.tokenize_line_comment()?,
+                               next: '*' => { self.skip_block_comment(start); return None };
+                               default: Token::BinOp(BinOp::Div))
+            }
 
             '%' => emit!(self, Token::BinOp(BinOp::Mod)),
 
@@ -276,7 +519,8 @@ impl<'a> Lexer<'a> {
                                next: '=' => Token::BinOp(BinOp::Ge);
                                default: Token::BinOp(BinOp::Gt)),
 
-            '=' => emit!(self, next: '=' => Token::BinOp(BinOp::EqEq);
+            '=' => emit!(self, next: '=' => Token::BinOp(BinOp::EqEq),
+                               next: '>' => Token::FatArrow;
                                default: Token::Eq),
 
             '!' => emit!(self, next: '=' => Token::BinOp(BinOp::Ne);
@@ -290,24 +534,38 @@ impl<'a> Lexer<'a> {
 
             '}' => emit!(self, Token::RBrace),
 
+            '[' => emit!(self, Token::LBracket),
+
+            ']' => emit!(self, Token::RBracket),
+
             ',' => emit!(self, Token::Comma),
 
+            '.' => emit!(self, next: '.' => if self.curr == Some('=') {
+                                   self.bump();
+                                   Token::DotDotEq
+                               } else {
+                                   Token::DotDot
+                               };
+                               default: Token::Dot),
+
             ':' => emit!(self, Token::Colon),
 
             ';' => emit!(self, Token::Semicolon),
 
+            '\'' if self.at_label() => self.tokenize_label(),
+
             '\'' => self.tokenize_char(),
 
-            c if c.is_alphabetic() => self.tokenize_ident(),
+            '"' => self.tokenize_str(),
+
+            c if c.is_alphabetic() || c == '_' => self.tokenize_ident(),
 
-            c if c.is_numeric() => self.tokenize_integer(),
+            c if c.is_ascii_digit() => self.tokenize_number(),
 
             c if c.is_whitespace() => {
                 // Skip whitespaces of any type
                 if c == '\n' {
-                    self.lineno += 1;
-                    //let offset = if self.nextch() == Some('\r') { 2 } else { 1 };
-                    session().codemap.new_line(BytePos(self.pos as u32))
+                    self.new_line();
                 }
 
                 self.bump();
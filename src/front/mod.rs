@@ -1,12 +1,15 @@
 //! The front end: parsing + semantic analysis
 
 pub mod ast;
+mod const_eval;
+pub mod intrinsics;
 mod lexer;
 mod parser;
 mod semck;
 mod tokens;
 mod typeck;
 
+pub use self::const_eval::run as eval_constants;
 pub use self::lexer::Lexer;
 pub use self::parser::Parser;
 pub use self::semck::run as semantic_checks;
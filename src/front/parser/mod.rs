@@ -10,41 +10,69 @@
 //! ```ignore
 //! # AST
 //! program:        comment | (symbol comment?)*
-//! symbol:         function | static | constant | impl
+//! symbol:         DOC* (function | extern | static | constant | struct | impl)
 //!
-//! function:       k_fn IDENT LPAREN (binding COMMA)* binding? RPAREN (RARROW TYPE)? block
-//! static:         k_static binding EQ literal
-//! constant:       k_const binding EQ literal
-//! impl:           k_impl LBRACE function* RBRACE
+//! function:       k_fn IDENT LPAREN (arg COMMA)* arg? RPAREN (RARROW type)? block
+//! extern:         k_extern k_fn IDENT LPAREN (arg COMMA)* arg? RPAREN (RARROW type)? SEMICOLON
+//! method:         k_fn IDENT LPAREN k_mut? k_self (COMMA arg)* COMMA? RPAREN (RARROW type)? block
+//! static:         k_static binding EQ expression
+//! constant:       k_const binding EQ expression
+//! struct:         k_struct IDENT LBRACE (binding COMMA)* binding? RBRACE
+//! impl:           k_impl IDENT LBRACE method* RBRACE
 //!
-//! binding:        IDENT COLON TYPE
+//! binding:        IDENT COLON type
+//! arg:            k_mut? binding
+//! type:           TYPE | IDENT | LBRACKET type SEMICOLON INT RBRACKET
 //! block:          LBRACE (declaration | expression SEMICOLON)* expr? RBRACE
 //!
-//! declaration:    k_let binding EQ expression
+//! declaration:    k_let k_mut? IDENT (COLON type)? EQ expression
 //! expression:     call
+//!                 | method_call
 //!                 | binary
 //!                 | unary
+//!                 | cast
 //!                 | literal
+//!                 | array
+//!                 | index
+//!                 | struct_lit
+//!                 | field
 //!                 | if
+//!                 | match
 //!                 | while
+//!                 | for
+//!                 | loop
 //!                 | assign
 //!                 | assign_op
 //!                 | break
+//!                 | continue
 //!                 | return
 //!                 | variable
 //!
-//! literal:        BOOL | INT | CHAR
+//! literal:        BOOL | INT | FLOAT | CHAR | STR
+//! array:          LBRACKET (expression COMMA)* expression RBRACKET
+//!                 | LBRACKET expression SEMICOLON INT RBRACKET
+//! index:          expression LBRACKET expression RBRACKET
+//! struct_lit:     IDENT LBRACE (IDENT COLON expression COMMA)* (IDENT COLON expression)? RBRACE
+//! field:          expression DOT IDENT
 //! variable:       IDENT
 //! assign:         expression EQ expression
 //! assign_op:      expression BINOP EQ expression
 //! return:         k_return (expression)?
 //! call:           IDENT LPAREN (expr COMMA)* expr? RPAREN
+//! method_call:    expression DOT IDENT LPAREN (expr COMMA)* expr? RPAREN
 //! group:          LPAREN expr RPAREN
 //! infix:          expression BINOP expression
 //! prefix:         UNOP expression
-//! if:             k_if expression block (k_else block)?
-//! while:          k_while expression block
-//! break:          k_break
+//! cast:           expression k_as type
+//! if:             k_if expression block (k_else (block | if))?
+//! match:          k_match expression LBRACE (arm COMMA)* arm? RBRACE
+//! arm:            pattern (BITOR pattern)* FATARROW (block COMMA? | expression)
+//! pattern:        literal (DOTDOTEQ literal)? | UNDERSCORE
+//! while:          (LABEL COLON)? k_while expression block
+//! for:            (LABEL COLON)? k_for IDENT k_in expression (DOTDOT | DOTDOTEQ) expression block
+//! loop:           (LABEL COLON)? k_loop block
+//! break:          k_break LABEL? expression?
+//! continue:       k_continue LABEL?
 //!
 //!
 //! # Tokens
@@ -52,20 +80,33 @@
 //!             '<<' | '>>' | '==' | '<' | '<=' | '!=' | '>=' | '>' | '**'
 //! UNOP:       '-' | '!'
 //! IDENT:      [a-Z]+ ( '_' | [a-Z] | [0-9]+ )+
+//! LABEL:      '\'' IDENT
 //! TYPE:       [a-Z]+ ( '_' | [a-Z] | [0-9]+ )+
 //! LPAREN:     '('
 //! RPAREN:     ')'
 //! LBRACE:     '{'
 //! RBRACE:     '}'
+//! LBRACKET:   '['
+//! RBRACKET:   ']'
 //! COMMA:      ','
+//! DOT:        '.'
+//! DOTDOT:     '..'
+//! DOTDOTEQ:   '..='
 //! COLON:      ':'
 //! SEMICOLON:  ';'
 //! RARROW:     '->'
+//! FATARROW:   '=>'
 //! EQ:         '='
+//! UNDERSCORE: '_'
 //!
 //! BOOL:       'true' | 'false'
-//! INT:        [0-9]+
-//! CHAR:       '\'' ( [a-z] | [A-Z] | '\n' ) '\''
+//! INT:        [0-9] [0-9_]* | '0x' [0-9a-fA-F_]+ | '0o' [0-7_]+ | '0b' [01_]+
+//! FLOAT:      [0-9] [0-9_]* '.' [0-9] [0-9_]*
+//! CHAR:       '\'' ( CHARACTER | ESCAPE ) '\''
+//! STR:        '"' ( CHARACTER | ESCAPE )* '"'
+//! ESCAPE:     '\n' | '\r' | '\t' | '\\' | '\0' | '\'' | '\"' |
+//!             '\x' [0-7] [0-9a-fA-F] | '\u{' [0-9a-fA-F]{1,6} '}'
+//! DOC:        ( '///' | '//!' ) [^\n]*
 //! ```
 
 use driver::interner::Ident;
@@ -74,6 +115,7 @@ use front::ast::*;
 use front::parser::parselet::PARSELET_MANAGER;
 use front::tokens::{Keyword, Token};
 use front::Lexer;
+use std::mem;
 
 mod parselet; // Parselets for the Pratt parser
 mod test;
@@ -81,22 +123,30 @@ mod test;
 pub struct Parser<'a> {
     token: Token,
     span: Span,
+    /// The doc comments in front of the current token
+    docs: Vec<Doc>,
     lexer: Lexer<'a>,
+    /// Whether an identifier followed by `{` starts a struct literal
+    struct_literals: bool,
 }
 
 impl<'a> Parser<'a> {
     // --- The public API -------------------------------------------------------
 
     /// Create a new parser instance
-    pub fn new(mut lx: Lexer<'a>) -> Parser<'a> {
+    pub fn new(lx: Lexer<'a>) -> Parser<'a> {
+        let mut parser = Parser {
+            token: Token::EOF,
+            span: EMPTY_SPAN,
+            docs: Vec::new(),
+            lexer: lx,
+            struct_literals: true,
+        };
+
         // Initialize with first token
-        let first_token = lx.next_token();
+        parser.bump();
 
-        Parser {
-            token: first_token.value,
-            span: first_token.span,
-            lexer: lx,
-        }
+        parser
     }
 
     /// Process all tokens and create an AST
@@ -140,7 +190,16 @@ impl<'a> Parser<'a> {
     fn bump(&mut self) {
         debug!("asking the lexer for the next token");
 
-        let next_token = self.lexer.next_token();
+        let mut next_token = self.lexer.next_token();
+
+        // Collect the doc comments so the next symbol can pick them up. They
+        // are ignored everywhere else.
+        self.docs.clear();
+        while let Token::DocComment(doc) = next_token.value {
+            self.docs.push(doc);
+            next_token = self.lexer.next_token();
+        }
+
         self.token = next_token.value;
         self.span = next_token.span;
 
@@ -180,33 +239,44 @@ impl<'a> Parser<'a> {
         Node::new(ident, span)
     }
 
-    /// Parse a literal
-    fn parse_literal(&mut self) -> Node<Expression> {
-        debug!("parsing a literal");
-
-        let span = self.span;
-        let value = match self.token {
-            Token::Int(i) => Value::Int(i),
-            Token::Char(c) => Value::Char(c),
-            Token::Keyword(Keyword::True) => Value::Bool(true),
-            Token::Keyword(Keyword::False) => Value::Bool(false),
-            _ => self.unexpected_token(Some("a literal")),
+    /// Parse an integer literal used as a length (e.g. in `[int; 3]`)
+    fn parse_length(&mut self) -> u32 {
+        let len = match self.token {
+            Token::Int(i, _) if i <= u64::from(u32::MAX) => i as u32,
+            Token::Int(..) => {
+                fatal_at!("array length is too large"; self.span);
+                session().abort()
+            }
+            _ => self.unexpected_token(Some("an integer")),
         };
         self.bump();
 
-        Node::new(Expression::Literal { val: value }, span)
+        len
     }
 
-    /// Parse a builitin type
+    /// Parse a type
     fn parse_type(&mut self) -> Type {
         debug!("parsing a type");
 
-        let ident = self.parse_ident();
-        let ty: Result<Type, ()> = (*ident).parse();
-        match ty {
-            Ok(ty) => ty,
-            Err(()) => self.unexpected_token(Some("a type")),
+        if self.eat(Token::LBracket) {
+            // An array type: `[elem; len]`
+            let elem = self.parse_type();
+            self.expect(Token::Semicolon);
+            let len = self.parse_length();
+            self.expect(Token::RBracket);
+
+            return Type::array(elem, len);
         }
+
+        let ident = match self.token {
+            Token::Ident(id) => id,
+            _ => self.unexpected_token(Some("a type")),
+        };
+        self.bump();
+
+        // Every name that isn't a builtin type refers to a struct
+        let ty: Result<Type, ()> = (*ident).parse();
+        ty.unwrap_or(Type::Struct(StructType(ident)))
     }
 
     // --- Parse helpers --------------------------------------------------------
@@ -221,7 +291,27 @@ impl<'a> Parser<'a> {
         self.expect(Token::Colon);
         let ty = self.parse_type();
 
-        Node::new(Binding { ty, name }, lo + self.span)
+        Node::new(
+            Binding {
+                ty,
+                name,
+                mutable: false,
+            },
+            lo + self.span,
+        )
+    }
+
+    /// Parse a function argument, which may be declared as `mut`
+    fn parse_arg(&mut self) -> Node<Binding> {
+        // Grammar: k_mut? binding
+        let lo = self.span;
+
+        let mutable = self.eat(Token::Keyword(Keyword::Mut));
+        let mut binding = self.parse_binding();
+        binding.mutable = mutable;
+        binding.span = lo + binding.span;
+
+        binding
     }
 
     /// Parse a block of expressions
@@ -238,40 +328,65 @@ impl<'a> Parser<'a> {
 
         self.expect(Token::LBrace);
 
+        // The block might be part of a condition, but inside of it struct
+        // literals are unambiguous again
+        let struct_literals = mem::replace(&mut self.struct_literals, true);
+
         let mut stmts = vec![];
         let mut expr = None;
 
         // Parse all statements
         loop {
-            // Special cases: declarations, if's and while's
+            while self.eat(Token::Semicolon) {
+                // Eat all semicolons that are remaining
+            }
+
+            // Special cases: declarations, if's and loops
             match self.token {
                 Token::Keyword(Keyword::Let) => {
                     stmts.push(self.parse_declaration());
                     continue;
                 }
 
-                // If and While expressions can appear as statements ...
-                // ... without a trainling semicolon!
-                Token::Keyword(Keyword::If) => {
+                // If and match expressions and loops can appear as
+                // statements ... without a trainling semicolon!
+                Token::Keyword(Keyword::If) | Token::Keyword(Keyword::Match) => {
                     let lo = self.span;
-                    let if_expr = self.parse_if();
+                    let branch_expr = self.parse_expression();
+
+                    if self.token == Token::RBrace {
+                        // It's the block's value
+                        expr = Some(branch_expr);
+                        break;
+                    }
 
                     stmts.push(Node::new(
                         Statement::Expression {
-                            val: Box::new(if_expr),
+                            val: Box::new(branch_expr),
                         },
                         lo + self.span,
                     ));
                     continue;
                 }
 
-                Token::Keyword(Keyword::While) => {
+                Token::Keyword(Keyword::While)
+                | Token::Keyword(Keyword::For)
+                | Token::Keyword(Keyword::Loop)
+                | Token::Label(..) => {
                     let lo = self.span;
-                    let while_expr = self.parse_while();
+                    let loop_expr = self.parse_labeled_loop();
+
+                    if let Expression::Loop { .. } = *loop_expr {
+                        if self.token == Token::RBrace {
+                            // It's the block's value
+                            expr = Some(loop_expr);
+                            break;
+                        }
+                    }
 
                     stmts.push(Node::new(
                         Statement::Expression {
-                            val: Box::new(while_expr),
+                            val: Box::new(loop_expr),
                         },
                         lo + self.span,
                     ));
@@ -281,10 +396,6 @@ impl<'a> Parser<'a> {
                 _ => {}
             }
 
-            while self.eat(Token::Semicolon) {
-                // Eat all semicolons that are remaining
-            }
-
             if self.token == Token::RBrace {
                 // We've reached the end of the block already
                 break;
@@ -314,6 +425,7 @@ impl<'a> Parser<'a> {
         let expr = expr.unwrap_or_else(|| Node::new(Expression::Unit, self.span));
 
         self.expect(Token::RBrace);
+        self.struct_literals = struct_literals;
 
         debug!("done parsing a block");
 
@@ -329,13 +441,21 @@ impl<'a> Parser<'a> {
     // --- Parsing: Statements --------------------------------------------------
 
     fn parse_declaration(&mut self) -> Node<Statement> {
-        // Grammar: k_let binding EQ expression
+        // Grammar: k_let k_mut? IDENT (COLON type)? EQ expression
         debug!("parsing a declaration");
         let lo = self.span;
 
         self.expect(Token::Keyword(Keyword::Let));
 
-        let binding = self.parse_binding();
+        let binding_lo = self.span;
+        let mutable = self.eat(Token::Keyword(Keyword::Mut));
+        let name = self.parse_ident();
+        let ty = if self.eat(Token::Colon) {
+            self.parse_type()
+        } else {
+            Type::Infer
+        };
+        let binding = Node::new(Binding { ty, name, mutable }, binding_lo + self.span);
 
         self.expect(Token::Eq);
 
@@ -365,13 +485,17 @@ impl<'a> Parser<'a> {
     fn parse_expression_with_precedence(&mut self, precedence: u32) -> Node<Expression> {
         match self.token {
             Token::Keyword(Keyword::If) => self.parse_if(),
-            Token::Keyword(Keyword::While) => self.parse_while(),
+            Token::Keyword(Keyword::Match) => self.parse_match(),
+            Token::Keyword(Keyword::While)
+            | Token::Keyword(Keyword::For)
+            | Token::Keyword(Keyword::Loop)
+            | Token::Label(..) => self.parse_labeled_loop(),
             Token::Keyword(Keyword::Return) => {
                 let lo = self.span;
 
                 self.bump();
                 // Parse the return value
-                let val = if self.token == Token::RBrace || self.token == Token::Semicolon {
+                let val = if self.at_expression_end() {
                     Node::new(Expression::Unit, self.span)
                 } else {
                     self.parse_expression()
@@ -383,13 +507,48 @@ impl<'a> Parser<'a> {
                 let lo = self.span;
 
                 self.bump();
+                let label = self.parse_label();
+                // Parse the value the loop evaluates to
+                let value = if self.at_expression_end() {
+                    None
+                } else {
+                    Some(Box::new(self.parse_expression()))
+                };
+
+                Node::new(Expression::Break { label, value }, lo + self.span)
+            }
+            Token::Keyword(Keyword::Continue) => {
+                let lo = self.span;
+
+                self.bump();
+                let label = self.parse_label();
 
-                Node::new(Expression::Break, lo + self.span)
+                Node::new(Expression::Continue { label }, lo + self.span)
             }
             _ => self.pratt_parser(precedence),
         }
     }
 
+    /// Whether the current token ends an expression (e.g. a `return` without
+    /// a value)
+    fn at_expression_end(&self) -> bool {
+        matches!(self.token, Token::RBrace | Token::Semicolon | Token::Comma)
+    }
+
+    /// Parse an expression that is followed by a block (e.g. an `if`'s
+    /// condition)
+    ///
+    /// Struct literals aren't allowed here, as `if a { ... }` would be parsed
+    /// as the struct literal `a { ... }` otherwise. They can be wrapped in
+    /// parentheses though.
+    fn parse_condition(&mut self) -> Node<Expression> {
+        let struct_literals = mem::replace(&mut self.struct_literals, false);
+        let cond = self.parse_expression();
+        self.struct_literals = struct_literals;
+
+        cond
+    }
+
     /// The current token's infix precedence
     fn current_precedence(&self) -> u32 {
         match PARSELET_MANAGER.lookup_infix(self.token) {
@@ -439,18 +598,30 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_if(&mut self) -> Node<Expression> {
-        // Grammar: k_if expression block (k_else block)?
+        // Grammar: k_if expression block (k_else (block | if))?
         debug!("parsing an if");
         let lo = self.span;
 
         self.expect(Token::Keyword(Keyword::If));
 
-        let cond = self.parse_expression();
+        let cond = self.parse_condition();
         let conseq = self.parse_block();
-        let altern = if self.eat(Token::Keyword(Keyword::Else)) {
-            Some(self.parse_block())
-        } else {
+        let altern = if !self.eat(Token::Keyword(Keyword::Else)) {
             None
+        } else if self.token == Token::Keyword(Keyword::If) {
+            // `else if` is sugar for an `else` block containing just the `if`
+            let expr = self.parse_if();
+            let span = expr.span;
+
+            Some(Node::new(
+                Block {
+                    stmts: vec![],
+                    expr: Box::new(expr),
+                },
+                span,
+            ))
+        } else {
+            Some(self.parse_block())
         };
 
         Node::new(
@@ -463,19 +634,217 @@ impl<'a> Parser<'a> {
         )
     }
 
-    fn parse_while(&mut self) -> Node<Expression> {
+    fn parse_match(&mut self) -> Node<Expression> {
+        // Grammar: k_match expression LBRACE (arm COMMA)* arm? RBRACE
+        debug!("parsing a match");
+        let lo = self.span;
+
+        self.expect(Token::Keyword(Keyword::Match));
+
+        let expr = self.parse_condition();
+        self.expect(Token::LBrace);
+
+        let mut arms = vec![];
+        while self.token != Token::RBrace {
+            let (arm, is_block) = self.parse_arm();
+            arms.push(arm);
+
+            // Arms with a block as their body don't need a comma
+            if !self.eat(Token::Comma) && !is_block {
+                break;
+            }
+        }
+
+        self.expect(Token::RBrace);
+
+        Node::new(
+            Expression::Match {
+                expr: Box::new(expr),
+                arms,
+            },
+            lo + self.span,
+        )
+    }
+
+    /// Parse a match arm and return whether its body is a block
+    fn parse_arm(&mut self) -> (Arm, bool) {
+        // Grammar: pattern (BITOR pattern)* FATARROW (block COMMA? | expression)
+        let mut patterns = vec![self.parse_pattern()];
+        while self.eat(Token::BinOp(BinOp::BitOr)) {
+            patterns.push(self.parse_pattern());
+        }
+
+        self.expect(Token::FatArrow);
+
+        let is_block = self.token == Token::LBrace;
+        let body = if is_block {
+            self.parse_block()
+        } else {
+            // Store the expression as a block's value
+            let expr = self.parse_expression();
+            let span = expr.span;
+
+            Node::new(
+                Block {
+                    stmts: vec![],
+                    expr: Box::new(expr),
+                },
+                span,
+            )
+        };
+
+        let arm = Arm {
+            patterns,
+            body: Box::new(body),
+        };
+
+        (arm, is_block)
+    }
+
+    fn parse_pattern(&mut self) -> Node<Pattern> {
+        // Grammar: literal (DOTDOTEQ literal)? | UNDERSCORE
+        let lo = self.span;
+
+        if self.eat(Token::Underscore) {
+            return Node::new(Pattern::Wildcard, lo);
+        }
+
+        let (first, hi) = self.parse_pattern_literal();
+        if self.eat(Token::DotDotEq) {
+            let (last, hi) = self.parse_pattern_literal();
+            Node::new(Pattern::Range(first, last), lo + hi)
+        } else {
+            Node::new(Pattern::Literal(first), lo + hi)
+        }
+    }
+
+    /// Parse a (possibly negative) literal in a pattern and return it with
+    /// its span
+    fn parse_pattern_literal(&mut self) -> (Value, Span) {
+        let lo = self.span;
+        let negative = self.eat(Token::BinOp(BinOp::Sub));
+
+        let value = match self.token {
+            Token::Int(i, spelling) if negative => Value::Int(-i128::from(i), Some(spelling)),
+            Token::Int(i, spelling) => Value::Int(i128::from(i), Some(spelling)),
+            Token::Char(c) if !negative => Value::Char(c),
+            Token::Keyword(Keyword::True) if !negative => Value::Bool(true),
+            Token::Keyword(Keyword::False) if !negative => Value::Bool(false),
+            _ => self.unexpected_token(Some("a pattern")),
+        };
+        let span = lo + self.span;
+        self.bump();
+
+        (value, span)
+    }
+
+    /// Parse the label a `break` or `continue` refers to, if any
+    fn parse_label(&mut self) -> Option<Node<Ident>> {
+        if let Token::Label(name) = self.token {
+            let label = Node::new(name, self.span);
+            self.bump();
+
+            Some(label)
+        } else {
+            None
+        }
+    }
+
+    fn parse_labeled_loop(&mut self) -> Node<Expression> {
+        // Grammar: (LABEL COLON)? (while | for | loop)
+        let label = self.parse_label();
+        if label.is_some() {
+            self.expect(Token::Colon);
+        }
+
+        let lo = label.as_ref().map_or(self.span, |label| label.span);
+        let mut expr = match self.token {
+            Token::Keyword(Keyword::While) => self.parse_while(label),
+            Token::Keyword(Keyword::For) => self.parse_for(label),
+            Token::Keyword(Keyword::Loop) => self.parse_loop(label),
+            _ => self.unexpected_token(Some("a loop")),
+        };
+
+        // Include the label in the loop's span
+        expr.span = lo + expr.span;
+
+        expr
+    }
+
+    fn parse_while(&mut self, label: Option<Node<Ident>>) -> Node<Expression> {
         // Grammar: k_while expression block
         debug!("parsing a while");
         let lo = self.span;
 
         self.expect(Token::Keyword(Keyword::While));
-        let cond = self.parse_expression();
+        let cond = self.parse_condition();
         let body = self.parse_block();
 
         Node::new(
             Expression::While {
                 cond: Box::new(cond),
                 body: Box::new(body),
+                label,
+            },
+            lo + self.span,
+        )
+    }
+
+    fn parse_loop(&mut self, label: Option<Node<Ident>>) -> Node<Expression> {
+        // Grammar: k_loop block
+        debug!("parsing a loop");
+        let lo = self.span;
+
+        self.expect(Token::Keyword(Keyword::Loop));
+        let body = self.parse_block();
+
+        Node::new(
+            Expression::Loop {
+                body: Box::new(body),
+                label,
+            },
+            lo + self.span,
+        )
+    }
+
+    fn parse_for(&mut self, label: Option<Node<Ident>>) -> Node<Expression> {
+        // Grammar: k_for IDENT k_in expression (DOTDOT | DOTDOTEQ) expression block
+        debug!("parsing a for");
+        let lo = self.span;
+
+        self.expect(Token::Keyword(Keyword::For));
+
+        // The loop variable counts through the range, so it's always an int
+        let name = self.parse_ident();
+        let span = name.span;
+        let var = Node::new(
+            Binding {
+                ty: Type::Int,
+                name,
+                mutable: false,
+            },
+            span,
+        );
+
+        self.expect(Token::Keyword(Keyword::In));
+        let start = self.parse_condition();
+        let inclusive = match self.token {
+            Token::DotDot => false,
+            Token::DotDotEq => true,
+            _ => self.unexpected_token(Some("`..` or `..=`")),
+        };
+        self.bump();
+        let end = self.parse_condition();
+        let body = self.parse_block();
+
+        Node::new(
+            Expression::For {
+                var: Box::new(var),
+                start: Box::new(start),
+                end: Box::new(end),
+                inclusive,
+                body: Box::new(body),
+                label,
             },
             lo + self.span,
         )
@@ -483,11 +852,56 @@ impl<'a> Parser<'a> {
 
     // --- Parsing: Symbols -----------------------------------------------------
 
-    fn parse_fn(&mut self) -> Node<Symbol> {
-        // Grammar:  k_fn IDENT LPAREN (binding COMMA)* binding? RPAREN (RARROW TYPE)? block
+    /// Parse a function or, if `self_ty` is set, a method of that type
+    fn parse_fn(&mut self, self_ty: Option<Type>) -> Node<Symbol> {
+        // Grammar:  k_fn IDENT LPAREN (arg COMMA)* arg? RPAREN (RARROW type)? block
         debug!("parsing a fn");
         let lo = self.span;
+        let docs = mem::take(&mut self.docs);
+
+        let (ident, bindings, ret_ty) = self.parse_signature(self_ty);
+
+        // Parse the body
+        let body = self.parse_block();
+
+        Node::new(
+            Symbol::Function {
+                name: ident,
+                bindings,
+                ret_ty,
+                body: Box::new(body),
+                docs,
+            },
+            lo + self.span,
+        )
+    }
+
+    fn parse_extern(&mut self) -> Node<Symbol> {
+        // Grammar: k_extern k_fn IDENT LPAREN (arg COMMA)* arg? RPAREN (RARROW type)? SEMICOLON
+        debug!("parsing an extern fn");
+        let lo = self.span;
+        let docs = mem::take(&mut self.docs);
+
+        self.expect(Token::Keyword(Keyword::Extern));
+        let (name, bindings, ret_ty) = self.parse_signature(None);
+        self.expect(Token::Semicolon);
 
+        Node::new(
+            Symbol::Extern {
+                name,
+                bindings,
+                ret_ty,
+                docs,
+            },
+            lo + self.span,
+        )
+    }
+
+    /// Parse a function's name, arguments and return type
+    fn parse_signature(
+        &mut self,
+        self_ty: Option<Type>,
+    ) -> (Node<Ident>, Vec<Node<Binding>>, Type) {
         // Parse `fn <name>`
         self.expect(Token::Keyword(Keyword::Fn));
         let ident = self.parse_ident();
@@ -496,11 +910,19 @@ impl<'a> Parser<'a> {
 
         // Parse the expected arguments
         let mut bindings = vec![];
-        while self.token != Token::RParen {
-            bindings.push(self.parse_binding());
-            if !self.eat(Token::Comma) {
-                break;
+
+        // Methods take the value they're called on as the first argument
+        let mut more = match self_ty {
+            Some(ty) => {
+                bindings.push(self.parse_self(ty));
+                self.eat(Token::Comma)
             }
+            None => true,
+        };
+
+        while more && self.token != Token::RParen {
+            bindings.push(self.parse_arg());
+            more = self.eat(Token::Comma);
         }
 
         self.expect(Token::RParen);
@@ -512,24 +934,58 @@ impl<'a> Parser<'a> {
             Type::Unit
         };
 
-        // Parse the body
-        let body = self.parse_block();
+        (ident, bindings, ret_ty)
+    }
+
+    /// Parse a method's `self` argument
+    fn parse_self(&mut self, ty: Type) -> Node<Binding> {
+        let lo = self.span;
+        let mutable = self.eat(Token::Keyword(Keyword::Mut));
+        let span = self.span;
+
+        match self.token {
+            Token::Ident(id) if &*id == "self" => self.bump(),
+            _ => self.unexpected_token(Some("`self`")),
+        }
+
+        let name = Node::new(Ident::from_str("self"), span);
+        Node::new(Binding { ty, name, mutable }, lo + span)
+    }
+
+    fn parse_impl(&mut self) -> Node<Symbol> {
+        // Grammar: k_impl IDENT LBRACE method* RBRACE
+        debug!("parsing an impl");
+        let lo = self.span;
+        let docs = mem::take(&mut self.docs);
+
+        self.expect(Token::Keyword(Keyword::Impl));
+        let name = self.parse_ident();
+        let self_ty = Type::Struct(StructType(*name));
+
+        self.expect(Token::LBrace);
+
+        let mut methods = vec![];
+        while self.token != Token::RBrace {
+            methods.push(self.parse_fn(Some(self_ty)));
+        }
+
+        self.expect(Token::RBrace);
 
         Node::new(
-            Symbol::Function {
-                name: ident,
-                bindings,
-                ret_ty,
-                body: Box::new(body),
+            Symbol::Impl {
+                name,
+                methods,
+                docs,
             },
             lo + self.span,
         )
     }
 
     fn parse_static(&mut self) -> Node<Symbol> {
-        // Grammar: k_static binding EQ literal
+        // Grammar: k_static binding EQ expression
         debug!("parsing a static");
         let lo = self.span;
+        let docs = mem::take(&mut self.docs);
 
         self.expect(Token::Keyword(Keyword::Static));
 
@@ -537,7 +993,7 @@ impl<'a> Parser<'a> {
 
         self.expect(Token::Eq);
 
-        let value = self.parse_literal();
+        let value = self.parse_expression();
 
         self.expect(Token::Semicolon);
 
@@ -545,15 +1001,17 @@ impl<'a> Parser<'a> {
             Symbol::Static {
                 binding: Box::new(binding),
                 value: Box::new(value),
+                docs,
             },
             lo + self.span,
         )
     }
 
     fn parse_const(&mut self) -> Node<Symbol> {
-        // Grammar: k_const binding EQ literal
+        // Grammar: k_const binding EQ expression
         debug!("parsing a const");
         let lo = self.span;
+        let docs = mem::take(&mut self.docs);
 
         self.expect(Token::Keyword(Keyword::Const));
 
@@ -561,7 +1019,7 @@ impl<'a> Parser<'a> {
 
         self.expect(Token::Eq);
 
-        let value = self.parse_literal();
+        let value = self.parse_expression();
 
         self.expect(Token::Semicolon);
 
@@ -569,20 +1027,47 @@ impl<'a> Parser<'a> {
             Symbol::Constant {
                 binding: Box::new(binding),
                 value: Box::new(value),
+                docs,
             },
             lo + self.span,
         )
     }
 
+    fn parse_struct(&mut self) -> Node<Symbol> {
+        // Grammar: k_struct IDENT LBRACE (binding COMMA)* binding? RBRACE
+        debug!("parsing a struct");
+        let lo = self.span;
+        let docs = mem::take(&mut self.docs);
+
+        self.expect(Token::Keyword(Keyword::Struct));
+        let name = self.parse_ident();
+
+        self.expect(Token::LBrace);
+
+        let mut fields = vec![];
+        while self.token != Token::RBrace {
+            fields.push(self.parse_binding());
+            if !self.eat(Token::Comma) {
+                break;
+            }
+        }
+
+        self.expect(Token::RBrace);
+
+        Node::new(Symbol::Struct { name, fields, docs }, lo + self.span)
+    }
+
     fn parse_symbol(&mut self) -> Node<Symbol> {
-        // Grammar: function | static | constant | impl
+        // Grammar: function | extern | static | constant | struct | impl
         debug!("parsing a symbol");
 
         match self.token {
-            Token::Keyword(Keyword::Fn) => self.parse_fn(),
+            Token::Keyword(Keyword::Fn) => self.parse_fn(None),
+            Token::Keyword(Keyword::Extern) => self.parse_extern(),
             Token::Keyword(Keyword::Static) => self.parse_static(),
             Token::Keyword(Keyword::Const) => self.parse_const(),
-            //Token::Keyword(Keyword::Impl) => unimplemented!(),  // TODO: Implement
+            Token::Keyword(Keyword::Struct) => self.parse_struct(),
+            Token::Keyword(Keyword::Impl) => self.parse_impl(),
             _ => self.unexpected_token(Some("a symbol")),
         }
     }
@@ -5,6 +5,7 @@ use front::ast::*;
 use front::parser::Parser;
 use front::tokens::{Keyword, Token, TokenType};
 use std::collections::HashMap;
+use std::mem;
 
 /// The associativity of an infix operator
 enum Associativity {
@@ -14,9 +15,10 @@ enum Associativity {
 
 /// `RusTiny` operator precedence
 enum Precedence {
-    Call = 13,
-    //Prefix      = 12,
-    Exponent = 11,
+    Call = 14,
+    Exponent = 13,
+    Prefix = 12,
+    Cast = 11,
     Product = 10,
     Sum = 9,
     Shift = 8,
@@ -77,6 +79,7 @@ impl ParseletManager {
         self.register_prefix(TokenType::UnOp, PrefixOperatorParselet);
         self.register_prefix(TokenType::BinOp(BinOp::Sub), PrefixOperatorParselet);
         self.register_prefix(TokenType::LParen, GroupParselet);
+        self.register_prefix(TokenType::LBracket, ArrayParselet);
 
         // Infix parselets
         self.register_binop(TokenType::BinOp(BinOp::Add), Precedence::Sum, Left);
@@ -100,6 +103,9 @@ impl ParseletManager {
         self.register_binop(TokenType::BinOp(BinOp::EqEq), Precedence::Compare, Left);
         self.register_infix(TokenType::Eq, AssignParselet);
         self.register_infix(TokenType::LParen, CallParselet);
+        self.register_infix(TokenType::LBracket, IndexParselet);
+        self.register_infix(TokenType::Dot, FieldParselet);
+        self.register_infix(TokenType::As, CastParselet);
 
         self
     }
@@ -162,6 +168,26 @@ define_prefix!(IdentParselet:
             _ => parser.unexpected_token(Some("an identifier"))
         };
 
+        if parser.token == Token::LBrace && parser.struct_literals {
+            // A struct literal: `Point { x: 1, y: 2 }`
+            parser.bump();
+
+            let mut fields = vec![];
+            while parser.token != Token::RBrace {
+                let name = parser.parse_ident();
+                parser.expect(Token::Colon);
+                fields.push((name, parser.parse_expression()));
+
+                if !parser.eat(Token::Comma) {
+                    break;
+                }
+            }
+
+            parser.expect(Token::RBrace);
+
+            return Node::new(Expression::Struct { name: ident, fields }, span + parser.span);
+        }
+
         Node::new(Expression::Variable { name: ident }, span)
     }
 );
@@ -169,8 +195,10 @@ define_prefix!(IdentParselet:
 define_prefix!(LiteralParselet:
     fn parse(parser, token, span) -> Node<Expression> {
         let value = match token {
-            Token::Int(i) => Value::Int(i),
+            Token::Int(i, spelling) => Value::Int(i128::from(i), Some(spelling)),
+            Token::Float(bits) => Value::Float(f64::from_bits(bits)),
             Token::Char(c) => Value::Char(c),
+            Token::Str(s) => Value::Str(s),
             Token::Keyword(Keyword::True) => Value::Bool(true),
             Token::Keyword(Keyword::False) => Value::Bool(false),
             _ => parser.unexpected_token(Some("a literal"))
@@ -186,7 +214,19 @@ define_prefix!(PrefixOperatorParselet:
     fn parse(parser, token, span) -> Node<Expression> {
         let lo = span;
 
-        let operand = parser.parse_expression();
+        // `-9223372036854775808` is only valid as a whole
+        if let (Token::BinOp(BinOp::Sub), Token::Int(i, spelling)) = (token, parser.token) {
+            if i == i64::MIN.unsigned_abs() {
+                let span = lo + parser.span;
+                parser.bump();
+
+                return Node::new(Expression::Literal {
+                    val: Value::Int(-i128::from(i), Some(spelling))
+                }, span);
+            }
+        }
+
+        let operand = parser.parse_expression_with_precedence(Precedence::Prefix.val());
         let op = match token {
             Token::UnOp(op) => op,
             Token::BinOp(BinOp::Sub) => UnOp::Neg,
@@ -202,13 +242,50 @@ define_prefix!(GroupParselet:
     fn parse(parser, token, span) -> Node<Expression> {
         let lo = span;
 
+        // Struct literals are unambiguous inside of parentheses
+        let struct_literals = mem::replace(&mut parser.struct_literals, true);
         let expr = parser.parse_expression();
+        parser.struct_literals = struct_literals;
+
         parser.expect(Token::RParen);
 
         Node::new(Expression::Group(Box::new(expr)), lo + parser.span)
     }
 );
 
+define_prefix!(ArrayParselet:
+    fn parse(parser, token, span) -> Node<Expression> {
+        let lo = span;
+
+        let first = parser.parse_expression();
+
+        if parser.eat(Token::Semicolon) {
+            // `[elem; len]`
+            let len = parser.parse_length();
+            parser.expect(Token::RBracket);
+
+            return Node::new(Expression::ArrayRepeat {
+                elem: Box::new(first),
+                len
+            }, lo + parser.span);
+        }
+
+        // `[a, b, c]`
+        let mut elems = vec![first];
+        while parser.eat(Token::Comma) {
+            if parser.token == Token::RBracket {
+                break; // Trailing comma
+            }
+
+            elems.push(parser.parse_expression());
+        }
+
+        parser.expect(Token::RBracket);
+
+        Node::new(Expression::Array { elems }, lo + parser.span)
+    }
+);
+
 // --- Infix Parselets ----------------------------------------------------------
 
 pub trait InfixParselet {
@@ -253,7 +330,9 @@ impl InfixParselet for BinaryOperatorParselet {
 
         if parser.token == Token::Eq && op != BinOp::And && op != BinOp::Or {
             parser.bump();
-            let right = parser.parse_expression_with_precedence(precedence);
+            // Like plain assignments, compound assignments bind the whole
+            // right hand side (`a -= b + c` is `a -= (b + c)`)
+            let right = parser.parse_expression();
 
             let hi = right.span;
             Node::new(
@@ -360,3 +439,101 @@ impl InfixParselet for CallParselet {
         Precedence::Call.val()
     }
 }
+
+pub struct IndexParselet;
+
+impl InfixParselet for IndexParselet {
+    fn parse(
+        &self,
+        parser: &mut Parser,
+        left: Node<Expression>,
+        _: Token,
+        _: Span,
+    ) -> Node<Expression> {
+        let lo = left.span;
+
+        let index = parser.parse_expression();
+        parser.expect(Token::RBracket);
+
+        Node::new(
+            Expression::Index {
+                array: Box::new(left),
+                index: Box::new(index),
+            },
+            lo + parser.span,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "IndexParselet"
+    }
+
+    fn precedence(&self) -> u32 {
+        Precedence::Call.val()
+    }
+}
+
+pub struct FieldParselet;
+
+impl InfixParselet for FieldParselet {
+    fn parse(
+        &self,
+        parser: &mut Parser,
+        left: Node<Expression>,
+        _: Token,
+        _: Span,
+    ) -> Node<Expression> {
+        let lo = left.span;
+
+        let field = parser.parse_ident();
+
+        let hi = field.span;
+        Node::new(
+            Expression::Field {
+                object: Box::new(left),
+                field,
+            },
+            lo + hi,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "FieldParselet"
+    }
+
+    fn precedence(&self) -> u32 {
+        Precedence::Call.val()
+    }
+}
+
+pub struct CastParselet;
+
+impl InfixParselet for CastParselet {
+    fn parse(
+        &self,
+        parser: &mut Parser,
+        left: Node<Expression>,
+        _: Token,
+        _: Span,
+    ) -> Node<Expression> {
+        let lo = left.span;
+
+        let ty = parser.parse_type();
+
+        Node::new(
+            Expression::Cast {
+                expr: Box::new(left),
+                ty,
+            },
+            lo + parser.span,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "CastParselet"
+    }
+
+    fn precedence(&self) -> u32 {
+        Precedence::Cast.val()
+    }
+}
@@ -1,5 +1,6 @@
 #![cfg(test)]
 
+use front::ast::pretty::PrettyPrinter;
 use front::ast::*;
 use front::{Lexer, Parser};
 
@@ -44,6 +45,102 @@ fn operator_precedence_prefix_exponent() {
     assert_eq!(*op, BinOp::Pow);
 }
 
+#[test]
+fn operator_precedence_prefix_product() {
+    let ast = parser!("-1 * 2").parse_expression();
+
+    let (op, lhs, _) = ast_assert!(Expression::Infix { op, lhs, rhs } == *ast);
+    assert_eq!(*op, BinOp::Mul);
+
+    let (op, _) = ast_assert!(Expression::Prefix { op, item } == ***lhs);
+    assert_eq!(*op, UnOp::Neg);
+}
+
+#[test]
+fn negative_int_min_literal() {
+    let ast = parser!("-9223372036854775808").parse_expression();
+
+    let val = ast_assert!(Expression::Literal { val } == *ast);
+    assert_eq!(val.as_i64(), i64::MIN);
+}
+
+#[test]
+fn float_literal() {
+    let ast = parser!("1.5 * 2.25").parse_expression();
+
+    let (op, lhs, rhs) = ast_assert!(Expression::Infix { op, lhs, rhs } == *ast);
+    assert_eq!(*op, BinOp::Mul);
+
+    let val = ast_assert!(Expression::Literal { val } == ***lhs);
+    assert_eq!(val.get_ty(), Type::F64);
+    assert_eq!(val.as_i64(), 1.5f64.to_bits() as i64);
+
+    let val = ast_assert!(Expression::Literal { val } == ***rhs);
+    assert_eq!(val.as_i64(), 2.25f64.to_bits() as i64);
+}
+
+#[test]
+fn field_of_int_is_not_a_float() {
+    let ast = parser!("a.b + 1.x").parse_expression();
+
+    let (_, _, rhs) = ast_assert!(Expression::Infix { op, lhs, rhs } == *ast);
+    let (object, field) = ast_assert!(Expression::Field { object, field } == ***rhs);
+    assert_eq!(&***field, "x");
+
+    let val = ast_assert!(Expression::Literal { val } == ***object);
+    assert_eq!(val.get_ty(), Type::Int);
+}
+
+#[test]
+fn int_literal_radixes() {
+    for &(source, value) in &[
+        ("0xFF", 255),
+        ("0x_dead_BEEF", 0xdead_beef),
+        ("0o17", 15),
+        ("0b1010", 10),
+        ("1_000_", 1000),
+    ] {
+        let ast = parser!(source).parse_expression();
+
+        let val = ast_assert!(Expression::Literal { val } == *ast);
+        assert_eq!(val.as_i64(), value);
+    }
+}
+
+#[test]
+fn int_literal_spelling_is_kept() {
+    let source = "\nfn f() -> int {\n    0xFF + 0o17 + 0b1010 + 1_000 + -0x10\n}\n";
+    let ast = parser!(source).parse();
+
+    let mut out = Vec::new();
+    PrettyPrinter::print(&ast, &mut out);
+    assert_eq!(String::from_utf8(out).unwrap(), source);
+}
+
+#[test]
+fn string_literal_escapes() {
+    let ast = parser!(r#"print_str("a\t\\\0\x41\u{e9}\"\n")"#).parse_expression();
+
+    let (_, args) = ast_assert!(Expression::Call { func, args } == *ast);
+    let val = ast_assert!(Expression::Literal { val } == *args[0]);
+    assert_eq!(val.get_ty(), Type::Str);
+    assert!(matches!(*val, Value::Str(s) if &*s == "a\t\\\0A\u{e9}\"\n"));
+}
+
+#[test]
+fn char_literal_escapes() {
+    for &(source, c) in &[
+        (r"'\t'", '\t'),
+        (r"'\x7F'", '\x7F'),
+        (r"'\u{1F600}'", '\u{1F600}'),
+    ] {
+        let ast = parser!(source).parse_expression();
+
+        let val = ast_assert!(Expression::Literal { val } == *ast);
+        assert!(matches!(*val, Value::Char(v) if v == c));
+    }
+}
+
 #[test]
 fn operator_precedence_exponent_product() {
     let ast = parser!("1 * 2 ** 3").parse_expression();
@@ -151,3 +248,321 @@ fn operator_precedence_or_assignment() {
     let (op, _, _) = ast_assert!(Expression::Infix { op, lhs, rhs } == ***rhs);
     assert_eq!(*op, BinOp::Or);
 }
+
+#[test]
+fn operator_precedence_bitand_assign_op() {
+    let ast = parser!("a /= 2 & 3").parse_expression();
+
+    let (op, _, rhs) = ast_assert!(Expression::AssignOp { op, lhs, rhs } == *ast);
+    assert_eq!(*op, BinOp::Div);
+
+    let (op, _, _) = ast_assert!(Expression::Infix { op, lhs, rhs } == ***rhs);
+    assert_eq!(*op, BinOp::BitAnd);
+}
+
+#[test]
+fn operator_precedence_index_prefix() {
+    let ast = parser!("-a[0][1]").parse_expression();
+
+    let (op, item) = ast_assert!(Expression::Prefix { op, item } == *ast);
+    assert_eq!(*op, UnOp::Neg);
+
+    let (array, _) = ast_assert!(Expression::Index { array, index } == ***item);
+    let (array, _) = ast_assert!(Expression::Index { array, index } == ***array);
+
+    let name = ast_assert!(Expression::Variable { name } == ***array);
+    assert_eq!(&***name, "a");
+}
+
+#[test]
+fn operator_precedence_field_prefix() {
+    let ast = parser!("-a.b.c").parse_expression();
+
+    let (op, item) = ast_assert!(Expression::Prefix { op, item } == *ast);
+    assert_eq!(*op, UnOp::Neg);
+
+    let (object, field) = ast_assert!(Expression::Field { object, field } == ***item);
+    assert_eq!(&***field, "c");
+    let (object, field) = ast_assert!(Expression::Field { object, field } == ***object);
+    assert_eq!(&***field, "b");
+
+    let name = ast_assert!(Expression::Variable { name } == ***object);
+    assert_eq!(&***name, "a");
+}
+
+#[test]
+fn operator_precedence_prefix_cast_product() {
+    let ast = parser!("-a as u8 * 2").parse_expression();
+
+    let (op, lhs, _) = ast_assert!(Expression::Infix { op, lhs, rhs } == *ast);
+    assert_eq!(*op, BinOp::Mul);
+
+    let (expr, ty) = ast_assert!(Expression::Cast { expr, ty } == ***lhs);
+    assert_eq!(*ty, Type::U8);
+
+    let (op, _) = ast_assert!(Expression::Prefix { op, item } == ***expr);
+    assert_eq!(*op, UnOp::Neg);
+}
+
+#[test]
+fn chained_casts() {
+    let ast = parser!("a as u8 as int").parse_expression();
+
+    let (expr, ty) = ast_assert!(Expression::Cast { expr, ty } == *ast);
+    assert_eq!(*ty, Type::Int);
+
+    let (expr, ty) = ast_assert!(Expression::Cast { expr, ty } == ***expr);
+    assert_eq!(*ty, Type::U8);
+
+    let name = ast_assert!(Expression::Variable { name } == ***expr);
+    assert_eq!(&***name, "a");
+}
+
+#[test]
+fn struct_literal_in_condition() {
+    let ast = parser!("if a { b } else { P { x: 1 }.x }").parse_expression();
+
+    let (cond, _, altern) = ast_assert!(
+        Expression::If {
+            cond,
+            conseq,
+            altern
+        } == *ast
+    );
+    let name = ast_assert!(Expression::Variable { name } == ***cond);
+    assert_eq!(&***name, "a");
+
+    let altern = altern.as_ref().unwrap();
+    let (object, _) = ast_assert!(Expression::Field { object, field } == **altern.expr);
+    let (name, fields) = ast_assert!(Expression::Struct { name, fields } == ***object);
+    assert_eq!(&***name, "P");
+    assert_eq!(fields.len(), 1);
+}
+
+#[test]
+fn for_range() {
+    let ast = parser!("for i in 0..n + 1 { }").parse_expression();
+
+    let (var, start, end, inclusive, _, label) = ast_assert!(
+        Expression::For {
+            var,
+            start,
+            end,
+            inclusive,
+            body,
+            label
+        } == *ast
+    );
+    assert!(label.is_none());
+    assert_eq!(&**var.name, "i");
+    assert_eq!(var.ty, Type::Int);
+    assert!(!*inclusive);
+
+    let val = ast_assert!(Expression::Literal { val } == ***start);
+    assert_eq!(val.as_i64(), 0);
+
+    let (op, _, _) = ast_assert!(Expression::Infix { op, lhs, rhs } == ***end);
+    assert_eq!(*op, BinOp::Add);
+}
+
+#[test]
+fn for_range_inclusive() {
+    let ast = parser!("for i in a..=b { }").parse_expression();
+
+    let (_, _, end, inclusive, _, _) = ast_assert!(
+        Expression::For {
+            var,
+            start,
+            end,
+            inclusive,
+            body,
+            label
+        } == *ast
+    );
+    assert!(*inclusive);
+
+    let name = ast_assert!(Expression::Variable { name } == ***end);
+    assert_eq!(&***name, "b");
+}
+
+#[test]
+fn labeled_loop() {
+    let ast = parser!("'outer: loop { continue 'outer; }").parse_expression();
+
+    let (body, label) = ast_assert!(Expression::Loop { body, label } == *ast);
+    assert_eq!(&**label.unwrap(), "outer");
+
+    let stmt = &body.stmts[0];
+    let val = ast_assert!(Statement::Expression { val } == **stmt);
+    let label = ast_assert!(Expression::Continue { label } == ***val);
+    assert_eq!(&**label.unwrap(), "outer");
+}
+
+#[test]
+fn label_and_char_literal() {
+    let ast = parser!("while a == 'a' { break 'a }").parse_expression();
+
+    let (cond, body, label) = ast_assert!(Expression::While { cond, body, label } == *ast);
+    assert!(label.is_none());
+
+    let (_, _, rhs) = ast_assert!(Expression::Infix { op, lhs, rhs } == ***cond);
+    let val = ast_assert!(Expression::Literal { val } == ***rhs);
+    assert_eq!(val.get_ty(), Type::Char);
+
+    let (label, value) = ast_assert!(Expression::Break { label, value } == **body.expr);
+    assert_eq!(&**label.unwrap(), "a");
+    assert!(value.is_none());
+}
+
+#[test]
+fn break_with_value() {
+    let ast = parser!("loop { break 'a -1 }").parse_expression();
+
+    let (body, _) = ast_assert!(Expression::Loop { body, label } == *ast);
+    let (label, value) = ast_assert!(Expression::Break { label, value } == **body.expr);
+    assert_eq!(&**label.unwrap(), "a");
+
+    let (op, _) = ast_assert!(Expression::Prefix { op, item } == ***value.as_ref().unwrap());
+    assert_eq!(*op, UnOp::Neg);
+}
+
+#[test]
+fn declaration_without_type() {
+    let ast = parser!("{ let a = 1; let b: u8 = 2; }").parse_block();
+
+    let (binding, _) = ast_assert!(Statement::Declaration { binding, value } == *ast.stmts[0]);
+    assert_eq!(&**binding.name, "a");
+    assert_eq!(binding.ty, Type::Infer);
+
+    let (binding, _) = ast_assert!(Statement::Declaration { binding, value } == *ast.stmts[1]);
+    assert_eq!(binding.ty, Type::U8);
+}
+
+#[test]
+fn mutable_bindings() {
+    let ast = parser!("fn f(mut a: int, b: int) { let mut c = a; }").parse_symbol();
+
+    let (_, bindings, _, body, _) = ast_assert!(
+        Symbol::Function {
+            name,
+            bindings,
+            ret_ty,
+            body,
+            docs
+        } == *ast
+    );
+    assert!(bindings[0].mutable);
+    assert!(!bindings[1].mutable);
+
+    let (binding, _) = ast_assert!(Statement::Declaration { binding, value } == *body.stmts[0]);
+    assert_eq!(&**binding.name, "c");
+    assert!(binding.mutable);
+}
+
+#[test]
+fn const_expression() {
+    let ast = parser!("const A: int = B * 2;").parse_symbol();
+
+    let (_, value, _) = ast_assert!(
+        Symbol::Constant {
+            binding,
+            value,
+            docs
+        } == *ast
+    );
+    let (op, _, _) = ast_assert!(Expression::Infix { op, lhs, rhs } == ***value);
+    assert_eq!(*op, BinOp::Mul);
+}
+
+#[test]
+fn match_arms() {
+    let ast = parser!("match c { 1 | -2 => 3, 'a'..='z' => { 4 } _ => 5 }").parse_expression();
+
+    let (expr, arms) = ast_assert!(Expression::Match { expr, arms } == *ast);
+    let name = ast_assert!(Expression::Variable { name } == ***expr);
+    assert_eq!(&***name, "c");
+    assert_eq!(arms.len(), 3);
+
+    assert_eq!(arms[0].patterns.len(), 2);
+    assert!(matches!(
+        *arms[0].patterns[0],
+        Pattern::Literal(Value::Int(1, _))
+    ));
+    assert!(matches!(
+        *arms[0].patterns[1],
+        Pattern::Literal(Value::Int(-2, _))
+    ));
+    assert!(matches!(
+        *arms[1].patterns[0],
+        Pattern::Range(Value::Char('a'), Value::Char('z'))
+    ));
+    assert!(matches!(*arms[2].patterns[0], Pattern::Wildcard));
+
+    // Expressions are stored as the value of an arm's block
+    let val = ast_assert!(Expression::Literal { val } == **arms[2].body.expr);
+    assert!(matches!(*val, Value::Int(5, _)));
+}
+
+#[test]
+fn else_if() {
+    let ast = parser!("if a { 1 } else if b { 2 } else { 3 }").parse_expression();
+
+    let (_, _, altern) = ast_assert!(
+        Expression::If {
+            cond,
+            conseq,
+            altern
+        } == *ast
+    );
+    let altern = altern.as_ref().unwrap();
+
+    // The nested `if` is the only content of the `else` block
+    assert!(altern.stmts.is_empty());
+    let (cond, _, altern) = ast_assert!(
+        Expression::If {
+            cond,
+            conseq,
+            altern
+        } == **altern.expr
+    );
+    let name = ast_assert!(Expression::Variable { name } == ***cond);
+    assert_eq!(&***name, "b");
+    assert!(altern.is_some());
+}
+
+#[test]
+fn doc_comments() {
+    let ast = parser!(
+        "//! The program\n/// A constant\n//// Not a doc comment\nconst A: int = 1;\n\
+         /* A /* nested */ block comment */\nfn f() { /// Ignored\n }"
+    )
+    .parse();
+
+    let docs = ast[0].docs();
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].kind, DocKind::Inner);
+    assert_eq!(&*docs[0].text, " The program");
+    assert_eq!(docs[1].kind, DocKind::Outer);
+    assert_eq!(&*docs[1].text, " A constant");
+
+    assert!(ast[1].docs().is_empty());
+}
+
+#[test]
+fn extern_fn() {
+    let ast = parser!("/// From libc\nextern fn putchar(c: int) -> int;").parse_symbol();
+
+    let (name, bindings, ret_ty, docs) = ast_assert!(
+        Symbol::Extern {
+            name,
+            bindings,
+            ret_ty,
+            docs
+        } == *ast
+    );
+    assert_eq!(&***name, "putchar");
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0].ty, Type::Int);
+    assert_eq!(*ret_ty, Type::Int);
+    assert_eq!(docs.len(), 1);
+}
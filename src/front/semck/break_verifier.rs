@@ -1,30 +1,69 @@
-//! Make sure a `break` is always within a while loop
+//! Make sure `break` and `continue` are always within a loop and only refer
+//! to labels of loops they're in
 
+use driver::interner::Ident;
 use front::ast::visit::*;
 use front::ast::*;
 
 struct BreakVerifier {
-    while_depth: u32,
+    /// The labels of the loops we're in (`None` for unlabeled loops)
+    loops: Vec<Option<Ident>>,
 }
 
 impl BreakVerifier {
     fn new() -> BreakVerifier {
-        BreakVerifier { while_depth: 0 }
+        BreakVerifier { loops: Vec::new() }
+    }
+
+    fn check_jump(&self, keyword: &str, label: &Option<Node<Ident>>, expr: &Node<Expression>) {
+        match *label {
+            Some(ref label) => {
+                if !self.loops.contains(&Some(**label)) {
+                    fatal_at!("use of undeclared label `'{}`", label; label);
+                }
+            }
+            None => {
+                if self.loops.is_empty() {
+                    fatal_at!("`{}` outside of loop", keyword; expr);
+                }
+            }
+        }
+    }
+
+    fn visit_loop(&mut self, label: &Option<Node<Ident>>, expr: &Node<Expression>) {
+        self.loops.push(label.as_ref().map(|label| **label));
+        walk_expression(self, expr);
+        self.loops.pop();
     }
 }
 
 impl<'v> Visitor<'v> for BreakVerifier {
     fn visit_expression(&mut self, expr: &'v Node<Expression>) {
         match **expr {
-            Expression::While { .. } => {
-                self.while_depth += 1;
+            Expression::While { ref label, .. } | Expression::Loop { ref label, .. } => {
+                self.visit_loop(label, expr);
             }
-            Expression::Break => {
-                if self.while_depth == 0 {
-                    fatal_at!("`break` outside of loop"; expr);
-                }
+            Expression::For {
+                ref start,
+                ref end,
+                ref body,
+                ref label,
+                ..
+            } => {
+                // The range is evaluated before entering the loop
+                self.visit_expression(start);
+                self.visit_expression(end);
+
+                self.loops.push(label.as_ref().map(|label| **label));
+                self.visit_block(body);
+                self.loops.pop();
+            }
+            Expression::Break { ref label, .. } => {
+                self.check_jump("break", label, expr);
+                walk_expression(self, expr);
             }
-            _ => {}
+            Expression::Continue { ref label } => self.check_jump("continue", label, expr),
+            _ => walk_expression(self, expr),
         }
     }
 }
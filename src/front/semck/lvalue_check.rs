@@ -1,4 +1,5 @@
-//! Make sure the left-hand side of all assignments are variables
+//! Make sure the left-hand side of all assignments are variables, array
+//! elements or struct fields that may be assigned to
 //!
 //! For example, this program wouldn't compile:
 //!
@@ -7,38 +8,121 @@
 //!     1 = false;
 //! }
 //! ```
+//!
+//! Only bindings declared as `mut` and statics can be assigned to, either
+//! directly or through one of their elements or fields. Constants can't be
+//! assigned to at all. As the fix usually is to change the declaration,
+//! these errors are reported there.
+//!
+//! Variables are resolved using the scope table, so this has to run after
+//! the scope table builder.
 
+use driver::codemap::BytePos;
+use driver::interner::Ident;
+use driver::session;
+use driver::symbol_table::SymbolTable;
 use front::ast::visit::*;
 use front::ast::*;
 
-struct LValueCheck;
+struct LValueCheck<'a> {
+    current_scope: Option<NodeId>,
+    sytbl: &'a SymbolTable,
+}
 
-impl LValueCheck {
-    fn new() -> LValueCheck {
-        LValueCheck
+impl<'a> LValueCheck<'a> {
+    fn new(sytbl: &'a SymbolTable) -> LValueCheck<'a> {
+        LValueCheck {
+            current_scope: None,
+            sytbl,
+        }
+    }
+
+    /// The variable an assignable expression refers to (`a` in `a[i].x`)
+    fn assigned_variable(expr: &Node<Expression>) -> Option<&Node<Ident>> {
+        match **expr {
+            Expression::Variable { ref name } => Some(name),
+            // An element of an array we can assign to (`a[i]`, `a[i][j]`, ...)
+            Expression::Index { ref array, .. } => LValueCheck::assigned_variable(array),
+            // A field of a struct we can assign to (`p.x`, `a[i].x`, ...)
+            Expression::Field { ref object, .. } => LValueCheck::assigned_variable(object),
+            Expression::Group(ref expr) => LValueCheck::assigned_variable(expr),
+            _ => None,
+        }
     }
 
     fn check_expr(&self, expr: &Node<Expression>) {
-        if let Expression::Variable { .. } = **expr {
-            return; // Everything's okay
-        } else {
-            fatal_at!("left-hand side of assignment is not a variable"; expr);
+        let name = match LValueCheck::assigned_variable(expr) {
+            Some(name) => name,
+            None => {
+                fatal_at!("left-hand side of assignment is not a variable"; expr);
+                return;
+            }
+        };
+
+        let scope = self
+            .current_scope
+            .expect("checking an assignment without a containing scope");
+        let loc = session().codemap.resolve(BytePos(expr.span.pos));
+
+        if let Some(var) = self.sytbl.resolve_local(scope, name) {
+            if !var.mutable {
+                fatal_at!("`{}` is assigned to in line {}:{}, but isn't declared as `mut`",
+                          name, loc.line, loc.col; var.decl);
+            }
+        } else if let Some(Symbol::Constant { ref binding, .. }) = self.sytbl.lookup_symbol(name) {
+            fatal_at!("cannot assign to constant `{}` in line {}:{}",
+                      name, loc.line, loc.col; &**binding);
         }
     }
 }
 
-impl<'v> Visitor<'v> for LValueCheck {
+impl<'a, 'v> Visitor<'v> for LValueCheck<'a> {
+    fn visit_symbol(&mut self, symbol: &'v Node<Symbol>) {
+        // Initializers of statics and constants have a scope of their own
+        with_reset!(self.current_scope, Some(symbol.id), {
+            walk_symbol(self, symbol);
+        })
+    }
+
+    fn visit_block(&mut self, block: &'v Node<Block>) {
+        with_reset!(self.current_scope, Some(block.id), {
+            walk_block(self, block);
+        })
+    }
+
     fn visit_expression(&mut self, expr: &'v Node<Expression>) {
         match **expr {
             Expression::Assign { ref lhs, .. } | Expression::AssignOp { ref lhs, .. } => {
                 self.check_expr(lhs)
             }
-            _ => walk_expression(self, expr),
+            Expression::For {
+                ref var,
+                ref start,
+                ref end,
+                ref body,
+                ..
+            } => {
+                // The loop variable lives in a scope of its own
+                self.visit_expression(start);
+                self.visit_expression(end);
+
+                with_reset!(self.current_scope, Some(var.id), {
+                    self.visit_block(body);
+                });
+
+                return;
+            }
+            _ => {}
         }
+
+        walk_expression(self, expr)
     }
 }
 
 pub fn run(program: &[Node<Symbol>]) {
-    let mut visitor = LValueCheck::new();
+    let symbol_table = &session().symbol_table;
+    let mut visitor = LValueCheck::new(symbol_table);
     walk_program(&mut visitor, program);
+
+    session().abort_if_errors();
 }
@@ -12,8 +12,8 @@ mod symbol_table_builder;
 
 pub fn run(program: &[Node<Symbol>]) {
     main_presence_check::run(program);
-    lvalue_check::run(program);
     break_verifier::run(program);
     symbol_table_builder::run(program);
     scope_table_builder::run(program);
+    lvalue_check::run(program);
 }
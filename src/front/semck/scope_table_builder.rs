@@ -5,10 +5,13 @@ use driver::session;
 use driver::symbol_table::SymbolTable;
 use front::ast::visit::*;
 use front::ast::*;
+use front::intrinsics::Intrinsic;
 
 struct ScopeTableBuilder<'a> {
     current_scope: Option<NodeId>,
     current_symbol: Option<Ident>,
+    /// The type whose methods we're visiting
+    current_impl: Option<StructType>,
     sytbl: &'a SymbolTable,
 }
 
@@ -17,6 +20,7 @@ impl<'a> ScopeTableBuilder<'a> {
         ScopeTableBuilder {
             current_scope: None,
             current_symbol: None,
+            current_impl: None,
             sytbl,
         }
     }
@@ -61,16 +65,17 @@ impl<'a> ScopeTableBuilder<'a> {
         // Look up the symbol in the symbol table
         let symbol = if let Some(symbol) = self.sytbl.lookup_symbol(name) {
             symbol
+        } else if Intrinsic::lookup(**name).is_some() {
+            return; // Calls an intrinsic
         } else {
             fatal_at!("no such function: `{}`", &name; expr);
             return;
         };
 
         // Verify the symbol is a function
-        if let Symbol::Function { .. } = symbol {
-            return; // Everything's okay
-        } else {
-            fatal_at!("cannot call non-function"; expr)
+        match symbol {
+            Symbol::Function { .. } | Symbol::Extern { .. } => {} // Everything's okay
+            _ => fatal_at!("cannot call non-function"; expr),
         }
     }
 
@@ -85,6 +90,23 @@ impl<'a> ScopeTableBuilder<'a> {
         };
     }
 
+    /// Make sure all structs a type refers to exist
+    fn resolve_type<T>(&self, ty: Type, node: &Node<T>) {
+        match ty {
+            Type::Array(arr) => self.resolve_type(arr.elem(), node),
+            Type::Struct(s) if self.sytbl.lookup_struct(&s.name()).is_none() => {
+                fatal_at!("no such type: `{}`", s.name(); node);
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve_struct(&self, name: &Node<Ident>) {
+        if self.sytbl.lookup_struct(name).is_none() {
+            fatal_at!("no such struct: `{}`", &name; name);
+        }
+    }
+
     fn resolve_declaration(&mut self, binding: &Node<Binding>) {
         let scope = self
             .current_scope
@@ -99,12 +121,60 @@ impl<'a> ScopeTableBuilder<'a> {
 
 impl<'v> Visitor<'v> for ScopeTableBuilder<'v> {
     fn visit_symbol(&mut self, symbol: &'v Node<Symbol>) {
+        if let Symbol::Impl { ref name, .. } = **symbol {
+            if self.sytbl.lookup_struct(name).is_none() {
+                // Don't report the type of every method's `self` as well
+                fatal_at!("no such struct: `{}`", &name; name);
+                return;
+            }
+
+            self.current_impl = Some(StructType(**name));
+            walk_symbol(self, symbol);
+            self.current_impl = None;
+
+            return;
+        }
+
         // Set the current symbol (needed in visit_block)
-        self.current_symbol = Some(symbol.get_ident());
+        self.current_symbol = Some(match self.current_impl {
+            Some(ty) => ty.mangle_method(symbol.get_ident()),
+            None => symbol.get_ident(),
+        });
+
+        match **symbol {
+            Symbol::Function {
+                ref name,
+                ref ret_ty,
+                ..
+            }
+            | Symbol::Extern {
+                ref name,
+                ref ret_ty,
+                ..
+            } => self.resolve_type(*ret_ty, name),
+            Symbol::Static { .. } | Symbol::Constant { .. } => {
+                // The initializer gets an empty scope of its own, so names
+                // used in it resolve to other statics and constants
+                self.sytbl.register_scope(symbol.id).unwrap();
+
+                with_reset!(self.current_scope, Some(symbol.id), {
+                    walk_symbol(self, symbol);
+                });
+
+                return;
+            }
+            _ => {}
+        }
 
         walk_symbol(self, symbol)
     }
 
+    fn visit_binding(&mut self, binding: &'v Node<Binding>) {
+        self.resolve_type(binding.ty, binding);
+
+        walk_binding(self, binding)
+    }
+
     fn visit_block(&mut self, block: &'v Node<Block>) {
         // Register the new block
         self.sytbl.register_scope(block.id).unwrap();
@@ -138,13 +208,57 @@ impl<'v> Visitor<'v> for ScopeTableBuilder<'v> {
 
     fn visit_expression(&mut self, expr: &'v Node<Expression>) {
         match **expr {
-            Expression::Call { ref func, .. } => {
-                self.resolve_call(func);
-                return; // Don't visit sub-expressions
+            Expression::Call { ref func, ref args } => {
+                match ***func {
+                    // A method call. The method depends on the object's type,
+                    // so the type checker resolves it.
+                    Expression::Field { ref object, .. } => self.visit_expression(object),
+                    _ => self.resolve_call(func),
+                }
+
+                // Only visit the arguments as the function's name isn't a
+                // variable
+                for arg in args {
+                    self.visit_expression(arg);
+                }
+                return;
             }
             Expression::Variable { ref name } => {
                 self.resolve_variable(name);
             }
+            Expression::Struct { ref name, .. } => {
+                self.resolve_struct(name);
+            }
+            Expression::Cast { ty, .. } => {
+                self.resolve_type(ty, expr);
+            }
+            Expression::For {
+                ref var,
+                ref start,
+                ref end,
+                ref body,
+                ..
+            } => {
+                // The range is evaluated outside of the loop variable's scope
+                self.visit_expression(start);
+                self.visit_expression(end);
+
+                // The loop variable lives in a scope of its own which is
+                // identified by the variable's id and contains the loop's body
+                let scope = self
+                    .current_scope
+                    .expect("resolving a loop without a containing scope");
+                self.sytbl.register_scope(var.id).unwrap();
+                self.sytbl.set_parent_scope(var.id, scope);
+
+                self.current_scope = Some(var.id);
+                self.visit_binding(var);
+                self.resolve_declaration(var);
+                self.visit_block(body);
+                self.current_scope = Some(scope);
+
+                return;
+            }
             _ => {}
         }
 
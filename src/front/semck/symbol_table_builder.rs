@@ -19,7 +19,31 @@ impl<'v> Visitor<'v> for SymbolTableBuilder<'v> {
     fn visit_symbol(&mut self, symbol: &'v Node<Symbol>) {
         let name = symbol.get_ident();
 
-        match self.sytbl.register_symbol(name, symbol.clone_stripped()) {
+        let registered = match **symbol {
+            Symbol::Struct { .. } => self.sytbl.register_type(name, symbol.clone_stripped()),
+            Symbol::Impl { ref methods, .. } => {
+                // Methods are registered as functions with mangled names
+                let ty = StructType(name);
+
+                for method in methods {
+                    let method_name = method.get_ident();
+                    let mangled = ty.mangle_method(method_name);
+
+                    if self
+                        .sytbl
+                        .register_symbol(mangled, method.clone_stripped())
+                        .is_err()
+                    {
+                        fatal_at!("duplicate method `{}` for `{}`", method_name, name; method);
+                    }
+                }
+
+                return;
+            }
+            _ => self.sytbl.register_symbol(name, symbol.clone_stripped()),
+        };
+
+        match registered {
             Ok(..) => {}
             Err(..) => fatal_at!("cannot redeclare `{}`", &name; symbol),
         };
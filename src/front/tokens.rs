@@ -2,7 +2,7 @@
 
 use driver;
 use driver::interner::Ident;
-use front::ast::{BinOp, UnOp};
+use front::ast::{BinOp, Doc, UnOp};
 use std::fmt;
 
 // --- List of tokens -----------------------------------------------------------
@@ -15,17 +15,34 @@ pub enum Token {
     RParen,
     LBrace,
     RBrace,
+    LBracket,
+    RBracket,
     Comma,
+    Dot,
+    DotDot,
+    DotDotEq,
     Colon,
     Semicolon,
     RArrow,
+    FatArrow,
     Eq,
+    Underscore,
 
     Keyword(Keyword),
     Ident(Ident),
+    /// A loop label (`'outer`), stored without the quote
+    Label(Ident),
     Type(Ident),
-    Int(u32),
+    /// An integer literal along with its spelling in the source (`0xFF`)
+    Int(u64, Ident),
+    /// A float literal, stored as its bit pattern so tokens can be compared
+    /// and hashed
+    Float(u64),
     Char(char),
+    /// A string literal, stored unescaped
+    Str(Ident),
+    /// A doc comment (`/// ...` or `//! ...`)
+    DocComment(Doc),
 
     EOF,
 }
@@ -36,14 +53,19 @@ impl Token {
             Token::BinOp(op) => TokenType::BinOp(op),
             Token::UnOp(..) => TokenType::UnOp,
             Token::LParen => TokenType::LParen,
+            Token::LBracket => TokenType::LBracket,
+            Token::Dot => TokenType::Dot,
             Token::Eq => TokenType::Eq,
 
             Token::Keyword(kw) => match kw {
                 Keyword::True | Keyword::False => TokenType::Literal,
+                Keyword::As => TokenType::As,
                 _ => TokenType::Other,
             },
             Token::Ident(..) => TokenType::Ident,
-            Token::Int(..) | Token::Char(..) => TokenType::Literal,
+            Token::Int(..) | Token::Float(..) | Token::Char(..) | Token::Str(..) => {
+                TokenType::Literal
+            }
 
             _ => TokenType::Other,
         }
@@ -56,7 +78,10 @@ pub enum TokenType {
     Literal,
     Ident,
     LParen,
+    LBracket,
+    Dot,
     Eq,
+    As,
     UnOp,
     BinOp(BinOp),
 
@@ -75,17 +100,28 @@ impl fmt::Display for Token {
             RParen => write!(f, ")"),
             LBrace => write!(f, "{{"),
             RBrace => write!(f, "}}"),
+            LBracket => write!(f, "["),
+            RBracket => write!(f, "]"),
             Comma => write!(f, ","),
+            Dot => write!(f, "."),
+            DotDot => write!(f, ".."),
+            DotDotEq => write!(f, "..="),
             Colon => write!(f, ":"),
             Semicolon => write!(f, ";"),
             RArrow => write!(f, "->"),
+            FatArrow => write!(f, "=>"),
             Eq => write!(f, "="),
+            Underscore => write!(f, "_"),
 
-            Int(i) => write!(f, "{}", i),
+            Int(_, spelling) => write!(f, "{}", spelling),
+            Float(bits) => write!(f, "{:?}", f64::from_bits(bits)),
             Char(c) => write!(f, "{}", c),
+            Str(s) => write!(f, "\"{}\"", s.escape_default()),
+            DocComment(doc) => write!(f, "{}", doc),
 
             Keyword(ref kw) => write!(f, "{}", kw),
             Ident(id) => write!(f, "{}", id),
+            Label(id) => write!(f, "'{}", id),
             Type(ty) => write!(f, "{}", ty),
             Token::EOF => write!(f, "EOF"),
         }
@@ -135,16 +171,25 @@ macro_rules! keywords(
 );
 
 keywords! {
-    Break   => "break",
-    Const   => "const",
-    Else    => "else",
-    False   => "false",
-    Fn      => "fn",
-    If      => "if",
-    Impl    => "impl",
-    Let     => "let",
-    Return  => "return",
-    Static  => "static",
-    True    => "true",
-    While   => "while"
+    As       => "as",
+    Break    => "break",
+    Const    => "const",
+    Continue => "continue",
+    Else     => "else",
+    Extern   => "extern",
+    False    => "false",
+    Fn       => "fn",
+    For      => "for",
+    If       => "if",
+    Impl     => "impl",
+    In       => "in",
+    Let      => "let",
+    Loop     => "loop",
+    Match    => "match",
+    Mut      => "mut",
+    Return   => "return",
+    Static   => "static",
+    Struct   => "struct",
+    True     => "true",
+    While    => "while"
 }
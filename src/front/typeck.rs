@@ -5,11 +5,13 @@
 //! doing that we check some coherence rules (e.g. addition requires two ints).
 //! If types mismatch, an error is reported.
 
+use driver::interner::Ident;
 use driver::session;
 use driver::symbol_table::SymbolTable;
+use driver::type_table::TypeTable;
 use front::ast::visit::*;
 use front::ast::*;
-use std::collections::HashMap;
+use front::intrinsics::Intrinsic;
 
 /// Information about the current function
 struct FunctionContext {
@@ -19,28 +21,40 @@ struct FunctionContext {
     explicit_return: bool,
 }
 
+/// A loop whose `break`s we're checking
+struct LoopContext {
+    label: Option<Ident>,
+    /// The kind of loop (`loop`, `while` or `for`)
+    keyword: &'static str,
+    /// The type of the values the loop is left with, once known
+    ty: Option<Type>,
+}
+
 struct TypeCheck<'a> {
     sytbl: &'a SymbolTable,
-    types: HashMap<NodeId, Type>,
+    types: &'a TypeTable,
     scope: NodeId,
     fctx: FunctionContext,
+    /// The loops we're in, the innermost one last
+    loops: Vec<LoopContext>,
 }
 
 impl<'a> TypeCheck<'a> {
-    fn new(sytbl: &'a SymbolTable) -> TypeCheck<'a> {
+    fn new(sytbl: &'a SymbolTable, types: &'a TypeTable) -> TypeCheck<'a> {
         TypeCheck {
             sytbl,
-            types: HashMap::new(),
+            types,
             scope: NodeId(!0),
             fctx: FunctionContext {
                 return_ty: Type::Unit,
                 explicit_return: false,
             },
+            loops: Vec::new(),
         }
     }
 
     fn type_check<T>(&self, ty: Type, expected: Type, node: &Node<T>) {
-        if ty == Type::Err {
+        if ty == Type::Err || expected == Type::Err {
             // Assume there's nothing wrong to collect more type errors
             return;
         }
@@ -50,6 +64,22 @@ impl<'a> TypeCheck<'a> {
         }
     }
 
+    /// Make sure a function's arguments and return value can be passed around
+    fn check_signature(&self, name: &Node<Ident>, bindings: &[Node<Binding>], ret_ty: Type) {
+        // Arrays live on the caller's stack and there is no way to
+        // pass them around yet
+        for binding in bindings {
+            if let Type::Array(..) = binding.ty {
+                fatal_at!("arrays cannot be passed to functions"; binding);
+            }
+        }
+        match ret_ty {
+            Type::Array(..) => fatal_at!("arrays cannot be returned from functions"; name),
+            Type::Struct(..) => fatal_at!("structs cannot be returned from functions"; name),
+            _ => {}
+        }
+    }
+
     fn check_fn(&mut self, return_ty: Type, body: &Node<Block>) {
         self.fctx = FunctionContext {
             return_ty,
@@ -78,7 +108,7 @@ impl<'a> TypeCheck<'a> {
             }
 
             let block_ty = self.check_expression(&block.expr, expected_ty);
-            self.types.insert(block.id, block_ty);
+            self.types.record(block.id, block_ty);
 
             block_ty
         })
@@ -94,7 +124,20 @@ impl<'a> TypeCheck<'a> {
                     .sytbl
                     .resolve_variable(self.scope, &binding.name)
                     .unwrap();
-                self.check_expression(value, Some(var.ty));
+
+                if var.ty != Type::Infer {
+                    self.check_expression(value, Some(var.ty));
+                    return;
+                }
+
+                // No annotation: the binding gets the type of its initializer
+                let mut ty = self.check_expression(value, None);
+                if ty == Type::Unit {
+                    fatal_at!("cannot infer the type of `{}` from an initializer of type ()", binding.name; &**binding);
+                    ty = Type::Err;
+                }
+
+                self.sytbl.set_type(self.scope, &binding.name, ty);
             }
             Statement::Expression { ref val } => {
                 // Expression can be of any type as the statement always
@@ -113,13 +156,22 @@ impl<'a> TypeCheck<'a> {
     fn check_expression(&mut self, expr: &Node<Expression>, expected: Option<Type>) -> Type {
         let ty = match **expr {
             // Basic expressions:
-            Expression::Literal { ref val } => val.get_ty(),
+            Expression::Literal { ref val } => self.check_literal(val, expected, expr),
             Expression::Variable { ref name } => {
                 let scope = self.scope;
-                self.sytbl
+                let ty = self
+                    .sytbl
                     .resolve_variable(scope, name)
                     .unwrap_or_else(|| panic!("no variable named {}", name))
-                    .ty
+                    .ty;
+
+                if ty == Type::Infer {
+                    // Only happens in the initializer of the binding itself
+                    fatal_at!("cannot infer the type of `{}` from its own initializer", name; expr);
+                    Type::Err
+                } else {
+                    ty
+                }
             }
             // Compound expressions
             Expression::Assign { ref lhs, ref rhs } => self.check_assign(lhs, rhs),
@@ -135,19 +187,63 @@ impl<'a> TypeCheck<'a> {
                 ref op,
                 ref lhs,
                 ref rhs,
-            } => self.check_infix(op, lhs, rhs),
-            Expression::Prefix { ref op, ref item } => self.check_prefix(op, item),
+            } => self.check_infix(op, lhs, rhs, expected),
+            Expression::Prefix { ref op, ref item } => self.check_prefix(op, item, expected),
+            Expression::Cast {
+                expr: ref inner,
+                ty,
+            } => self.check_cast(inner, ty, expr),
             Expression::If {
                 ref cond,
                 ref conseq,
                 ref altern,
             } => self.check_if(cond, conseq, altern, expected),
-            Expression::While { ref cond, ref body } => self.check_while(cond, body),
-            Expression::Break | Expression::Unit => Type::Unit,
+            Expression::Match {
+                expr: ref scrutinee,
+                ref arms,
+            } => self.check_match(scrutinee, arms, expected),
+            Expression::Array { ref elems } => self.check_array(elems, expected),
+            Expression::ArrayRepeat { ref elem, len } => {
+                self.check_array_repeat(elem, len, expected)
+            }
+            Expression::Index {
+                ref array,
+                ref index,
+            } => self.check_index(array, index),
+            Expression::Struct {
+                ref name,
+                ref fields,
+            } => self.check_struct(name, fields, expr),
+            Expression::Field {
+                ref object,
+                ref field,
+            } => self.check_field(object, field),
+            Expression::While {
+                ref cond,
+                ref body,
+                ref label,
+            } => self.check_while(cond, body, label),
+            Expression::Loop {
+                ref body,
+                ref label,
+            } => self.check_loop(body, label, expected),
+            Expression::For {
+                ref var,
+                ref start,
+                ref end,
+                ref body,
+                ref label,
+                ..
+            } => self.check_for(var, (start, end), body, label),
+            Expression::Break {
+                ref label,
+                ref value,
+            } => self.check_break(label, value, expr),
+            Expression::Continue { .. } | Expression::Unit => Type::Unit,
         };
 
         // Store result in type cache
-        self.types.insert(expr.id, ty);
+        self.types.record(expr.id, ty);
 
         if let Some(expected) = expected {
             self.type_check(ty, expected, expr)
@@ -170,7 +266,7 @@ impl<'a> TypeCheck<'a> {
         lhs: &Node<Expression>,
         rhs: &Node<Expression>,
     ) -> Type {
-        self.check_infix(op, lhs, rhs);
+        self.check_infix(op, lhs, rhs, None);
         Type::Unit
     }
 
@@ -182,7 +278,24 @@ impl<'a> TypeCheck<'a> {
     }
 
     fn check_call(&mut self, func: &Node<Expression>, args: &[Node<Expression>]) -> Type {
-        let (bindings, ret_ty) = self.sytbl.lookup_function(&func.unwrap_ident()).unwrap();
+        let (bindings, ret_ty) = match **func {
+            Expression::Field {
+                ref object,
+                ref field,
+            } => match self.check_method(object, field) {
+                // `self` is the object the method is called on
+                Some((bindings, ret_ty)) => (bindings[1..].to_vec(), ret_ty),
+                None => return Type::Err,
+            },
+            _ => {
+                let name = func.unwrap_ident();
+                if let Some(intrinsic) = Intrinsic::lookup(name) {
+                    return self.check_intrinsic_call(intrinsic, func, args);
+                }
+
+                self.sytbl.lookup_function(&name).unwrap()
+            }
+        };
 
         // Check argument count
         if args.len() != bindings.len() {
@@ -198,12 +311,195 @@ impl<'a> TypeCheck<'a> {
         ret_ty
     }
 
-    fn check_infix(&mut self, op: &BinOp, lhs: &Node<Expression>, rhs: &Node<Expression>) -> Type {
+    /// Check a call to an intrinsic
+    ///
+    /// Strings only exist as literals, so `str` arguments have to be string
+    /// literals.
+    fn check_intrinsic_call(
+        &mut self,
+        intrinsic: Intrinsic,
+        func: &Node<Expression>,
+        args: &[Node<Expression>],
+    ) -> Type {
+        let arg_types = intrinsic.arg_types();
+
+        // Check argument count
+        if args.len() != arg_types.len() {
+            fatal_at!("mismatching argument count: expected {}, got {}", arg_types.len(), args.len(); func);
+            return Type::Err;
+        }
+
+        // Check argument types
+        for (arg, &ty) in args.iter().zip(arg_types) {
+            let is_str_literal = matches!(
+                **arg,
+                Expression::Literal {
+                    val: Value::Str(..)
+                }
+            );
+
+            if ty == Type::Str && !is_str_literal {
+                // Don't let a `str` be expected from any other expression
+                let arg_ty = self.check_expression(arg, None);
+                self.type_check(arg_ty, ty, arg);
+            } else {
+                self.check_expression(arg, Some(ty));
+            }
+        }
+
+        intrinsic.ret_ty()
+    }
+
+    /// Look up the arguments and the return type of the method called on
+    /// `object`
+    fn check_method(
+        &mut self,
+        object: &Node<Expression>,
+        method: &Node<Ident>,
+    ) -> Option<(Vec<Node<Binding>>, Type)> {
+        let object_ty = self.check_expression(object, None);
+
+        let signature = match object_ty {
+            Type::Struct(s) => self.sytbl.lookup_function(&s.mangle_method(**method)),
+            Type::Err => return None,
+            _ => None,
+        };
+
+        if signature.is_none() {
+            fatal_at!("no method named `{}` found for type {}", method, object_ty; method);
+        }
+
+        signature
+    }
+
+    /// Type of a literal
+    ///
+    /// Integer literals have no type of their own. They take the expected
+    /// integer type and default to `int`.
+    fn check_literal(&self, val: &Value, expected: Option<Type>, expr: &Node<Expression>) -> Type {
+        match *val {
+            Value::Int(i, _) => {
+                let ty = TypeCheck::expected_int(expected);
+                self.check_int_range(i, ty, expr);
+                ty
+            }
+            Value::Str(..) if expected != Some(Type::Str) => {
+                fatal_at!("string literals can only be passed to `print_str`"; expr);
+                Type::Err
+            }
+            _ => val.get_ty(),
+        }
+    }
+
+    /// The type an integer literal takes if `expected` is expected
+    fn expected_int(expected: Option<Type>) -> Type {
+        match expected {
+            Some(ty) if ty.is_integer() => ty,
+            _ => Type::Int,
+        }
+    }
+
+    /// Check that an integer literal fits into `ty` and return whether it does
+    fn check_int_range<T>(&self, value: i128, ty: Type, node: &Node<T>) -> bool {
+        let (min, max) = ty.int_range();
+        if value < min || value > max {
+            fatal_at!("literal out of range for `{}`", ty; node);
+            return false;
+        }
+
+        true
+    }
+
+    /// Whether an expression is an integer literal (possibly negated or in
+    /// parentheses) which adopts the type of its surroundings
+    fn is_int_literal(expr: &Expression) -> bool {
+        match *expr {
+            Expression::Literal {
+                val: Value::Int(..),
+            } => true,
+            Expression::Group(ref expr) => TypeCheck::is_int_literal(expr),
+            Expression::Prefix {
+                op: UnOp::Neg,
+                ref item,
+            } => TypeCheck::is_int_literal(item),
+            _ => false,
+        }
+    }
+
+    /// Check the operands of a binary operation which have to be of the same
+    /// type and return that type
+    ///
+    /// An integer literal takes the type of the other operand. Thus, if only
+    /// the left hand side is a literal, the right hand side is checked first.
+    /// If the left hand side's type isn't `valid` for the operation, it is
+    /// returned as an error and the right hand side is left unchecked.
+    fn check_operands(
+        &mut self,
+        lhs: &Node<Expression>,
+        rhs: &Node<Expression>,
+        expected: Option<Type>,
+        valid: fn(Type) -> bool,
+    ) -> Result<Type, Type> {
+        if TypeCheck::is_int_literal(lhs) && !TypeCheck::is_int_literal(rhs) {
+            let rhs_ty = self.check_expression(rhs, None);
+            let ty = if rhs_ty.is_integer() {
+                rhs_ty
+            } else {
+                TypeCheck::expected_int(expected)
+            };
+
+            self.check_expression(lhs, Some(ty));
+            self.type_check(rhs_ty, ty, rhs);
+
+            return Ok(ty);
+        }
+
+        let hint = if TypeCheck::is_int_literal(lhs) {
+            expected
+        } else {
+            None
+        };
+        let ty = self.check_expression(lhs, hint);
+
+        if ty == Type::Err {
+            self.check_expression(rhs, None);
+        } else if valid(ty) {
+            self.check_expression(rhs, Some(ty));
+        } else {
+            return Err(ty);
+        }
+
+        Ok(ty)
+    }
+
+    fn check_infix(
+        &mut self,
+        op: &BinOp,
+        lhs: &Node<Expression>,
+        rhs: &Node<Expression>,
+        expected: Option<Type>,
+    ) -> Type {
         match op.get_type() {
             BinOpType::Arithmetic => {
-                self.check_expression(lhs, Some(Type::Int));
-                self.check_expression(rhs, Some(Type::Int));
-                Type::Int
+                // Floats only support the basic arithmetic operations
+                let valid: fn(Type) -> bool = match *op {
+                    BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div => |ty| ty.is_numeric(),
+                    _ => |ty| ty.is_integer(),
+                };
+
+                match self.check_operands(lhs, rhs, expected, valid) {
+                    Ok(ty) => ty,
+                    Err(Type::F64) => {
+                        fatal_at!("binary operation `{}` cannot be applied to f64", op; lhs);
+                        self.check_expression(rhs, Some(Type::F64));
+                        Type::Err
+                    }
+                    Err(ty) => {
+                        self.type_check(ty, Type::Int, lhs);
+                        self.check_expression(rhs, Some(Type::Int));
+                        Type::Int
+                    }
+                }
             }
             BinOpType::Logic => {
                 self.check_expression(lhs, Some(Type::Bool));
@@ -211,34 +507,65 @@ impl<'a> TypeCheck<'a> {
                 Type::Bool
             }
             BinOpType::Bitwise => {
-                // Both Ints and Bools are accepted here, thus we infer the
-                // used type from the left hand side
-                let ty = self.check_expression(lhs, None);
-                if ty == Type::Bool || ty == Type::Int {
-                    self.check_expression(rhs, Some(ty));
-                    ty
-                } else {
-                    fatal_at!("binary operation `{}` cannot be applied to {}", op, ty; lhs);
-                    Type::Err
+                // Both integers and bools are accepted here, thus we infer the
+                // used type from the operands
+                let valid = |ty: Type| ty.is_integer() || ty == Type::Bool;
+                match self.check_operands(lhs, rhs, expected, valid) {
+                    Ok(ty) => ty,
+                    Err(ty) => {
+                        fatal_at!("binary operation `{}` cannot be applied to {}", op, ty; lhs);
+                        Type::Err
+                    }
                 }
             }
             BinOpType::Comparison => {
-                self.check_expression(lhs, Some(Type::Int));
-                self.check_expression(rhs, Some(Type::Int));
+                if let Err(ty) = self.check_operands(lhs, rhs, None, |ty| ty.is_numeric()) {
+                    self.type_check(ty, Type::Int, lhs);
+                    self.check_expression(rhs, Some(Type::Int));
+                }
                 Type::Bool
             }
         }
     }
 
-    fn check_prefix(&mut self, op: &UnOp, item: &Node<Expression>) -> Type {
+    fn check_prefix(&mut self, op: &UnOp, item: &Node<Expression>, expected: Option<Type>) -> Type {
+        let hint = if TypeCheck::is_int_literal(item) {
+            expected
+        } else {
+            None
+        };
+
         match *op {
             UnOp::Neg => {
-                self.check_expression(item, Some(Type::Int));
-                Type::Int
+                let ty = match **item {
+                    Expression::Literal {
+                        val: Value::Int(i, _),
+                    } => {
+                        // The range is checked after negating the literal
+                        // (`-2147483648` is a valid `i32`)
+                        let ty = TypeCheck::expected_int(hint);
+                        self.types.record(item.id, ty);
+                        if ty.is_signed() {
+                            self.check_int_range(-i, ty, item);
+                        }
+                        ty
+                    }
+                    _ => self.check_expression(item, hint),
+                };
+
+                if ty.is_integer() && !ty.is_signed() {
+                    fatal_at!("cannot apply unary operator `-` to type {}", ty; item);
+                    Type::Err
+                } else if ty.is_numeric() || ty == Type::Err {
+                    ty
+                } else {
+                    self.type_check(ty, Type::Int, item);
+                    Type::Err
+                }
             }
             UnOp::Not => {
-                let ty = self.check_expression(item, None);
-                if ty == Type::Bool || ty == Type::Int {
+                let ty = self.check_expression(item, hint);
+                if ty == Type::Bool || ty.is_integer() {
                     ty
                 } else {
                     fatal_at!("unary operation `{}` cannot be applied to {}", op, ty; item);
@@ -248,6 +575,152 @@ impl<'a> TypeCheck<'a> {
         }
     }
 
+    fn check_cast(&mut self, expr: &Node<Expression>, ty: Type, node: &Node<Expression>) -> Type {
+        let expr_ty = self.check_expression(expr, None);
+
+        let valid = match (expr_ty, ty) {
+            (Type::Err, _) => true,
+            (from, to) if to.is_integer() => {
+                from.is_numeric() || from == Type::Bool || from == Type::Char
+            }
+            (from, Type::F64) => from.is_numeric(),
+            (Type::U8, Type::Char) => true,
+            (from, to) => from == to && !from.is_aggregate(),
+        };
+
+        if valid {
+            ty
+        } else {
+            fatal_at!("cannot cast {} as {}", expr_ty, ty; node);
+            Type::Err
+        }
+    }
+
+    /// The expected element type if an array of type `expected` is expected
+    fn expected_elem(expected: Option<Type>) -> Option<Type> {
+        match expected {
+            Some(Type::Array(arr)) => Some(arr.elem()),
+            _ => None,
+        }
+    }
+
+    fn check_array(&mut self, elems: &[Node<Expression>], expected: Option<Type>) -> Type {
+        // Infer the element type from the expectation or the first element
+        let expected_elem = TypeCheck::expected_elem(expected);
+        let elem_ty = self.check_expression(&elems[0], expected_elem);
+
+        if elem_ty == Type::Err || expected_elem.is_some_and(|ty| ty != elem_ty) {
+            // The error has already been reported
+            return Type::Err;
+        }
+
+        for elem in &elems[1..] {
+            self.check_expression(elem, Some(elem_ty));
+        }
+
+        Type::array(elem_ty, elems.len() as u32)
+    }
+
+    fn check_array_repeat(
+        &mut self,
+        elem: &Node<Expression>,
+        len: u32,
+        expected: Option<Type>,
+    ) -> Type {
+        let expected_elem = TypeCheck::expected_elem(expected);
+        let elem_ty = self.check_expression(elem, expected_elem);
+
+        if elem_ty == Type::Err || expected_elem.is_some_and(|ty| ty != elem_ty) {
+            return Type::Err;
+        }
+
+        Type::array(elem_ty, len)
+    }
+
+    fn check_index(&mut self, array: &Node<Expression>, index: &Node<Expression>) -> Type {
+        let array_ty = self.check_expression(array, None);
+        self.check_expression(index, Some(Type::Int));
+
+        match array_ty {
+            Type::Array(arr) => arr.elem(),
+            Type::Err => Type::Err,
+            _ => {
+                fatal_at!("cannot index into a value of type {}", array_ty; array);
+                Type::Err
+            }
+        }
+    }
+
+    fn check_struct(
+        &mut self,
+        name: &Node<Ident>,
+        fields: &[(Node<Ident>, Node<Expression>)],
+        expr: &Node<Expression>,
+    ) -> Type {
+        let ty = StructType(**name);
+        let mut initialized = Vec::new();
+
+        for (field, value) in fields {
+            let field_ty = match ty.field(**field) {
+                Some((field_ty, _)) => Some(field_ty),
+                None => {
+                    fatal_at!("struct `{}` has no field named `{}`", name, field; field);
+                    None
+                }
+            };
+
+            if initialized.contains(&**field) {
+                fatal_at!("field `{}` specified more than once", field; field);
+            }
+            initialized.push(**field);
+
+            self.check_expression(value, field_ty);
+        }
+
+        for field in ty.fields() {
+            if !initialized.contains(&*field.name) {
+                fatal_at!("missing field `{}` in initializer of `{}`", field.name, name; expr);
+            }
+        }
+
+        Type::Struct(ty)
+    }
+
+    fn check_field(&mut self, object: &Node<Expression>, field: &Node<Ident>) -> Type {
+        let object_ty = self.check_expression(object, None);
+
+        if object_ty == Type::Err {
+            return Type::Err;
+        }
+
+        let field_ty = match object_ty {
+            Type::Struct(s) => s.field(**field).map(|(ty, _)| ty),
+            _ => None,
+        };
+
+        field_ty.unwrap_or_else(|| {
+            fatal_at!("no field `{}` on type {}", field, object_ty; field);
+            Type::Err
+        })
+    }
+
+    /// Make sure a struct doesn't contain itself (directly or through other
+    /// structs), which would make it infinitely large
+    fn check_recursion(&self, ty: Type, outer: Ident, name: &Node<Ident>) -> bool {
+        match ty {
+            Type::Array(arr) => self.check_recursion(arr.elem(), outer, name),
+            Type::Struct(s) if s.name() == outer => {
+                fatal_at!("recursive struct `{}` has infinite size", outer; name);
+                false
+            }
+            Type::Struct(s) => s
+                .fields()
+                .iter()
+                .all(|field| self.check_recursion(field.ty, outer, name)),
+            _ => true,
+        }
+    }
+
     fn check_if(
         &mut self,
         cond: &Node<Expression>,
@@ -261,6 +734,20 @@ impl<'a> TypeCheck<'a> {
         // ... or infer it if `expected` is None
         let conseq_ty = self.check_block(conseq, expected);
 
+        // Arrays and structs live in stack slots, there is no way to merge
+        // them yet
+        match conseq_ty {
+            Type::Array(..) => {
+                fatal_at!("an `if` expression cannot evaluate to an array"; conseq);
+                return Type::Err;
+            }
+            Type::Struct(..) => {
+                fatal_at!("an `if` expression cannot evaluate to a struct"; conseq);
+                return Type::Err;
+            }
+            _ => {}
+        }
+
         if let Some(ref altern) = *altern {
             self.check_block(altern, Some(conseq_ty));
         } else if let Some(expected) = expected {
@@ -282,9 +769,261 @@ impl<'a> TypeCheck<'a> {
         conseq_ty
     }
 
-    fn check_while(&mut self, cond: &Node<Expression>, body: &Node<Block>) -> Type {
+    fn check_match(
+        &mut self,
+        scrutinee: &Node<Expression>,
+        arms: &[Arm],
+        expected: Option<Type>,
+    ) -> Type {
+        let ty = self.check_expression(scrutinee, None);
+        let matchable = ty.is_integer() || ty == Type::Char || ty == Type::Bool;
+        if !matchable && ty != Type::Err {
+            fatal_at!("cannot match on a value of type {}", ty; scrutinee);
+        }
+
+        // Collect the values the patterns cover as inclusive ranges
+        let mut ranges = Vec::new();
+        let mut wildcard = false;
+
+        for pattern in arms.iter().flat_map(|arm| &arm.patterns) {
+            if !matchable {
+                break;
+            }
+
+            match **pattern {
+                Pattern::Literal(val) => {
+                    if let Some(val) = self.check_pattern_value(val, ty, pattern) {
+                        ranges.push((val, val));
+                    }
+                }
+                Pattern::Range(lo, hi) => {
+                    if ty == Type::Bool {
+                        fatal_at!("only integers and chars can be matched with ranges"; pattern);
+                        continue;
+                    }
+
+                    let lo = self.check_pattern_value(lo, ty, pattern);
+                    let hi = self.check_pattern_value(hi, ty, pattern);
+
+                    if let (Some(lo), Some(hi)) = (lo, hi) {
+                        if lo > hi {
+                            fatal_at!("lower range bound must be less than or equal to upper"; pattern);
+                        } else {
+                            ranges.push((lo, hi));
+                        }
+                    }
+                }
+                Pattern::Wildcard => wildcard = true,
+            }
+        }
+
+        if matchable && !wildcard {
+            if let Some(val) = TypeCheck::first_uncovered(ty, ranges) {
+                let val = match ty {
+                    Type::Bool => (val == 1).to_string(),
+                    Type::Char => format!("{:?}", ::std::char::from_u32(val as u32).unwrap()),
+                    _ => val.to_string(),
+                };
+                fatal_at!("non-exhaustive patterns: `{}` not covered", val; scrutinee);
+            }
+        }
+
+        // All arms have to evaluate to the type of the first one (see
+        // `check_if`)
+        let mut arms_ty = None;
+        for arm in arms {
+            let ty = self.check_block(&arm.body, arms_ty.or(expected));
+            if arms_ty.is_some() {
+                continue;
+            }
+
+            match ty {
+                Type::Array(..) => {
+                    fatal_at!("a `match` expression cannot evaluate to an array"; &*arm.body);
+                    return Type::Err;
+                }
+                Type::Struct(..) => {
+                    fatal_at!("a `match` expression cannot evaluate to a struct"; &*arm.body);
+                    return Type::Err;
+                }
+                _ => arms_ty = Some(ty),
+            }
+        }
+
+        let arms_ty = arms_ty.unwrap_or(Type::Unit);
+        match expected {
+            // The mismatch has already been reported for the first arm
+            Some(expected) if expected != arms_ty => Type::Err,
+            _ => arms_ty,
+        }
+    }
+
+    /// Check that a pattern's value has the type of the matched value and
+    /// return it as an integer (chars as their code point, bools as 0 or 1)
+    fn check_pattern_value(&self, val: Value, ty: Type, pattern: &Node<Pattern>) -> Option<i128> {
+        let val_ty = match val {
+            // Integer literals take the type of the matched value
+            Value::Int(..) if ty.is_integer() => ty,
+            _ => val.get_ty(),
+        };
+
+        if val_ty != ty {
+            fatal_at!("type mismatch: expected {}, got {}", ty, val_ty; pattern);
+            return None;
+        }
+
+        let val = match val {
+            Value::Int(i, _) => i,
+            _ => i128::from(val.as_i64()),
+        };
+        if ty.is_integer() && !self.check_int_range(val, ty, pattern) {
+            return None;
+        }
+
+        Some(val)
+    }
+
+    /// The smallest value of `ty` that isn't covered by one of the ranges
+    fn first_uncovered(ty: Type, mut ranges: Vec<(i128, i128)>) -> Option<i128> {
+        let domain = match ty {
+            Type::Bool => vec![(0, 1)],
+            // Chars are unicode scalar values, i.e. all code points except
+            // for the surrogates
+            Type::Char => vec![(0, 0xD7FF), (0xE000, 0x10_FFFF)],
+            _ => vec![ty.int_range()],
+        };
+
+        ranges.sort();
+
+        for (lo, hi) in domain {
+            // Everything below `next` is covered
+            let mut next = lo;
+            for &(from, to) in &ranges {
+                if from > next {
+                    break;
+                }
+                next = next.max(to + 1);
+            }
+
+            if next <= hi {
+                return Some(next);
+            }
+        }
+
+        None
+    }
+
+    fn enter_loop(&mut self, label: &Option<Node<Ident>>, keyword: &'static str, ty: Option<Type>) {
+        self.loops.push(LoopContext {
+            label: label.as_ref().map(|label| **label),
+            keyword,
+            ty,
+        });
+    }
+
+    fn check_while(
+        &mut self,
+        cond: &Node<Expression>,
+        body: &Node<Block>,
+        label: &Option<Node<Ident>>,
+    ) -> Type {
+        self.enter_loop(label, "while", None);
         self.check_expression(cond, Some(Type::Bool));
         self.check_block(body, Some(Type::Unit));
+        self.loops.pop();
+
+        Type::Unit
+    }
+
+    fn check_loop(
+        &mut self,
+        body: &Node<Block>,
+        label: &Option<Node<Ident>>,
+        expected: Option<Type>,
+    ) -> Type {
+        // The `break` values have to match the expected type. Otherwise, the
+        // first `break` determines the loop's type.
+        self.enter_loop(label, "loop", expected);
+        self.check_block(body, Some(Type::Unit));
+        let ty = self.loops.pop().unwrap().ty;
+
+        // Arrays and structs live in stack slots, there is no way to merge
+        // them yet
+        match ty {
+            Some(Type::Array(..)) => {
+                fatal_at!("a `loop` expression cannot evaluate to an array"; body);
+                Type::Err
+            }
+            Some(Type::Struct(..)) => {
+                fatal_at!("a `loop` expression cannot evaluate to a struct"; body);
+                Type::Err
+            }
+            Some(ty) => ty,
+            // A loop that is never left with `break` doesn't produce a value
+            None => Type::Unit,
+        }
+    }
+
+    fn check_for(
+        &mut self,
+        var: &Node<Binding>,
+        range: (&Node<Expression>, &Node<Expression>),
+        body: &Node<Block>,
+        label: &Option<Node<Ident>>,
+    ) -> Type {
+        self.check_expression(range.0, Some(var.ty));
+        self.check_expression(range.1, Some(var.ty));
+
+        // The body's scope is nested in the loop variable's scope
+        self.enter_loop(label, "for", None);
+        with_reset!(self.scope, var.id, {
+            self.check_block(body, Some(Type::Unit));
+        });
+        self.loops.pop();
+
+        Type::Unit
+    }
+
+    fn check_break(
+        &mut self,
+        label: &Option<Node<Ident>>,
+        value: &Option<Box<Node<Expression>>>,
+        expr: &Node<Expression>,
+    ) -> Type {
+        // The break verifier made sure that the loop exists
+        let target = match *label {
+            Some(ref label) => self
+                .loops
+                .iter()
+                .rposition(|l| l.label == Some(**label))
+                .unwrap(),
+            None => self.loops.len() - 1,
+        };
+        let keyword = self.loops[target].keyword;
+        let expected = self.loops[target].ty;
+
+        let ty = match *value {
+            Some(ref value) if keyword != "loop" => {
+                fatal_at!("`break` with value from a `{}` loop", keyword; &**value);
+                self.check_expression(value, None);
+                return Type::Unit;
+            }
+            Some(ref value) => self.check_expression(value, expected),
+            None => {
+                // A `break` without a value leaves the loop with `()`
+                if let Some(expected) = expected {
+                    self.type_check(Type::Unit, expected, expr);
+                }
+
+                Type::Unit
+            }
+        };
+
+        if expected.is_none() {
+            self.loops[target].ty = Some(ty);
+        }
+
+        // The `break` itself never produces a value
         Type::Unit
     }
 }
@@ -293,30 +1032,83 @@ impl<'v> Visitor<'v> for TypeCheck<'v> {
     fn visit_symbol(&mut self, symbol: &'v Node<Symbol>) {
         match **symbol {
             Symbol::Function {
+                ref name,
+                ref bindings,
                 ref ret_ty,
                 ref body,
                 ..
             } => {
+                self.check_signature(name, bindings, *ret_ty);
                 self.check_fn(*ret_ty, body);
             }
+            Symbol::Extern {
+                ref name,
+                ref bindings,
+                ref ret_ty,
+                ..
+            } => {
+                self.check_signature(name, bindings, *ret_ty);
+
+                // Structs are passed as a pointer to the caller's copy which
+                // C code doesn't know about
+                for binding in bindings {
+                    if let Type::Struct(..) = binding.ty {
+                        fatal_at!("structs cannot be passed to extern functions"; binding);
+                    }
+                }
+            }
             Symbol::Static {
                 ref binding,
                 ref value,
+                ..
             }
             | Symbol::Constant {
                 ref binding,
                 ref value,
+                ..
             } => {
-                self.check_expression(value, Some(binding.ty));
+                match binding.ty {
+                    Type::Array(..) => {
+                        fatal_at!("statics and constants cannot be arrays"; &**binding)
+                    }
+                    Type::Struct(..) => {
+                        fatal_at!("statics and constants cannot be structs"; &**binding)
+                    }
+                    _ => {}
+                }
+
+                with_reset!(self.scope, symbol.id, {
+                    self.check_expression(value, Some(binding.ty));
+                });
+            }
+            Symbol::Impl { ref methods, .. } => {
+                for method in methods {
+                    self.visit_symbol(method);
+                }
+            }
+            Symbol::Struct {
+                ref name,
+                ref fields,
+                ..
+            } => {
+                for (i, field) in fields.iter().enumerate() {
+                    if fields[..i].iter().any(|f| *f.name == *field.name) {
+                        fatal_at!("field `{}` is already declared", field.name; field);
+                    }
+
+                    self.check_recursion(field.ty, **name, name);
+                }
             }
         }
     }
 }
 
+/// Type check the program and record the type of every expression and block
+/// in the session's type table
 pub fn run(program: &[Node<Symbol>]) {
-    let symbol_table = &session().symbol_table;
-    let mut visitor = TypeCheck::new(symbol_table);
+    let session = session();
+    let mut visitor = TypeCheck::new(&session.symbol_table, &session.type_table);
     walk_program(&mut visitor, program);
 
-    session().abort_if_errors();
+    session.abort_if_errors();
 }
@@ -1,35 +1,335 @@
+//! Constant folding
+//!
+//! # Motivation
+//!
+//! The translator doesn't care about values that are known at compile time,
+//! so `1 + 2` becomes an `add` instruction just like `a + b`. This pass
+//! evaluates all instructions whose operands are known and replaces the
+//! uses of their results with the computed values. Branches on known
+//! conditions become jumps and blocks that can't be reached anymore are
+//! removed. Among others, this removes the bounds checks of array indices
+//! that are known to be in range.
+//!
+//! # Implementation notes
+//!
+//! The IR isn't in SSA form: the same register may be assigned in several
+//! places (e.g. the result of an `if` expression). Thus, we only fold
+//! registers that are assigned exactly once.
+//!
+//! Variables live in stack slots. A slot that's only stored to once with a
+//! known value (e.g. `let i = 1;`) is folded like a register, unless its
+//! address is taken: arrays and structs may be written through a pointer.
+//!
+//! Operations are evaluated by the interpreter's implementation so folding
+//! can't change what a program computes.
+
+use driver::interner::Ident;
+use middle::interp::{eval_binop, eval_cmp, eval_extend, eval_unop};
 use middle::ir::*;
+use std::collections::{HashMap, HashSet};
+
+/// The known values of registers
+type Constants = HashMap<Ident, Immediate>;
+
+pub fn fold_constants(program: &mut Program) {
+    for symbol in program.iter_mut() {
+        if let Symbol::Function { ref mut body, .. } = *symbol {
+            fold_function(body);
+            remove_unreachable_blocks(body);
+        }
+    }
+}
 
-// TODO: Implement
+fn fold_function(body: &mut [Block]) {
+    let definitions = count_definitions(body);
+    let mut constants = Constants::new();
 
+    // Folding a register might make other registers' values known, so we
+    // repeat until nothing changes
+    loop {
+        let known = constants.len();
+        let slots = constant_slots(body);
 
-fn fold_constants(ir: &mut [Symbol]) {
-    for symbol in ir.iter_mut() {
-        match *symbol {
-            Symbol::Global { .. } => {}
-            Symbol::Function { name: _, ref mut body, args: _ } => {
-                for block in body {
-                    fold_block(block);
-                }
-            }
+        for block in body.iter_mut() {
+            fold_block(block, &definitions, &slots, &mut constants);
+        }
+
+        if constants.len() == known {
+            break;
         }
     }
 }
 
+/// Count how many times every local register is assigned
+fn count_definitions(body: &[Block]) -> HashMap<Ident, u32> {
+    let mut definitions = HashMap::new();
+
+    for block in body {
+        for phi in &block.phis {
+            *definitions.entry(phi.dst.ident()).or_insert(0) += 1;
+        }
+
+        for inst in &block.inst {
+            let dst = match *inst {
+                Instruction::BinOp { dst, .. }
+                | Instruction::UnOp { dst, .. }
+                | Instruction::Cmp { dst, .. }
+                | Instruction::Extend { dst, .. }
+                | Instruction::Index { dst, .. }
+                | Instruction::Load { dst, .. }
+                | Instruction::Call { dst, .. }
+                | Instruction::Alloca { dst, .. } => dst,
+                Instruction::Store {
+                    dst: Value::Register(dst),
+                    ..
+                } => dst,
+                Instruction::Store { .. } => continue,
+            };
 
-fn fold_block(block: &mut Block) {
-    for instr in block.inst.iter_mut() {
-        fold_instr(instr);
+            *definitions.entry(dst.ident()).or_insert(0) += 1;
+        }
     }
+
+    definitions
 }
 
+/// Find the stack slots that are assigned a known value exactly once
+fn constant_slots(body: &[Block]) -> Constants {
+    let mut stores: HashMap<Ident, Vec<Value>> = HashMap::new();
+    let mut escaped = HashSet::new();
 
-fn fold_instr(instr: &mut Instruction) {
-    match *instr {
-        Instruction::BinOp { .. } => fold_binop(instr),
+    for inst in body.iter().flat_map(|block| &block.inst) {
+        match *inst {
+            Instruction::Store {
+                src,
+                dst: Value::Register(Register::Stack(slot)),
+            } => stores.entry(slot).or_default().push(src),
+            Instruction::Index {
+                base: Value::Register(Register::Stack(slot)),
+                ..
+            } => {
+                escaped.insert(slot);
+            }
+            Instruction::Call { ref args, .. } => {
+                escaped.extend(args.iter().filter_map(|arg| match *arg {
+                    Value::Register(Register::Stack(slot)) => Some(slot),
+                    _ => None,
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    stores
+        .into_iter()
+        .filter(|&(slot, _)| !escaped.contains(&slot))
+        .filter_map(|(slot, srcs)| match *srcs {
+            [Value::Immediate(value)] => Some((slot, value)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn fold_block(
+    block: &mut Block,
+    definitions: &HashMap<Ident, u32>,
+    slots: &Constants,
+    constants: &mut Constants,
+) {
+    for phi in &mut block.phis {
+        for src in &mut phi.srcs {
+            substitute(&mut src.0, constants);
+        }
+    }
+
+    for inst in &mut block.inst {
+        substitute_operands(inst, constants);
+
+        if let Some((Register::Local(id), value)) = evaluate(inst, slots) {
+            if definitions[&id] == 1 {
+                constants.insert(id, value);
+            }
+        }
+    }
+
+    // The folded instructions aren't needed anymore
+    block.inst.retain(|inst| match defined_constant(inst) {
+        Some(id) => !constants.contains_key(&id),
+        None => true,
+    });
+
+    match block.last {
+        ControlFlowInstruction::Return {
+            value: Some(ref mut value),
+        } => substitute(value, constants),
+        ControlFlowInstruction::Branch { ref mut cond, .. } => substitute(cond, constants),
+        ControlFlowInstruction::Switch { ref mut value, .. } => substitute(value, constants),
         _ => {}
     }
+
+    // Branches on a known condition always take the same way
+    if let ControlFlowInstruction::Branch {
+        cond: Value::Immediate(cond),
+        conseq,
+        altern,
+    } = block.last
+    {
+        let dest = if cond.val() & 1 == 1 { conseq } else { altern };
+        block.last = ControlFlowInstruction::Jump { dest };
+    }
+
+    // The same goes for switches on a known value
+    if let ControlFlowInstruction::Switch {
+        value: Value::Immediate(value),
+        ref cases,
+        default,
+    } = block.last
+    {
+        let dest = cases
+            .iter()
+            .find(|&&(lo, hi, _)| lo.val() <= value.val() && value.val() <= hi.val())
+            .map_or(default, |case| case.2);
+        block.last = ControlFlowInstruction::Jump { dest };
+    }
+}
+
+/// Replace a register with its value if it's known
+fn substitute(value: &mut Value, constants: &Constants) {
+    if let Value::Register(Register::Local(id)) = *value {
+        if let Some(&imm) = constants.get(&id) {
+            *value = Value::Immediate(imm);
+        }
+    }
+}
+
+fn substitute_operands(inst: &mut Instruction, constants: &Constants) {
+    match *inst {
+        Instruction::BinOp {
+            ref mut lhs,
+            ref mut rhs,
+            ..
+        }
+        | Instruction::Cmp {
+            ref mut lhs,
+            ref mut rhs,
+            ..
+        } => {
+            substitute(lhs, constants);
+            substitute(rhs, constants);
+        }
+        Instruction::UnOp { ref mut item, .. } => substitute(item, constants),
+        Instruction::Extend { ref mut src, .. } => substitute(src, constants),
+        Instruction::Index {
+            ref mut base,
+            ref mut index,
+            ..
+        } => {
+            substitute(base, constants);
+            substitute(index, constants);
+        }
+        Instruction::Store { ref mut src, .. } => substitute(src, constants),
+        Instruction::Call { ref mut args, .. } => {
+            for arg in args {
+                substitute(arg, constants);
+            }
+        }
+        Instruction::Load { .. } | Instruction::Alloca { .. } => {}
+    }
 }
 
+/// Evaluate an instruction whose operands are all known
+fn evaluate(inst: &Instruction, slots: &Constants) -> Option<(Register, Immediate)> {
+    let (dst, value) = match *inst {
+        Instruction::BinOp {
+            op,
+            lhs: Value::Immediate(lhs),
+            rhs: Value::Immediate(rhs),
+            dst,
+        } => (dst, eval_binop(op, lhs.val(), rhs.val())?),
+        Instruction::UnOp {
+            op,
+            item: Value::Immediate(item),
+            dst,
+        } => (dst, eval_unop(op, item.val())),
+        Instruction::Cmp {
+            cmp,
+            lhs: Value::Immediate(lhs),
+            rhs: Value::Immediate(rhs),
+            dst,
+        } => (dst, eval_cmp(cmp, lhs.val(), rhs.val())),
+        Instruction::Extend {
+            signed,
+            bits,
+            src: Value::Immediate(src),
+            dst,
+        } => (dst, eval_extend(signed, bits, src.val())),
+        Instruction::Store {
+            src: Value::Immediate(src),
+            dst: Value::Register(dst),
+        } => (dst, src.val()),
+        Instruction::Load {
+            src: Value::Register(Register::Stack(slot)),
+            dst,
+        } => (dst, slots.get(&slot)?.val()),
+        _ => return None,
+    };
 
-// fn fold_binop()
+    Some((dst, Immediate(value)))
+}
+
+/// The register an instruction assigns a (potentially) known value to
+fn defined_constant(inst: &Instruction) -> Option<Ident> {
+    match *inst {
+        Instruction::BinOp { dst, .. }
+        | Instruction::UnOp { dst, .. }
+        | Instruction::Cmp { dst, .. }
+        | Instruction::Extend { dst, .. }
+        | Instruction::Load { dst, .. }
+        | Instruction::Store {
+            dst: Value::Register(dst),
+            ..
+        } => match dst {
+            Register::Local(id) => Some(id),
+            Register::Stack(..) => None,
+        },
+        _ => None,
+    }
+}
+
+/// Remove all blocks that can't be reached from the function's entry
+fn remove_unreachable_blocks(body: &mut Vec<Block>) {
+    let mut reachable = HashSet::new();
+    let mut worklist = vec![body[0].label.ident()];
+
+    while let Some(label) = worklist.pop() {
+        if !reachable.insert(label) {
+            continue;
+        }
+
+        let block = body.iter().find(|b| b.label.ident() == label).unwrap();
+        worklist.extend(block.last.successors());
+    }
+
+    body.retain(|block| reachable.contains(&block.label.ident()));
+
+    // Phis only keep the values of blocks that still jump to them
+    let predecessors: HashSet<(Ident, Ident)> = body
+        .iter()
+        .flat_map(|block| {
+            let label = block.label.ident();
+            block
+                .last
+                .successors()
+                .into_iter()
+                .map(move |succ| (label, succ))
+        })
+        .collect();
+
+    for block in body.iter_mut() {
+        let label = block.label.ident();
+        for phi in &mut block.phis {
+            phi.srcs
+                .retain(|&(_, pred)| predecessors.contains(&(pred.ident(), label)));
+        }
+    }
+}
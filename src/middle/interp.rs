@@ -0,0 +1,502 @@
+//! The IR interpreter
+//!
+//! # Motivation
+//!
+//! The back end translates the IR into machine code in several steps
+//! (instruction selection, register allocation, ...), each of which can
+//! introduce subtle bugs. To be able to tell whether a program was
+//! miscompiled, we need a reference implementation of the IR's semantics.
+//! The interpreter is exactly that: it executes the IR directly so that its
+//! results can be compared to the native binary's results.
+//!
+//! # Implementation notes
+//!
+//! All values are treated as signed 64 bit integers with wrapping arithmetics,
+//! mirroring what the x86-64 back end does. Only the unsigned operations
+//! (`udiv`, `ult`, ...) reinterpret their operands as unsigned and the
+//! floating point operations (`fadd`, `flt`, ...) reinterpret them as the bit
+//! patterns of `f64`s. Booleans are
+//! represented by their lowest bit, so `not 1` is false just like on the
+//! machine.
+//!
+//! Stack slots live in a flat, word-addressed memory that grows with every
+//! call and shrinks again on return. Each frame maps the names of its slots
+//! to their addresses, so `index` can compute the address of an array
+//! element and `load`/`store` can go through it. Function arguments live in
+//! stack slots named after the argument, just as the translator expects them
+//! to.
+//!
+//! Extern functions have no body to execute. The few libc functions programs
+//! commonly declare (like `putchar`) are emulated, calling any other extern
+//! function is an error.
+
+use driver::interner::Ident;
+use driver::session;
+use front::intrinsics::Intrinsic;
+use middle::ir::*;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+/// The maximum call depth before we report a stack overflow
+const MAX_CALL_DEPTH: u32 = 4096;
+
+/// A call frame of the interpreted program
+struct Frame {
+    /// The contents of all local registers
+    registers: HashMap<Ident, i64>,
+    /// The addresses of all stack slots
+    slots: HashMap<Ident, usize>,
+}
+
+impl Frame {
+    fn new() -> Frame {
+        Frame {
+            registers: HashMap::new(),
+            slots: HashMap::new(),
+        }
+    }
+}
+
+struct Interpreter<'a> {
+    functions: HashMap<Ident, (&'a [Ident], &'a [Block])>,
+    externs: HashSet<Ident>,
+    globals: HashMap<Ident, i64>,
+    /// The contents of all string symbols
+    strings: HashMap<Ident, Ident>,
+    memory: Vec<i64>,
+    depth: u32,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(program: &'a Program) -> Interpreter<'a> {
+        let mut functions = HashMap::new();
+        let mut externs = HashSet::new();
+        let mut globals = HashMap::new();
+        let mut strings = HashMap::new();
+
+        for symbol in program {
+            match *symbol {
+                Symbol::Global { name, value } => {
+                    globals.insert(name, value.val());
+                }
+                Symbol::Str { name, value } => {
+                    strings.insert(name, value);
+                }
+                Symbol::Function {
+                    name,
+                    ref body,
+                    ref args,
+                } => {
+                    functions.insert(name, (&args[..], &body[..]));
+                }
+                Symbol::Extern { name, .. } => {
+                    externs.insert(name);
+                }
+            }
+        }
+
+        Interpreter {
+            functions,
+            externs,
+            globals,
+            strings,
+            memory: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// Report a runtime error of the interpreted program and stop
+    fn fatal<S: AsRef<str>>(&self, msg: S) -> ! {
+        fatal!("runtime error: {}", msg.as_ref());
+        session().abort()
+    }
+
+    /// Get the value of an IR value in the current frame
+    fn value(&self, frame: &Frame, value: Value) -> i64 {
+        match value {
+            Value::Immediate(imm) => imm.val(),
+            Value::Register(Register::Local(id)) => *frame
+                .registers
+                .get(&id)
+                .unwrap_or_else(|| panic!("read from undefined register %{}", id)),
+            Value::Register(Register::Stack(id)) => {
+                panic!("stack slot {{{}}} used as a value", id)
+            }
+            Value::Static(id) => panic!("static @{} used as a value", id),
+            Value::Indirect(reg) => panic!("memory at [{}] used as a value", reg),
+        }
+    }
+
+    /// Get the address of a stack slot in the current frame
+    fn slot(&self, frame: &Frame, id: Ident) -> usize {
+        *frame
+            .slots
+            .get(&id)
+            .unwrap_or_else(|| panic!("access to unallocated stack slot {{{}}}", id))
+    }
+
+    /// Check that the address stored in a register points into the memory
+    fn address(&self, frame: &Frame, reg: Register) -> usize {
+        let addr = self.value(frame, Value::Register(reg));
+        if addr < 0 || addr as usize >= self.memory.len() {
+            self.fatal("invalid memory access");
+        }
+
+        addr as usize
+    }
+
+    /// Push a new stack slot with the given value and return its address
+    fn push_slot(&mut self, value: i64) -> usize {
+        self.memory.push(value);
+        self.memory.len() - 1
+    }
+
+    /// Call a function with the given arguments and return its result
+    ///
+    /// Void functions return 0.
+    fn call(&mut self, name: Ident, args: &[i64]) -> i64 {
+        let (arg_names, body) = match self.functions.get(&name) {
+            Some(&f) => f,
+            None => panic!("call to unknown function {}", name),
+        };
+
+        assert_eq!(
+            arg_names.len(),
+            args.len(),
+            "wrong argument count when calling {}",
+            name
+        );
+
+        if self.depth == MAX_CALL_DEPTH {
+            self.fatal("stack overflow");
+        }
+
+        let mut frame = Frame::new();
+        let stack_base = self.memory.len();
+        for (arg, value) in arg_names.iter().zip(args) {
+            let addr = self.push_slot(*value);
+            frame.slots.insert(*arg, addr);
+        }
+
+        self.depth += 1;
+
+        let mut block = &body[0];
+        let mut prev_label = None;
+
+        loop {
+            self.exec_phis(&mut frame, block, prev_label);
+
+            for inst in &block.inst {
+                self.exec_instruction(&mut frame, inst);
+            }
+
+            let next = match block.last {
+                ControlFlowInstruction::Return { value } => {
+                    self.depth -= 1;
+                    self.memory.truncate(stack_base);
+                    return value.map_or(0, |v| self.value(&frame, v));
+                }
+                ControlFlowInstruction::Branch {
+                    cond,
+                    conseq,
+                    altern,
+                } => {
+                    if self.value(&frame, cond) & 1 == 1 {
+                        conseq
+                    } else {
+                        altern
+                    }
+                }
+                ControlFlowInstruction::Jump { dest } => dest,
+                ControlFlowInstruction::Switch {
+                    value,
+                    ref cases,
+                    default,
+                } => {
+                    let value = self.value(&frame, value);
+                    cases
+                        .iter()
+                        .find(|&&(lo, hi, _)| lo.val() <= value && value <= hi.val())
+                        .map_or(default, |case| case.2)
+                }
+                ControlFlowInstruction::Trap { msg } => self.fatal(&*msg),
+                ControlFlowInstruction::NotYetProcessed => {
+                    panic!("block {} has no terminator", block.label)
+                }
+            };
+
+            prev_label = Some(block.label);
+            block = body
+                .iter()
+                .find(|b| b.label == next)
+                .unwrap_or_else(|| panic!("jump to unknown label {} in {}", next, name));
+        }
+    }
+
+    /// Evaluate the phi nodes of a block we just entered
+    fn exec_phis(&self, frame: &mut Frame, block: &Block, prev_label: Option<Label>) {
+        // All phis read their sources before any of them is written
+        let values: Vec<_> = block
+            .phis
+            .iter()
+            .map(|phi| {
+                let src = phi
+                    .srcs
+                    .iter()
+                    .find(|&&(_, label)| Some(label) == prev_label)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "phi for {} has no value for predecessor {:?}",
+                            phi.dst, prev_label
+                        )
+                    });
+
+                (phi.dst, self.value(frame, src.0))
+            })
+            .collect();
+
+        for (dst, value) in values {
+            frame.registers.insert(dst.ident(), value);
+        }
+    }
+
+    fn exec_instruction(&mut self, frame: &mut Frame, inst: &Instruction) {
+        match *inst {
+            Instruction::BinOp { op, lhs, rhs, dst } => {
+                let lhs = self.value(frame, lhs);
+                let rhs = self.value(frame, rhs);
+                let result = self.exec_binop(op, lhs, rhs);
+                frame.registers.insert(dst.ident(), result);
+            }
+            Instruction::UnOp { op, item, dst } => {
+                let item = self.value(frame, item);
+                frame.registers.insert(dst.ident(), eval_unop(op, item));
+            }
+            Instruction::Cmp { cmp, lhs, rhs, dst } => {
+                let lhs = self.value(frame, lhs);
+                let rhs = self.value(frame, rhs);
+                frame.registers.insert(dst.ident(), eval_cmp(cmp, lhs, rhs));
+            }
+            Instruction::Extend {
+                signed,
+                bits,
+                src,
+                dst,
+            } => {
+                let src = self.value(frame, src);
+                frame
+                    .registers
+                    .insert(dst.ident(), eval_extend(signed, bits, src));
+            }
+            Instruction::Alloca { dst, size } => {
+                let addr = self.memory.len();
+                self.memory.resize(addr + size as usize, 0);
+                frame.slots.insert(dst.ident(), addr);
+            }
+            Instruction::Index { base, index, dst } => {
+                let base = match base {
+                    Value::Register(Register::Stack(id)) => self.slot(frame, id) as i64,
+                    _ => self.value(frame, base),
+                };
+                let index = self.value(frame, index);
+                frame
+                    .registers
+                    .insert(dst.ident(), base.wrapping_add(index));
+            }
+            Instruction::Load { src, dst } => {
+                let value = match src {
+                    Value::Register(Register::Stack(id)) => self.memory[self.slot(frame, id)],
+                    Value::Indirect(reg) => self.memory[self.address(frame, reg)],
+                    Value::Static(id) => self.globals[&id],
+                    _ => panic!("invalid load source: {}", src),
+                };
+                frame.registers.insert(dst.ident(), value);
+            }
+            Instruction::Store { src, dst } => {
+                let value = self.value(frame, src);
+                match dst {
+                    Value::Register(Register::Local(id)) => {
+                        frame.registers.insert(id, value);
+                    }
+                    Value::Register(Register::Stack(id)) => {
+                        let addr = self.slot(frame, id);
+                        self.memory[addr] = value;
+                    }
+                    Value::Indirect(reg) => {
+                        let addr = self.address(frame, reg);
+                        self.memory[addr] = value;
+                    }
+                    Value::Static(id) => {
+                        self.globals.insert(id, value);
+                    }
+                    Value::Immediate(..) => panic!("attempt to store in an immediate"),
+                }
+            }
+            Instruction::Call {
+                name,
+                ref args,
+                dst,
+            } => {
+                if let Some(intrinsic) = Intrinsic::lookup(name) {
+                    let result = self.call_intrinsic(intrinsic, args);
+                    frame.registers.insert(dst.ident(), result);
+                    return;
+                }
+
+                let args: Vec<_> = args.iter().map(|arg| self.value(frame, *arg)).collect();
+                let result = if self.externs.contains(&name) {
+                    self.call_extern(name, &args)
+                } else {
+                    self.call(name, &args)
+                };
+                frame.registers.insert(dst.ident(), result);
+            }
+        }
+    }
+
+    /// Execute a call to an intrinsic
+    fn call_intrinsic(&self, intrinsic: Intrinsic, args: &[Value]) -> i64 {
+        match intrinsic {
+            Intrinsic::PrintStr => {
+                let s = match args[0] {
+                    Value::Static(id) => self.strings[&id],
+                    arg => panic!("print_str called with {}", arg),
+                };
+
+                let mut stdout = io::stdout();
+                stdout
+                    .write_all(s.as_bytes())
+                    .and_then(|()| stdout.flush())
+                    .unwrap_or_else(|e| self.fatal(format!("cannot write to stdout: {}", e)));
+
+                0
+            }
+        }
+    }
+
+    /// Execute a call to an extern function
+    ///
+    /// There's no C code to run, so only a few libc functions are emulated.
+    fn call_extern(&self, name: Ident, args: &[i64]) -> i64 {
+        match &*name {
+            "putchar" => {
+                let mut stdout = io::stdout();
+                stdout
+                    .write_all(&[args[0] as u8])
+                    .and_then(|()| stdout.flush())
+                    .unwrap_or_else(|e| self.fatal(format!("cannot write to stdout: {}", e)));
+
+                // Like C's `putchar`, return the character that was written
+                i64::from(args[0] as u8)
+            }
+            _ => {
+                fatal!("cannot call extern function `{}` in the interpreter", name);
+                session().abort()
+            }
+        }
+    }
+
+    fn exec_binop(&self, op: InfixOp, lhs: i64, rhs: i64) -> i64 {
+        eval_binop(op, lhs, rhs).unwrap_or_else(|| self.fatal("division by zero"))
+    }
+}
+
+/// Evaluate a binary operation
+///
+/// Returns `None` if the operation fails at runtime (division by zero).
+pub fn eval_binop(op: InfixOp, lhs: i64, rhs: i64) -> Option<i64> {
+    Some(match op {
+        InfixOp::Add => lhs.wrapping_add(rhs),
+        InfixOp::Sub => lhs.wrapping_sub(rhs),
+        InfixOp::Mul => lhs.wrapping_mul(rhs),
+        InfixOp::Div | InfixOp::Mod if rhs == 0 => return None,
+        InfixOp::Div => lhs.wrapping_div(rhs),
+        InfixOp::Mod => lhs.wrapping_rem(rhs),
+        InfixOp::Pow => lhs.wrapping_pow(rhs as u32),
+        // Like the machine, only use the lowest 6 bits of the shift amount
+        InfixOp::Shl => lhs.wrapping_shl(rhs as u32),
+        InfixOp::Shr => lhs.wrapping_shr(rhs as u32),
+        InfixOp::UDiv | InfixOp::UMod if rhs == 0 => return None,
+        InfixOp::UDiv => ((lhs as u64) / (rhs as u64)) as i64,
+        InfixOp::UMod => ((lhs as u64) % (rhs as u64)) as i64,
+        InfixOp::UShr => (lhs as u64).wrapping_shr(rhs as u32) as i64,
+        InfixOp::And => lhs & rhs,
+        InfixOp::Or => lhs | rhs,
+        InfixOp::Xor => lhs ^ rhs,
+        InfixOp::FAdd => float_bits(float(lhs) + float(rhs)),
+        InfixOp::FSub => float_bits(float(lhs) - float(rhs)),
+        InfixOp::FMul => float_bits(float(lhs) * float(rhs)),
+        InfixOp::FDiv => float_bits(float(lhs) / float(rhs)),
+    })
+}
+
+/// Evaluate a unary operation
+pub fn eval_unop(op: PrefixOp, item: i64) -> i64 {
+    match op {
+        PrefixOp::Neg => item.wrapping_neg(),
+        PrefixOp::Not => !item,
+        PrefixOp::FNeg => float_bits(-float(item)),
+        PrefixOp::IntToFloat => float_bits(item as f64),
+        PrefixOp::FloatToInt => {
+            // Like `cvttsd2si`, values that don't fit (and NaN) become the
+            // smallest `int`
+            let f = float(item).trunc();
+            if f >= -(2f64.powi(63)) && f < 2f64.powi(63) {
+                f as i64
+            } else {
+                i64::MIN
+            }
+        }
+    }
+}
+
+/// Evaluate a comparison (1 if it holds, 0 otherwise)
+pub fn eval_cmp(cmp: CmpOp, lhs: i64, rhs: i64) -> i64 {
+    let result = match cmp {
+        CmpOp::Lt => lhs < rhs,
+        CmpOp::Le => lhs <= rhs,
+        CmpOp::Eq => lhs == rhs,
+        CmpOp::Ne => lhs != rhs,
+        CmpOp::Ge => lhs >= rhs,
+        CmpOp::Gt => lhs > rhs,
+        CmpOp::ULt => (lhs as u64) < (rhs as u64),
+        CmpOp::ULe => (lhs as u64) <= (rhs as u64),
+        CmpOp::UGe => (lhs as u64) >= (rhs as u64),
+        CmpOp::UGt => (lhs as u64) > (rhs as u64),
+        CmpOp::FLt => float(lhs) < float(rhs),
+        CmpOp::FLe => float(lhs) <= float(rhs),
+        CmpOp::FEq => float(lhs) == float(rhs),
+        CmpOp::FNe => float(lhs) != float(rhs),
+        CmpOp::FGe => float(lhs) >= float(rhs),
+        CmpOp::FGt => float(lhs) > float(rhs),
+    };
+
+    i64::from(result)
+}
+
+/// Reinterpret a word as a float
+fn float(bits: i64) -> f64 {
+    f64::from_bits(bits as u64)
+}
+
+/// Reinterpret a float as a word
+fn float_bits(f: f64) -> i64 {
+    f.to_bits() as i64
+}
+
+/// Evaluate a sign or zero extension of the lowest `bits` bits
+pub fn eval_extend(signed: bool, bits: u32, src: i64) -> i64 {
+    let shift = 64 - bits;
+
+    if signed {
+        src.wrapping_shl(shift).wrapping_shr(shift)
+    } else {
+        ((src as u64).wrapping_shl(shift).wrapping_shr(shift)) as i64
+    }
+}
+
+/// Run a program and return the value `main` returns (0 if it returns `()`)
+pub fn run(program: &Program) -> i64 {
+    let mut interpreter = Interpreter::new(program);
+    interpreter.call(Ident::from_str("main"), &[])
+}
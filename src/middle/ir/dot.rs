@@ -0,0 +1,56 @@
+//! Export the control flow graph of IR functions to Graphviz
+
+use middle::ir::*;
+use util::dot::Digraph;
+
+/// Render one digraph per function
+///
+/// Every block becomes a node showing its phis, instructions and terminator.
+/// The edges of a conditional branch are labeled `true` and `false`.
+pub fn cfg_dot(program: &Program) -> String {
+    let mut out = String::new();
+
+    for symbol in program {
+        if let Symbol::Function { name, ref body, .. } = *symbol {
+            let mut graph = Digraph::new(&name);
+
+            for block in body {
+                let label = block.label.to_string();
+                graph.node(
+                    &label,
+                    block.to_string().lines().map(String::from).collect(),
+                );
+
+                match block.last {
+                    ControlFlowInstruction::Branch { conseq, altern, .. } => {
+                        graph.edge(&label, &conseq.to_string(), Some("true"));
+                        graph.edge(&label, &altern.to_string(), Some("false"));
+                    }
+                    ControlFlowInstruction::Jump { dest } => {
+                        graph.edge(&label, &dest.to_string(), None);
+                    }
+                    ControlFlowInstruction::Switch {
+                        ref cases, default, ..
+                    } => {
+                        for &(lo, hi, dest) in cases {
+                            let case = if lo == hi {
+                                lo.to_string()
+                            } else {
+                                format!("{}..={}", lo, hi)
+                            };
+                            graph.edge(&label, &dest.to_string(), Some(&case));
+                        }
+                        graph.edge(&label, &default.to_string(), Some("_"));
+                    }
+                    ControlFlowInstruction::Return { .. }
+                    | ControlFlowInstruction::Trap { .. }
+                    | ControlFlowInstruction::NotYetProcessed => {}
+                }
+            }
+
+            out.push_str(&graph.to_string());
+        }
+    }
+
+    out
+}
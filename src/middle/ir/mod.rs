@@ -6,9 +6,11 @@ use std::iter::IntoIterator;
 use std::slice;
 use std::vec::IntoIter;
 
+mod dot;
 mod trans;
 pub mod visit;
 
+pub use middle::ir::dot::cfg_dot;
 pub use middle::ir::trans::translate;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -34,6 +36,9 @@ pub enum Value {
 
     /// The address of a static symbol
     Static(Ident),
+
+    /// The memory a local register points to (e.g. an array element)
+    Indirect(Register),
 }
 
 impl Value {
@@ -69,10 +74,10 @@ impl Register {
 }
 
 #[derive(Clone, Copy, PartialEq, Hash)]
-pub struct Immediate(pub u32);
+pub struct Immediate(pub i64);
 
 impl Immediate {
-    pub fn val(self) -> u32 {
+    pub fn val(self) -> i64 {
         self.0
     }
 }
@@ -131,11 +136,22 @@ pub enum Symbol {
         name: Ident,
         value: Immediate,
     },
+    /// A read-only string (the contents of a string literal)
+    Str {
+        name: Ident,
+        value: Ident,
+    },
     Function {
         name: Ident,
         body: Vec<Block>,
         args: Vec<Ident>,
     },
+    /// A function that is defined outside of the program (e.g. in libc).
+    /// It can be called but has no body.
+    Extern {
+        name: Ident,
+        args: Vec<Ident>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -185,6 +201,30 @@ impl Block {
         self.last = ControlFlowInstruction::Jump { dest }
     }
 
+    fn switch(&mut self, value: Value, cases: Vec<(Immediate, Immediate, Label)>, default: Label) {
+        assert_eq!(
+            self.last,
+            ControlFlowInstruction::NotYetProcessed,
+            "self.last is already set: `{}`",
+            self.last
+        );
+        self.last = ControlFlowInstruction::Switch {
+            value,
+            cases,
+            default,
+        }
+    }
+
+    fn trap(&mut self, msg: Ident) {
+        assert_eq!(
+            self.last,
+            ControlFlowInstruction::NotYetProcessed,
+            "self.last is already set: `{}`",
+            self.last
+        );
+        self.last = ControlFlowInstruction::Trap { msg }
+    }
+
     fn binop(&mut self, op: InfixOp, lhs: Value, rhs: Value, dst: Register) {
         assert_eq!(
             self.last,
@@ -216,7 +256,22 @@ impl Block {
         self.inst.push_back(Instruction::Cmp { cmp, lhs, rhs, dst })
     }
 
-    fn alloc(&mut self, reg: Register) {
+    fn extend(&mut self, signed: bool, bits: u32, src: Value, dst: Register) {
+        assert_eq!(
+            self.last,
+            ControlFlowInstruction::NotYetProcessed,
+            "self.last is already set: `{}`",
+            self.last
+        );
+        self.inst.push_back(Instruction::Extend {
+            signed,
+            bits,
+            src,
+            dst,
+        })
+    }
+
+    fn alloc(&mut self, reg: Register, size: u32) {
         // No assert here because allocas are always placed in the first block
         // which may already be finalized
 
@@ -236,10 +291,21 @@ impl Block {
 
         // Insert new alloca after last alloca
         let mut non_allocas = self.inst.split_off(insert_pos);
-        self.inst.push_back(Instruction::Alloca { dst: reg });
+        self.inst.push_back(Instruction::Alloca { dst: reg, size });
         self.inst.append(&mut non_allocas);
     }
 
+    fn index(&mut self, base: Value, index: Value, dst: Register) {
+        assert_eq!(
+            self.last,
+            ControlFlowInstruction::NotYetProcessed,
+            "self.last is already set: `{}`",
+            self.last
+        );
+
+        self.inst.push_back(Instruction::Index { base, index, dst })
+    }
+
     fn load(&mut self, src: Value, dst: Register) {
         assert_eq!(
             self.last,
@@ -321,6 +387,19 @@ pub enum ControlFlowInstruction {
     Jump {
         dest: Label,
     },
+    /// Jump to the label of the first case whose range (`lo..=hi`) contains
+    /// the value or to `default` if there is none
+    ///
+    /// The bounds are compared as signed integers.
+    Switch {
+        value: Value,
+        cases: Vec<(Immediate, Immediate, Label)>,
+        default: Label,
+    },
+    /// Abort the program with an error message (e.g. a failed bounds check)
+    Trap {
+        msg: Ident,
+    },
     NotYetProcessed,
 }
 
@@ -331,6 +410,19 @@ impl ControlFlowInstruction {
                 vec![conseq.ident(), altern.ident()]
             }
             ControlFlowInstruction::Jump { dest } => vec![dest.ident()],
+            ControlFlowInstruction::Switch {
+                ref cases, default, ..
+            } => {
+                // Several cases may jump to the same arm
+                let mut successors = Vec::new();
+                for label in cases.iter().map(|case| case.2).chain(Some(default)) {
+                    if !successors.contains(&label.ident()) {
+                        successors.push(label.ident());
+                    }
+                }
+
+                successors
+            }
             _ => Vec::new(),
         }
     }
@@ -363,9 +455,24 @@ pub enum Instruction {
         dst: Register,
     },
 
+    /// Truncate a value to its lower `bits` bits and sign- or zero-extend
+    /// the result to a full word
+    Extend {
+        signed: bool,
+        bits: u32,
+        src: Value,
+        dst: Register,
+    },
+
     // MemOp
     Alloca {
         dst: Register, // Where to put the address
+        size: u32,     // The number of slots to allocate
+    },
+    Index {
+        base: Value,   // The address of the first slot (Stack register or pointer)
+        index: Value,  // The number of slots to skip
+        dst: Register, // Where to put the resulting address
     },
     Load {
         src: Value,    // The memory address
@@ -396,6 +503,17 @@ pub enum InfixOp {
     Shl, // <<
     Shr, // >>
 
+    // Unsigned variants
+    UDiv, // /
+    UMod, // %
+    UShr, // >>
+
+    // Floating point variants
+    FAdd, // +
+    FSub, // -
+    FMul, // *
+    FDiv, // /
+
     // Bitwise
     And, // &
     Or,  // |
@@ -421,6 +539,34 @@ impl InfixOp {
             _ => panic!("InfixOp::from_ast_op with invalid op: `{}`", op),
         }
     }
+
+    /// The variant of the operation that treats its operands as unsigned
+    pub fn unsigned(self) -> InfixOp {
+        match self {
+            InfixOp::Div => InfixOp::UDiv,
+            InfixOp::Mod => InfixOp::UMod,
+            InfixOp::Shr => InfixOp::UShr,
+            op => op,
+        }
+    }
+
+    /// The variant of the operation that treats its operands as floats
+    pub fn float(self) -> InfixOp {
+        match self {
+            InfixOp::Add => InfixOp::FAdd,
+            InfixOp::Sub => InfixOp::FSub,
+            InfixOp::Mul => InfixOp::FMul,
+            InfixOp::Div => InfixOp::FDiv,
+            op => panic!("no floating point variant of `{}`", op),
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(
+            self,
+            InfixOp::FAdd | InfixOp::FSub | InfixOp::FMul | InfixOp::FDiv
+        )
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash)]
@@ -430,6 +576,11 @@ pub enum PrefixOp {
 
     // Bitwise
     Not, // !
+
+    // Floating point
+    FNeg,       // -
+    IntToFloat, // int as f64
+    FloatToInt, // f64 as int
 }
 
 impl PrefixOp {
@@ -439,6 +590,11 @@ impl PrefixOp {
             ast::UnOp::Not => PrefixOp::Not,
         }
     }
+
+    /// Whether the operand is a float
+    pub fn has_float_operand(self) -> bool {
+        matches!(self, PrefixOp::FNeg | PrefixOp::FloatToInt)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash)]
@@ -449,6 +605,20 @@ pub enum CmpOp {
     Ne, // !=
     Ge, // >=
     Gt, // >
+
+    // Unsigned variants
+    ULt, // <
+    ULe, // <=
+    UGe, // >=
+    UGt, // >
+
+    // Floating point variants (false if an operand is NaN, except for `ne`)
+    FLt, // <
+    FLe, // <=
+    FEq, // ==
+    FNe, // !=
+    FGe, // >=
+    FGt, // >
 }
 
 impl CmpOp {
@@ -463,6 +633,37 @@ impl CmpOp {
             _ => panic!("CmpOp::from_ast_op with invalid op: `{}`", op),
         }
     }
+
+    /// The variant of the comparison that treats its operands as unsigned
+    pub fn unsigned(self) -> CmpOp {
+        match self {
+            CmpOp::Lt => CmpOp::ULt,
+            CmpOp::Le => CmpOp::ULe,
+            CmpOp::Ge => CmpOp::UGe,
+            CmpOp::Gt => CmpOp::UGt,
+            op => op,
+        }
+    }
+
+    /// The variant of the comparison that treats its operands as floats
+    pub fn float(self) -> CmpOp {
+        match self {
+            CmpOp::Lt => CmpOp::FLt,
+            CmpOp::Le => CmpOp::FLe,
+            CmpOp::Eq => CmpOp::FEq,
+            CmpOp::Ne => CmpOp::FNe,
+            CmpOp::Ge => CmpOp::FGe,
+            CmpOp::Gt => CmpOp::FGt,
+            op => panic!("no floating point variant of `{}`", op),
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(
+            self,
+            CmpOp::FLt | CmpOp::FLe | CmpOp::FEq | CmpOp::FNe | CmpOp::FGe | CmpOp::FGt
+        )
+    }
 }
 
 // --- Debug implementations ----------------------------------------------------
@@ -494,6 +695,21 @@ impl fmt::Display for Value {
             Value::Immediate(i) => write!(f, "{}", i),
             Value::Register(r) => write!(f, "{}", r),
             Value::Static(s) => write!(f, "@{}", s),
+            Value::Indirect(r) => write!(f, "[{}]", r),
+        }
+    }
+}
+
+/// An operand of a floating point operation
+///
+/// Immediates hold the float's bit pattern, but are displayed as floats.
+struct FloatOperand<'a>(&'a Value);
+
+impl<'a> fmt::Display for FloatOperand<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self.0 {
+            Value::Immediate(Immediate(bits)) => write!(f, "{:?}", f64::from_bits(bits as u64)),
+            ref value => write!(f, "{}", value),
         }
     }
 }
@@ -533,6 +749,13 @@ impl fmt::Display for Symbol {
                 writeln!(f, "static {} = {}", name, value)?;
                 writeln!(f)?;
             }
+            Symbol::Str {
+                ref name,
+                ref value,
+            } => {
+                writeln!(f, "str {} = \"{}\"", name, value.escape_default())?;
+                writeln!(f)?;
+            }
             Symbol::Function {
                 ref name,
                 ref body,
@@ -550,6 +773,10 @@ impl fmt::Display for Symbol {
                 writeln!(f, "}}")?;
                 writeln!(f)?;
             }
+            Symbol::Extern { ref name, ref args } => {
+                writeln!(f, "extern fn {}({})", name, connect!(args, "{}", ", "))?;
+                writeln!(f)?;
+            }
         }
 
         Ok(())
@@ -596,6 +823,25 @@ impl fmt::Display for ControlFlowInstruction {
                 ref altern,
             } => write!(f, "br {} {} {}", cond, conseq, altern),
             ControlFlowInstruction::Jump { ref dest } => write!(f, "jmp {}", dest),
+            ControlFlowInstruction::Switch {
+                ref value,
+                ref cases,
+                ref default,
+            } => {
+                let cases: Vec<_> = cases
+                    .iter()
+                    .map(|&(lo, hi, dest)| {
+                        if lo == hi {
+                            format!("{}: {}", lo, dest)
+                        } else {
+                            format!("{}..={}: {}", lo, hi, dest)
+                        }
+                    })
+                    .collect();
+
+                write!(f, "switch {} [{}] {}", value, cases.join(", "), default)
+            }
+            ControlFlowInstruction::Trap { ref msg } => write!(f, "trap {:?}", &**msg),
             ControlFlowInstruction::NotYetProcessed => write!(f, "<...>"),
         }
     }
@@ -604,27 +850,73 @@ impl fmt::Display for ControlFlowInstruction {
 impl fmt::Display for Instruction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Instruction::BinOp {
+                op,
+                ref lhs,
+                ref rhs,
+                ref dst,
+            } if op.is_float() => write!(
+                f,
+                "{} = {} {} {}",
+                dst,
+                op,
+                FloatOperand(lhs),
+                FloatOperand(rhs)
+            ),
             Instruction::BinOp {
                 ref op,
                 ref lhs,
                 ref rhs,
                 ref dst,
             } => write!(f, "{} = {} {} {}", dst, op, lhs, rhs),
+            Instruction::UnOp {
+                op,
+                ref item,
+                ref dst,
+            } if op.has_float_operand() => write!(f, "{} = {} {}", dst, op, FloatOperand(item)),
             Instruction::UnOp {
                 ref op,
                 ref item,
                 ref dst,
             } => write!(f, "{} = {} {}", dst, op, item),
 
+            Instruction::Cmp {
+                cmp,
+                ref lhs,
+                ref rhs,
+                ref dst,
+            } if cmp.is_float() => write!(
+                f,
+                "{} = cmp {} {} {}",
+                dst,
+                cmp,
+                FloatOperand(lhs),
+                FloatOperand(rhs)
+            ),
             Instruction::Cmp {
                 ref cmp,
                 ref lhs,
                 ref rhs,
                 ref dst,
             } => write!(f, "{} = cmp {} {} {}", dst, cmp, lhs, rhs),
+            Instruction::Extend {
+                signed,
+                bits,
+                ref src,
+                ref dst,
+            } => {
+                let op = if signed { "sext" } else { "zext" };
+                write!(f, "{} = {}{} {}", dst, op, bits, src)
+            }
 
             // MemOp
-            Instruction::Alloca { ref dst } => write!(f, "{} = alloca", dst),
+            Instruction::Alloca { ref dst, size: 1 } => write!(f, "{} = alloca", dst),
+            Instruction::Alloca { ref dst, size } => write!(f, "{} = alloca {}", dst, size),
+            Instruction::Index {
+                ref base,
+                ref index,
+                ref dst,
+            } => write!(f, "{} = index {} {}", dst, base, index),
             Instruction::Load { ref src, ref dst } => write!(f, "{} = load {}", dst, src),
             Instruction::Store { ref src, ref dst } => write!(f, "store {} {}", src, dst),
 
@@ -664,6 +956,13 @@ impl fmt::Display for InfixOp {
             InfixOp::Mod => write!(f, "mod"),
             InfixOp::Shl => write!(f, "shl"),
             InfixOp::Shr => write!(f, "shr"),
+            InfixOp::UDiv => write!(f, "udiv"),
+            InfixOp::UMod => write!(f, "umod"),
+            InfixOp::UShr => write!(f, "ushr"),
+            InfixOp::FAdd => write!(f, "fadd"),
+            InfixOp::FSub => write!(f, "fsub"),
+            InfixOp::FMul => write!(f, "fmul"),
+            InfixOp::FDiv => write!(f, "fdiv"),
 
             InfixOp::And => write!(f, "and"),
             InfixOp::Or => write!(f, "or"),
@@ -677,6 +976,9 @@ impl fmt::Display for PrefixOp {
         match *self {
             PrefixOp::Neg => write!(f, "neg"),
             PrefixOp::Not => write!(f, "not"),
+            PrefixOp::FNeg => write!(f, "fneg"),
+            PrefixOp::IntToFloat => write!(f, "sitofp"),
+            PrefixOp::FloatToInt => write!(f, "fptosi"),
         }
     }
 }
@@ -690,6 +992,16 @@ impl fmt::Display for CmpOp {
             CmpOp::Ne => write!(f, "ne"),
             CmpOp::Ge => write!(f, "ge"),
             CmpOp::Gt => write!(f, "gt"),
+            CmpOp::ULt => write!(f, "ult"),
+            CmpOp::ULe => write!(f, "ule"),
+            CmpOp::UGe => write!(f, "uge"),
+            CmpOp::UGt => write!(f, "ugt"),
+            CmpOp::FLt => write!(f, "flt"),
+            CmpOp::FLe => write!(f, "fle"),
+            CmpOp::FEq => write!(f, "feq"),
+            CmpOp::FNe => write!(f, "fne"),
+            CmpOp::FGe => write!(f, "fge"),
+            CmpOp::FGt => write!(f, "fgt"),
         }
     }
 }
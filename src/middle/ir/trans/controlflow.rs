@@ -5,107 +5,390 @@
 use driver::interner::Ident;
 use front::ast;
 use middle::ir;
-use middle::ir::trans::{Dest, Translator};
+use middle::ir::trans::{Dest, LoopLabels, Translator};
+
+/// Evaluates a loop's condition
+type LoopCond<'a> = &'a dyn Fn(&mut Translator, &mut ir::Block) -> ir::Value;
+
+/// Advances a loop to its next iteration, may leave it by jumping to the
+/// exit label
+type LoopStep<'a> = &'a dyn Fn(&mut Translator, &mut ir::Block, ir::Label);
 
 impl Translator {
     /// Translate a return statement
-    pub fn trans_return(&mut self, val: &ast::Expression, block: &mut ir::Block) {
+    pub fn trans_return(&mut self, val: &ast::Node<ast::Expression>, block: &mut ir::Block) {
+        let return_slot = match self.fcx().return_slot {
+            Some(slot) => slot,
+            None => {
+                // Functions without a return value don't have a return block
+                self.trans_expr(val, block, Dest::Ignore);
+                block.ret(None);
+                return;
+            }
+        };
+
         // Store the return value in the return slot and jump to the return block
         let val = self.trans_expr_to_value(val, block);
-        let return_slot = self.fcx().return_slot.unwrap();
+        let return_label = self.return_label();
 
         block.store_reg(val, return_slot);
-        block.jump(ir::Label(Ident::from_str("return")));
+        block.jump(return_label);
     }
 
     /// Translate an if expression
     pub fn trans_if(
         &mut self,
-        cond: &ast::Expression,
+        cond: &ast::Node<ast::Expression>,
         conseq: &ast::Node<ast::Block>,
         altern: Option<&ast::Node<ast::Block>>,
         block: &mut ir::Block,
         dest: Dest,
+    ) {
+        let label_next = self.next_free_label(Ident::from_str("next"));
+        self.trans_if_chain(cond, conseq, altern, block, dest, label_next);
+        self.commit_block_and_continue(block, label_next);
+    }
+
+    /// Translate an if expression whose branches continue at `label_next`
+    ///
+    /// The `if` of an `else if` is translated right into the `else` block
+    /// and continues at the same label. Otherwise it would get a join block
+    /// of its own which does nothing but jump to ours.
+    fn trans_if_chain(
+        &mut self,
+        cond: &ast::Node<ast::Expression>,
+        conseq: &ast::Node<ast::Block>,
+        altern: Option<&ast::Node<ast::Block>>,
+        block: &mut ir::Block,
+        dest: Dest,
+        label_next: ir::Label,
     ) {
         let cond_ir = self.trans_expr_to_value(cond, block);
 
-        match altern {
-            Some(altern) => {
-                let label_conseq = self.next_free_label(Ident::from_str("conseq"));
-                let label_altern = self.next_free_label(Ident::from_str("altern"));
-                let label_next = self.next_free_label(Ident::from_str("next"));
+        let label_conseq = self.next_free_label(Ident::from_str("conseq"));
+        let label_altern = match altern {
+            Some(..) => self.next_free_label(Ident::from_str("altern")),
+            None => label_next,
+        };
 
-                block.branch(cond_ir, label_conseq, label_altern);
+        block.branch(cond_ir, label_conseq, label_altern);
 
-                // The 'then' block
-                self.commit_block_and_continue(block, label_conseq);
-                self.trans_block(conseq, block, dest);
-                // FIXME: Better solution?
-                if !block.finalized() {
-                    block.jump(label_next); // Skip the 'else' part
-                }
+        // The 'then' block
+        self.commit_block_and_continue(block, label_conseq);
+        self.trans_block(conseq, block, dest);
+        if !block.finalized() {
+            block.jump(label_next); // Skip the 'else' part
+        }
 
-                // The 'else' block
-                self.commit_block_and_continue(block, label_altern);
-                self.trans_block(altern, block, dest);
-                if !block.finalized() {
-                    block.jump(label_next);
-                }
+        // The 'else' block
+        if let Some(altern) = altern {
+            self.commit_block_and_continue(block, label_altern);
 
-                self.commit_block_and_continue(block, label_next);
+            match **altern.expr {
+                ast::Expression::If {
+                    ref cond,
+                    ref conseq,
+                    altern: ref inner_altern,
+                } if altern.stmts.is_empty() => {
+                    with_reset!(self.fcx().scope, altern.id, {
+                        self.trans_if_chain(
+                            cond,
+                            conseq,
+                            inner_altern.as_ref().map(|b| &**b),
+                            block,
+                            dest,
+                            label_next,
+                        );
+                    })
+                }
+                _ => {
+                    self.trans_block(altern, block, dest);
+                    if !block.finalized() {
+                        block.jump(label_next);
+                    }
+                }
             }
-            None => {
-                let label_conseq = self.next_free_label(Ident::from_str("conseq"));
-                let label_next = self.next_free_label(Ident::from_str("next"));
+        }
+    }
 
-                block.branch(cond_ir, label_conseq, label_next);
+    /// Translate a match expression
+    pub fn trans_match(
+        &mut self,
+        expr: &ast::Node<ast::Expression>,
+        arms: &[ast::Arm],
+        block: &mut ir::Block,
+        dest: Dest,
+    ) {
+        let value = self.trans_expr_to_value(expr, block);
+
+        let labels: Vec<_> = arms
+            .iter()
+            .map(|_| self.next_free_label(Ident::from_str("arm")))
+            .collect();
+        let label_next = self.next_free_label(Ident::from_str("next"));
 
-                // The 'then' block
-                self.commit_block_and_continue(block, label_conseq);
-                self.trans_block(conseq, block, dest);
-                if !block.finalized() {
-                    block.jump(label_next);
+        let mut cases = Vec::new();
+        let mut default = None;
+
+        for (arm, &label) in arms.iter().zip(&labels) {
+            for pattern in &arm.patterns {
+                let (lo, hi) = match **pattern {
+                    ast::Pattern::Literal(val) => (val, val),
+                    ast::Pattern::Range(lo, hi) => (lo, hi),
+                    ast::Pattern::Wildcard => {
+                        default = default.or(Some(label));
+                        continue;
+                    }
+                };
+
+                // Cases after a wildcard can't be reached
+                if default.is_none() {
+                    cases.push((
+                        ir::Immediate(lo.as_i64()),
+                        ir::Immediate(hi.as_i64()),
+                        label,
+                    ));
                 }
+            }
+        }
+
+        // Without a wildcard the arms cover every value, so all values that
+        // don't match one of the other arms go to the last one
+        let default = default.unwrap_or_else(|| {
+            let last = *labels.last().expect("exhaustive `match` without arms");
+            cases.retain(|case| case.2 != last);
+            last
+        });
+
+        block.switch(value, cases, default);
 
-                self.commit_block_and_continue(block, label_next);
+        for (arm, &label) in arms.iter().zip(&labels) {
+            self.commit_block_and_continue(block, label);
+            self.trans_block(&arm.body, block, dest);
+            if !block.finalized() {
+                block.jump(label_next);
             }
         }
+
+        self.commit_block_and_continue(block, label_next);
     }
 
-    /// Translate a while expression
+    /// Translate a while loop
+    ///
+    /// `cond` evaluates the loop's condition. `step` runs after the body and
+    /// on `continue`, before the condition is checked again (`for` loops
+    /// advance their loop variable there). It may leave the loop early by
+    /// jumping to the exit label it's passed.
     pub fn trans_while(
         &mut self,
-        cond: &ast::Expression,
+        cond: LoopCond,
+        step: Option<LoopStep>,
         body: &ast::Node<ast::Block>,
+        label: Option<Ident>,
         block: &mut ir::Block,
     ) {
         let label_cond = self.next_free_label(Ident::from_str("while_cond"));
         let label_body = self.next_free_label(Ident::from_str("while_body"));
+        let label_step = match step {
+            Some(..) => self.next_free_label(Ident::from_str("while_step")),
+            None => label_cond,
+        };
         let label_next = self.next_free_label(Ident::from_str("while_exit"));
 
         block.jump(label_cond);
 
-        with_reset!(self.fcx().loop_exit, Some(label_next), {
-            // Condition block
-            self.commit_block_and_continue(block, label_cond);
-            let cond = self.trans_expr_to_value(cond, block);
-            block.branch(cond, label_body, label_next);
+        self.fcx().loops.push(LoopLabels {
+            name: label,
+            header: label_step,
+            exit: label_next,
+            values: Vec::new(),
+        });
+
+        // Condition block
+        self.commit_block_and_continue(block, label_cond);
+        let cond = cond(self, block);
+        block.branch(cond, label_body, label_next);
 
-            // Body block
-            self.commit_block_and_continue(block, label_body);
-            self.trans_block(body, block, Dest::Ignore);
-            if !block.finalized() {
-                // After executing the body, re-check the condition
-                block.jump(label_cond);
-            }
+        // Body block
+        self.commit_block_and_continue(block, label_body);
+        self.trans_block(body, block, Dest::Ignore);
+        if !block.finalized() {
+            // After executing the body, re-check the condition
+            block.jump(label_step);
+        }
+
+        self.fcx().loops.pop();
 
-            // Exit block
-            self.commit_block_and_continue(block, label_next);
+        // Step block
+        if let Some(step) = step {
+            self.commit_block_and_continue(block, label_step);
+            step(self, block, label_next);
+            block.jump(label_cond);
+        }
+
+        // Exit block
+        self.commit_block_and_continue(block, label_next);
+    }
+
+    /// Translate an infinite loop
+    ///
+    /// The values the loop is left with are merged with a phi in the exit
+    /// block.
+    pub fn trans_loop(
+        &mut self,
+        body: &ast::Node<ast::Block>,
+        label: Option<Ident>,
+        block: &mut ir::Block,
+        dest: Dest,
+    ) {
+        let label_body = self.next_free_label(Ident::from_str("loop_body"));
+        let label_next = self.next_free_label(Ident::from_str("loop_exit"));
+
+        block.jump(label_body);
+
+        self.fcx().loops.push(LoopLabels {
+            name: label,
+            header: label_body,
+            exit: label_next,
+            values: Vec::new(),
+        });
+
+        // Body block
+        self.commit_block_and_continue(block, label_body);
+        self.trans_block(body, block, Dest::Ignore);
+        if !block.finalized() {
+            block.jump(label_body);
+        }
+
+        let values = self.fcx().loops.pop().unwrap().values;
+
+        // Exit block, only reachable by `break`
+        self.commit_block_and_continue(block, label_next);
+        if !values.is_empty() {
+            block.phi(values, self.unwrap_dest(dest));
+        }
+    }
+
+    /// Translate a for loop over an integer range
+    ///
+    /// The loop is desugared into the `while` loop it stands for:
+    ///
+    /// ```ignore
+    /// let i = start;
+    /// while i < end {
+    ///     body;
+    ///     i += 1;
+    /// }
+    /// ```
+    ///
+    /// The range is evaluated only once. For inclusive ranges the condition
+    /// is `i <= end` and the loop is left before incrementing `i` past `end`,
+    /// so the loop variable can't overflow.
+    pub fn trans_for(
+        &mut self,
+        var: &ast::Node<ast::Binding>,
+        range: (&ast::Node<ast::Expression>, &ast::Node<ast::Expression>),
+        inclusive: bool,
+        body: &ast::Node<ast::Block>,
+        label: Option<Ident>,
+        block: &mut ir::Block,
+    ) {
+        // The range can't refer to the loop variable
+        let start = self.trans_expr_to_value(range.0, block);
+        let end = self.trans_expr_to_value(range.1, block);
+
+        // The loop variable lives in a scope of its own
+        with_reset!(self.fcx().scope, var.id, {
+            let slot = self.register_stack_slot(*var.name);
+            self.with_first_block(block, |block| block.alloc(slot, 1));
+            block.store_reg(start, slot);
+
+            let cmp = if inclusive {
+                ir::CmpOp::Le
+            } else {
+                ir::CmpOp::Lt
+            };
+
+            self.trans_while(
+                &|this, block| {
+                    let current = this.next_free_register();
+                    block.load(ir::Value::Register(slot), current);
+                    let cond = this.next_free_register();
+                    block.cmp(cmp, ir::Value::Register(current), end, cond);
+                    ir::Value::Register(cond)
+                },
+                Some(&|this, block, label_next| {
+                    let current = this.next_free_register();
+                    block.load(ir::Value::Register(slot), current);
+
+                    if inclusive {
+                        // Stop when `end` has been reached as `end + 1` might
+                        // not be representable
+                        let label_incr = this.next_free_label(Ident::from_str("for_incr"));
+                        let cond = this.next_free_register();
+                        block.cmp(ir::CmpOp::Lt, ir::Value::Register(current), end, cond);
+                        block.branch(ir::Value::Register(cond), label_incr, label_next);
+                        this.commit_block_and_continue(block, label_incr);
+                    }
+
+                    let next = this.next_free_register();
+                    block.binop(
+                        ir::InfixOp::Add,
+                        ir::Value::Register(current),
+                        ir::Value::Immediate(ir::Immediate(1)),
+                        next,
+                    );
+                    block.store_reg(ir::Value::Register(next), slot);
+                }),
+                body,
+                label,
+                block,
+            );
         });
     }
 
     /// Translate a break expression
-    pub fn trans_break(&mut self, block: &mut ir::Block) {
-        block.jump(self.fcx().loop_exit.unwrap());
+    pub fn trans_break(
+        &mut self,
+        label: Option<Ident>,
+        value: Option<&ast::Node<ast::Expression>>,
+        block: &mut ir::Block,
+    ) {
+        if let Some(value) = value {
+            let is_unit = self.type_of(value) == ast::Type::Unit;
+            // Phis only merge registers
+            let value = self.trans_expr_to_temporary(value, block);
+
+            // `break ()` doesn't produce a value to merge
+            if !is_unit {
+                let from = block.label;
+                self.target_loop(label).values.push((value, from));
+            }
+        }
+
+        let exit = self.target_loop(label).exit;
+        block.jump(exit);
+    }
+
+    /// Translate a continue expression
+    pub fn trans_continue(&mut self, label: Option<Ident>, block: &mut ir::Block) {
+        let header = self.target_loop(label).header;
+        block.jump(header);
+    }
+
+    /// Find the loop a `break`/`continue` refers to
+    fn target_loop(&mut self, label: Option<Ident>) -> &mut LoopLabels {
+        let loops = &mut self.fcx().loops;
+
+        match label {
+            Some(label) => loops
+                .iter_mut()
+                .rev()
+                .find(|l| l.name == Some(label))
+                .unwrap_or_else(|| panic!("no loop labeled `'{}`", label)),
+            None => loops
+                .last_mut()
+                .expect("`break`/`continue` outside of loop"),
+        }
     }
 }
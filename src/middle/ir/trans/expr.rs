@@ -1,15 +1,26 @@
 //! Translate of expressions
 
 use driver;
+use driver::codemap::BytePos;
 use driver::interner::Ident;
 use front::ast;
 use middle::ir;
 use middle::ir::trans::{Dest, Translator, VariableKind};
+use std::collections::VecDeque;
+
+/// Up to how many elements array initializations and copies are unrolled
+/// instead of being translated into a loop
+const UNROLL_LIMIT: u32 = 8;
 
 impl Translator {
     /// Translate an expression
-    pub fn trans_expr(&mut self, expr: &ast::Expression, block: &mut ir::Block, dest: Dest) {
-        match *expr {
+    pub fn trans_expr(
+        &mut self,
+        expr: &ast::Node<ast::Expression>,
+        block: &mut ir::Block,
+        dest: Dest,
+    ) {
+        match **expr {
             ast::Expression::Literal { ref val } => {
                 self.trans_literal(val, block, dest);
             }
@@ -25,25 +36,100 @@ impl Translator {
             ast::Expression::Return { ref val } => self.trans_return(val, block),
             ast::Expression::Call { ref func, ref args } => {
                 // Get the &Expr out of the &Node<Expr>
-                let args: Vec<_> = args.iter().map(|expr| &**expr).collect();
-                self.trans_call(&func.unwrap_ident(), &args[..], block, dest);
+                let mut args: Vec<_> = args.iter().collect();
+
+                if let ast::Expression::Field {
+                    ref object,
+                    ref field,
+                } = ***func
+                {
+                    // A method call: pass the object as `self`
+                    let method = match self.type_of(object) {
+                        ast::Type::Struct(s) => s.mangle_method(**field),
+                        ty => panic!("{} has no methods", ty),
+                    };
+                    args.insert(0, object);
+
+                    self.trans_call(&method, &args[..], block, dest);
+                } else {
+                    self.trans_call(&func.unwrap_ident(), &args[..], block, dest);
+                }
             }
             ast::Expression::Group(ref expr) => {
                 self.trans_expr(expr, block, dest);
             }
+            ast::Expression::Array { .. }
+            | ast::Expression::ArrayRepeat { .. }
+            | ast::Expression::Struct { .. } => {
+                // The value isn't used, but its elements might have side
+                // effects
+                self.trans_place(expr, block);
+            }
+            ast::Expression::Index {
+                ref array,
+                ref index,
+            } => {
+                // %dest = load [%elem]
+                let elem = self.trans_element(array, index, block);
+                block.load(elem, self.unwrap_dest(dest));
+            }
+            ast::Expression::Field {
+                ref object,
+                ref field,
+            } => {
+                // %dest = load [%field]
+                let field = self.trans_field(object, **field, block);
+                block.load(field, self.unwrap_dest(dest));
+            }
             ast::Expression::Infix {
                 op,
                 ref lhs,
                 ref rhs,
             } => self.trans_infix(op, lhs, rhs, block, dest),
             ast::Expression::Prefix { op, ref item } => self.trans_prefix(op, item, block, dest),
+            ast::Expression::Cast {
+                expr: ref inner,
+                ty,
+            } => self.trans_cast(inner, ty, block, dest),
             ast::Expression::If {
                 ref cond,
                 ref conseq,
                 ref altern,
             } => self.trans_if(cond, conseq, altern.as_ref().map(|b| &**b), block, dest),
-            ast::Expression::While { ref cond, ref body } => self.trans_while(cond, body, block),
-            ast::Expression::Break => self.trans_break(block),
+            ast::Expression::Match {
+                expr: ref scrutinee,
+                ref arms,
+            } => self.trans_match(scrutinee, arms, block, dest),
+            ast::Expression::While {
+                ref cond,
+                ref body,
+                ref label,
+            } => self.trans_while(
+                &|this, block| this.trans_expr_to_value(cond, block),
+                None,
+                body,
+                label_name(label),
+                block,
+            ),
+            ast::Expression::For {
+                ref var,
+                ref start,
+                ref end,
+                inclusive,
+                ref body,
+                ref label,
+            } => self.trans_for(var, (start, end), inclusive, body, label_name(label), block),
+            ast::Expression::Loop {
+                ref body,
+                ref label,
+            } => self.trans_loop(body, label_name(label), block, dest),
+            ast::Expression::Break {
+                ref label,
+                ref value,
+            } => self.trans_break(label_name(label), value.as_ref().map(|v| &**v), block),
+            ast::Expression::Continue { ref label } => {
+                self.trans_continue(label_name(label), block)
+            }
             ast::Expression::Unit => {}
         }
     }
@@ -56,15 +142,20 @@ impl Translator {
     /// the result. Enter `trans_expr_to_value`:
     pub fn trans_expr_to_value(
         &mut self,
-        expr: &ast::Expression,
+        expr: &ast::Node<ast::Expression>,
         block: &mut ir::Block,
     ) -> ir::Value {
-        // Special handling for literals: return the immediate value
-        if let ast::Expression::Literal { ref val } = *expr {
-            return ir::Value::Immediate(ir::Immediate(val.as_u32()));
+        // Special handling for literals: return the immediate value or the
+        // symbol holding a string
+        if let ast::Expression::Literal { ref val } = **expr {
+            if let ast::Value::Str(s) = *val {
+                return ir::Value::Static(self.string_symbol(s));
+            }
+
+            return ir::Value::Immediate(ir::Immediate(val.as_i64()));
         }
 
-        if let ast::Expression::Variable { ref name } = *expr {
+        if let ast::Expression::Variable { ref name } = **expr {
             // Look up of which kind the variable is
             let vkind = self.variable_kind(name);
 
@@ -74,16 +165,16 @@ impl Translator {
                 let sytable = &driver::session().symbol_table;
                 let symbol = sytable.lookup_symbol(name).unwrap();
                 let val = symbol.get_value().unwrap_literal();
-                return ir::Value::Immediate(ir::Immediate(val.as_u32()));
+                return ir::Value::Immediate(ir::Immediate(val.as_i64()));
             }
         }
 
         self.trans_expr_to_temporary(expr, block)
     }
 
-    fn trans_expr_to_temporary(
+    pub fn trans_expr_to_temporary(
         &mut self,
-        expr: &ast::Expression,
+        expr: &ast::Node<ast::Expression>,
         block: &mut ir::Block,
     ) -> ir::Value {
         let tmp = self.next_free_register();
@@ -91,31 +182,381 @@ impl Translator {
         ir::Value::Register(tmp)
     }
 
-    fn assign_dest(&mut self, dest: Ident) -> ir::Value {
+    fn assign_dest(&mut self, dest: &ast::Node<Ident>, block: &mut ir::Block) -> ir::Value {
         // Look up of which kind the variable is
-        let vkind = self.variable_kind(&dest);
+        let vkind = self.variable_kind(dest);
 
         match vkind {
-            VariableKind::Local => {
-                ir::Value::Register(self.lookup_register(ir::Register::Local(dest)))
-            }
+            VariableKind::Local => ir::Value::Register(self.lookup_register(dest, vkind)),
             VariableKind::Stack => {
-                ir::Value::Register(self.lookup_register(ir::Register::Stack(dest)))
+                let slot = self.lookup_register(dest, vkind);
+
+                if self.fcx().by_reference.contains(&slot.ident()) {
+                    // %address = load {arg}
+                    let address = self.next_free_register();
+                    block.load(ir::Value::Register(slot), address);
+
+                    return ir::Value::Indirect(address);
+                }
+
+                ir::Value::Register(slot)
             }
-            VariableKind::Static => ir::Value::Static(dest),
+            VariableKind::Static => ir::Value::Static(**dest),
             VariableKind::Constant => panic!("attempt to assign to a constant"),
         }
     }
 
+    /// Translate an expression that denotes a memory location
+    ///
+    /// Returns the stack slot or static of a variable or the address of an
+    /// array element or a struct field (`[%elem]`). Array and struct literals
+    /// are stored in a temporary stack slot first.
+    fn trans_place(
+        &mut self,
+        expr: &ast::Node<ast::Expression>,
+        block: &mut ir::Block,
+    ) -> ir::Value {
+        match **expr {
+            ast::Expression::Variable { ref name } => self.assign_dest(name, block),
+            ast::Expression::Index {
+                ref array,
+                ref index,
+            } => self.trans_element(array, index, block),
+            ast::Expression::Field {
+                ref object,
+                ref field,
+            } => self.trans_field(object, **field, block),
+            ast::Expression::Group(ref expr) => self.trans_place(expr, block),
+            ast::Expression::Array { .. }
+            | ast::Expression::ArrayRepeat { .. }
+            | ast::Expression::Struct { .. } => {
+                let slots = self.type_of(expr).slots();
+                let tmp = self.temporary_slot(slots, block);
+                self.trans_init(expr, ir::Value::Register(tmp), block);
+
+                ir::Value::Register(tmp)
+            }
+            _ => panic!("not a place expression: {:?}", expr),
+        }
+    }
+
+    /// Get the address of an array or a struct stored at a place
+    fn address_of(place: ir::Value) -> ir::Value {
+        match place {
+            ir::Value::Register(ir::Register::Stack(..)) => place,
+            ir::Value::Indirect(reg) => ir::Value::Register(reg),
+            _ => panic!("{} is not stored in memory", place),
+        }
+    }
+
+    /// The type of a struct's field and its offset in slots
+    fn field_layout(
+        &mut self,
+        object: &ast::Node<ast::Expression>,
+        field: Ident,
+    ) -> (ast::Type, u32) {
+        match self.type_of(object) {
+            ast::Type::Struct(s) => s.field(field).unwrap(),
+            ty => panic!("{} has no fields", ty),
+        }
+    }
+
+    /// Translate the address of a struct's field
+    fn trans_field(
+        &mut self,
+        object: &ast::Node<ast::Expression>,
+        field: Ident,
+        block: &mut ir::Block,
+    ) -> ir::Value {
+        let (_, offset) = self.field_layout(object, field);
+        let base = self.trans_place(object, block);
+        let offset = ir::Value::Immediate(ir::Immediate(i64::from(offset)));
+
+        ir::Value::Indirect(self.trans_index(Translator::address_of(base), offset, block))
+    }
+
+    /// Translate the address of an array element
+    fn trans_element(
+        &mut self,
+        array: &ast::Node<ast::Expression>,
+        index: &ast::Node<ast::Expression>,
+        block: &mut ir::Block,
+    ) -> ir::Value {
+        let arr = match self.type_of(array) {
+            ast::Type::Array(arr) => arr,
+            ty => panic!("cannot index into {}", ty),
+        };
+
+        let base = self.trans_place(array, block);
+        let index = self.trans_expr_to_value(index, block);
+
+        if driver::session().options.get().bounds_checks {
+            match index {
+                // No need to check indices that are known to be in range
+                ir::Value::Immediate(ir::Immediate(i))
+                    if (0..i64::from(arr.length())).contains(&i) => {}
+                _ => self.trans_bounds_check(index, arr.length(), array.span, block),
+            }
+        }
+
+        let offset = self.trans_offset(index, arr.elem().slots(), block);
+
+        ir::Value::Indirect(self.trans_index(Translator::address_of(base), offset, block))
+    }
+
+    /// Make sure `0 <= index < len` or abort the program
+    ///
+    /// The trap's message contains the line of the indexing expression which
+    /// starts at `span`.
+    fn trans_bounds_check(
+        &mut self,
+        index: ir::Value,
+        len: u32,
+        span: ast::Span,
+        block: &mut ir::Block,
+    ) {
+        let label_ok = self.next_free_label(Ident::from_str("bounds-ok"));
+        let label_trap = self.next_free_label(Ident::from_str("bounds-trap"));
+
+        // %ok = (index >= 0) & (index < len)
+        let lower = self.next_free_register();
+        block.cmp(
+            ir::CmpOp::Ge,
+            index,
+            ir::Value::Immediate(ir::Immediate(0)),
+            lower,
+        );
+        let upper = self.next_free_register();
+        block.cmp(
+            ir::CmpOp::Lt,
+            index,
+            ir::Value::Immediate(ir::Immediate(i64::from(len))),
+            upper,
+        );
+        let ok = self.next_free_register();
+        block.binop(
+            ir::InfixOp::And,
+            ir::Value::Register(lower),
+            ir::Value::Register(upper),
+            ok,
+        );
+        block.branch(ir::Value::Register(ok), label_ok, label_trap);
+
+        // The trap block
+        let codemap = &driver::session().codemap;
+        let loc = codemap.resolve(BytePos(span.pos));
+        let msg = format!(
+            "index out of bounds in line {}:{}: `{}`",
+            loc.line,
+            loc.col,
+            codemap.line(loc.line).trim()
+        );
+
+        let mut trap = ir::Block {
+            label: label_trap,
+            inst: VecDeque::new(),
+            last: ir::ControlFlowInstruction::NotYetProcessed,
+            phis: Vec::new(),
+        };
+        trap.trap(Ident::from_str(&msg));
+        self.fcx().traps.push(trap);
+
+        self.commit_block_and_continue(block, label_ok);
+    }
+
+    /// Scale an element index to an offset in slots
+    fn trans_offset(&mut self, index: ir::Value, slots: u32, block: &mut ir::Block) -> ir::Value {
+        match index {
+            ir::Value::Immediate(ir::Immediate(i)) => {
+                ir::Value::Immediate(ir::Immediate(i.wrapping_mul(i64::from(slots))))
+            }
+            _ if slots == 1 => index,
+            _ => {
+                let offset = self.next_free_register();
+                block.binop(
+                    ir::InfixOp::Mul,
+                    index,
+                    ir::Value::Immediate(ir::Immediate(i64::from(slots))),
+                    offset,
+                );
+
+                ir::Value::Register(offset)
+            }
+        }
+    }
+
+    /// %elem = index {base} offset
+    fn trans_index(
+        &mut self,
+        base: ir::Value,
+        offset: ir::Value,
+        block: &mut ir::Block,
+    ) -> ir::Register {
+        let elem = self.next_free_register();
+        block.index(base, offset, elem);
+
+        elem
+    }
+
+    /// Store the value of an array or struct expression in the memory at
+    /// `base`
+    pub fn trans_init(
+        &mut self,
+        value: &ast::Node<ast::Expression>,
+        base: ir::Value,
+        block: &mut ir::Block,
+    ) {
+        match **value {
+            ast::Expression::Array { ref elems } => {
+                let elem_slots = self.type_of(&elems[0]).slots();
+
+                for (i, elem) in elems.iter().enumerate() {
+                    self.trans_init_at(elem, base, i as u32 * elem_slots, block);
+                }
+            }
+            ast::Expression::Struct {
+                ref name,
+                ref fields,
+            } => {
+                // Initialize the fields in the order they're written in
+                let ty = ast::StructType(**name);
+
+                for (field, value) in fields {
+                    let (_, offset) = ty.field(**field).unwrap();
+                    self.trans_init_at(value, base, offset, block);
+                }
+            }
+            ast::Expression::ArrayRepeat { ref elem, len } => {
+                let elem_ty = self.type_of(elem);
+
+                if elem_ty.is_aggregate() {
+                    // Evaluate the element once and copy it into every element
+                    let slots = elem_ty.slots();
+                    let src = Translator::address_of(self.trans_place(elem, block));
+
+                    self.trans_repeat(len, block, |this, i, block| {
+                        let offset = this.trans_offset(i, slots, block);
+                        let dst = this.trans_index(base, offset, block);
+                        this.trans_copy(src, ir::Value::Register(dst), slots, block);
+                    });
+                } else {
+                    let value = self.trans_expr_to_value(elem, block);
+
+                    self.trans_repeat(len, block, |this, i, block| {
+                        let elem = this.trans_index(base, i, block);
+                        block.store(value, ir::Value::Indirect(elem));
+                    });
+                }
+            }
+            ast::Expression::Group(ref expr) => self.trans_init(expr, base, block),
+            _ => {
+                // Another array or struct: copy it
+                let slots = self.type_of(value).slots();
+                let src = Translator::address_of(self.trans_place(value, block));
+                self.trans_copy(src, base, slots, block);
+            }
+        }
+    }
+
+    /// Store the value of an element or a field `offset` slots behind `base`
+    fn trans_init_at(
+        &mut self,
+        value: &ast::Node<ast::Expression>,
+        base: ir::Value,
+        offset: u32,
+        block: &mut ir::Block,
+    ) {
+        let offset = ir::Value::Immediate(ir::Immediate(i64::from(offset)));
+
+        if self.type_of(value).is_aggregate() {
+            let inner = self.trans_index(base, offset, block);
+            self.trans_init(value, ir::Value::Register(inner), block);
+        } else {
+            let value = self.trans_expr_to_value(value, block);
+            let elem = self.trans_index(base, offset, block);
+            block.store(value, ir::Value::Indirect(elem));
+        }
+    }
+
+    /// Copy `slots` slots from the memory at `src` to the memory at `dst`
+    fn trans_copy(&mut self, src: ir::Value, dst: ir::Value, slots: u32, block: &mut ir::Block) {
+        self.trans_repeat(slots, block, |this, i, block| {
+            let src_elem = this.trans_index(src, i, block);
+            let value = this.next_free_register();
+            block.load(ir::Value::Indirect(src_elem), value);
+
+            let dst_elem = this.trans_index(dst, i, block);
+            block.store(ir::Value::Register(value), ir::Value::Indirect(dst_elem));
+        });
+    }
+
+    /// Call `f` for all `i` in `0..count`
+    ///
+    /// Small counts are unrolled, otherwise we emit a loop with the counter
+    /// living in a temporary stack slot.
+    fn trans_repeat<F>(&mut self, count: u32, block: &mut ir::Block, mut f: F)
+    where
+        F: FnMut(&mut Translator, ir::Value, &mut ir::Block),
+    {
+        if count <= UNROLL_LIMIT {
+            for i in 0..count {
+                f(
+                    self,
+                    ir::Value::Immediate(ir::Immediate(i64::from(i))),
+                    block,
+                );
+            }
+
+            return;
+        }
+
+        let counter = self.temporary_slot(1, block);
+        let label_cond = self.next_free_label(Ident::from_str("repeat-cond"));
+        let label_body = self.next_free_label(Ident::from_str("repeat-body"));
+        let label_next = self.next_free_label(Ident::from_str("repeat-next"));
+
+        block.store_reg(ir::Value::Immediate(ir::Immediate(0)), counter);
+        block.jump(label_cond);
+
+        // %i = load {counter}
+        // %cond = cmp lt %i count
+        self.commit_block_and_continue(block, label_cond);
+        let i = self.next_free_register();
+        block.load(ir::Value::Register(counter), i);
+        let cond = self.next_free_register();
+        block.cmp(
+            ir::CmpOp::Lt,
+            ir::Value::Register(i),
+            ir::Value::Immediate(ir::Immediate(i64::from(count))),
+            cond,
+        );
+        block.branch(ir::Value::Register(cond), label_body, label_next);
+
+        // The body, then increment the counter
+        self.commit_block_and_continue(block, label_body);
+        f(self, ir::Value::Register(i), block);
+        let next = self.next_free_register();
+        block.binop(
+            ir::InfixOp::Add,
+            ir::Value::Register(i),
+            ir::Value::Immediate(ir::Immediate(1)),
+            next,
+        );
+        block.store_reg(ir::Value::Register(next), counter);
+        block.jump(label_cond);
+
+        self.commit_block_and_continue(block, label_next);
+    }
+
     /// Translate a literal
     fn trans_literal(&mut self, val: &ast::Value, block: &mut ir::Block, dest: Dest) {
-        let val = ir::Value::Immediate(ir::Immediate(val.as_u32()));
+        let val = ir::Value::Immediate(ir::Immediate(val.as_i64()));
         let dst = self.unwrap_dest(dest);
         block.store_reg(val, dst)
     }
 
     /// Translate the usage of a variable
-    fn trans_variable(&mut self, name: &Ident, block: &mut ir::Block, dest: Dest) {
+    fn trans_variable(&mut self, name: &ast::Node<Ident>, block: &mut ir::Block, dest: Dest) {
         let vkind = self.variable_kind(name);
 
         match vkind {
@@ -125,20 +566,20 @@ impl Translator {
 
             VariableKind::Stack => {
                 // %dest = load %local
-                block.load(
-                    ir::Value::Register(ir::Register::Stack(*name)),
-                    self.unwrap_dest(dest),
-                );
+                // (using the alternative name if the variable is shadowed)
+                let slot = self.lookup_register(name, vkind);
+                block.load(ir::Value::Register(slot), self.unwrap_dest(dest));
             }
 
             VariableKind::Static => {
                 // %dest = load %static
-                block.load(ir::Value::Static(*name), self.unwrap_dest(dest));
+                block.load(ir::Value::Static(**name), self.unwrap_dest(dest));
             }
 
             VariableKind::Constant => {
                 // %dest = {const}
-                panic!("should already be handled!")
+                let symbol = driver::session().symbol_table.lookup_symbol(name).unwrap();
+                self.trans_literal(&symbol.get_value().unwrap_literal(), block, dest);
             }
         }
     }
@@ -146,12 +587,23 @@ impl Translator {
     /// Translate an assignment
     fn trans_assign(
         &mut self,
-        lhs: &ast::Expression,
-        rhs: &ast::Expression,
+        lhs: &ast::Node<ast::Expression>,
+        rhs: &ast::Node<ast::Expression>,
         block: &mut ir::Block,
     ) {
-        let dst = self.assign_dest(lhs.unwrap_ident());
+        let ty = self.type_of(lhs);
+        if ty.is_aggregate() {
+            // Evaluate the right-hand side first as it might read from the
+            // array we assign to (`a = [a[1], a[0]]`)
+            let src = Translator::address_of(self.trans_place(rhs, block));
+            let dst = Translator::address_of(self.trans_place(lhs, block));
+            self.trans_copy(src, dst, ty.slots(), block);
+
+            return;
+        }
+
         let val = self.trans_expr_to_value(rhs, block);
+        let dst = self.trans_place(lhs, block);
 
         block.store(val, dst);
     }
@@ -160,14 +612,30 @@ impl Translator {
     fn trans_assign_op(
         &mut self,
         op: ast::BinOp,
-        lhs: &ast::Expression,
-        rhs: &ast::Expression,
+        lhs: &ast::Node<ast::Expression>,
+        rhs: &ast::Node<ast::Expression>,
         block: &mut ir::Block,
     ) {
-        let dst = self.assign_dest(lhs.unwrap_ident());
+        if let ast::Expression::Variable { ref name } = **lhs {
+            let dst = self.assign_dest(name, block);
+            let tmp = self.next_free_register();
+
+            self.trans_infix(op, lhs, rhs, block, Dest::Store(tmp));
+            block.store(ir::Value::Register(tmp), dst);
+
+            return;
+        }
+
+        // Only evaluate the place once (`a[f()] += 1`)
+        let dst = self.trans_place(lhs, block);
         let tmp = self.next_free_register();
 
-        self.trans_infix(op, lhs, rhs, block, Dest::Store(tmp));
+        let current = self.next_free_register();
+        block.load(dst, current);
+        let rhs_val = self.trans_expr_to_value(rhs, block);
+
+        let ty = self.type_of(lhs);
+        self.trans_binop(op, ty, ir::Value::Register(current), rhs_val, tmp, block);
         block.store(ir::Value::Register(tmp), dst);
     }
 
@@ -175,81 +643,169 @@ impl Translator {
     fn trans_call(
         &mut self,
         func: &Ident,
-        args: &[&ast::Expression],
+        args: &[&ast::Node<ast::Expression>],
         block: &mut ir::Block,
         dest: Dest,
     ) {
         let translated_args: Vec<_> = args
             .iter()
-            .map(|expr| self.trans_expr_to_value(expr, block))
+            .map(|expr| {
+                if self.type_of(expr).is_aggregate() {
+                    self.trans_by_reference(expr, block)
+                } else {
+                    self.trans_expr_to_value(expr, block)
+                }
+            })
             .collect();
 
         block.call(*func, translated_args, self.unwrap_dest(dest));
     }
 
+    /// Pass a struct to a function by reference
+    ///
+    /// The callee may modify its argument, so we pass the address of a copy.
+    fn trans_by_reference(
+        &mut self,
+        expr: &ast::Node<ast::Expression>,
+        block: &mut ir::Block,
+    ) -> ir::Value {
+        let slots = self.type_of(expr).slots();
+        let copy = self.temporary_slot(slots, block);
+        self.trans_init(expr, ir::Value::Register(copy), block);
+
+        // %arg = index {copy} 0
+        let address = self.trans_index(
+            ir::Value::Register(copy),
+            ir::Value::Immediate(ir::Immediate(0)),
+            block,
+        );
+
+        ir::Value::Register(address)
+    }
+
     /// Translate an infix expression
     fn trans_infix(
         &mut self,
         op: ast::BinOp,
-        lhs: &ast::Expression,
-        rhs: &ast::Expression,
+        lhs: &ast::Node<ast::Expression>,
+        rhs: &ast::Node<ast::Expression>,
         block: &mut ir::Block,
         dest: Dest,
     ) {
         // FIXME: Docs
         match op.get_type() {
             ast::BinOpType::Arithmetic | ast::BinOpType::Bitwise => {
+                let ty = self.type_of(lhs);
                 let lhs_val = self.trans_expr_to_value(lhs, block);
                 let rhs_val = self.trans_expr_to_value(rhs, block);
+                let dst = self.unwrap_dest(dest);
 
-                block.binop(
-                    ir::InfixOp::from_ast_op(op),
-                    lhs_val,
-                    rhs_val,
-                    self.unwrap_dest(dest),
-                )
+                self.trans_binop(op, ty, lhs_val, rhs_val, dst, block);
             }
             ast::BinOpType::Logic => {
                 // Short-circuiting logic. This involves branching to skip the
                 // right-hand side part if possible. FIXME: more explanation
-                let label_lhs = block.label;
                 let label_rhs = self.next_free_label(Ident::from_str("lazy-rhs"));
                 let label_next = self.next_free_label(Ident::from_str("lazy-next"));
 
                 // The left-hand side
                 let lhs_val = self.trans_expr_to_temporary(lhs, block);
+                // The lhs might have started new blocks (e.g. nested lazy ops)
+                let label_lhs = block.label;
 
-                // FIXME: Explanation
-                match op {
-                    ast::BinOp::And => block.branch(lhs_val, label_rhs, label_next),
-                    ast::BinOp::Or => block.branch(lhs_val, label_next, label_rhs),
+                // If we skip the right-hand side, the result is already known:
+                // `false` for `&&` and `true` for `||`. The backend fuses a
+                // comparison with the branch on it, so the lhs's value isn't
+                // kept in a register we could merge.
+                let skipped_val = match op {
+                    ast::BinOp::And => {
+                        block.branch(lhs_val, label_rhs, label_next);
+                        ir::Value::Immediate(ir::Immediate(0))
+                    }
+                    ast::BinOp::Or => {
+                        block.branch(lhs_val, label_next, label_rhs);
+                        ir::Value::Immediate(ir::Immediate(1))
+                    }
                     _ => panic!(),
-                }
+                };
 
                 // Evaluate the right-hand side
                 self.commit_block_and_continue(block, label_rhs);
                 let rhs_val = self.trans_expr_to_temporary(rhs, block);
+                let label_rhs = block.label;
                 block.jump(label_next);
 
                 // Select the value (lhs vs rhs) based on where we came from
                 // (by using the Phi function).
                 self.commit_block_and_continue(block, label_next);
                 block.phi(
-                    vec![(lhs_val, label_lhs), (rhs_val, label_rhs)],
+                    vec![(skipped_val, label_lhs), (rhs_val, label_rhs)],
                     self.unwrap_dest(dest),
                 );
             }
             ast::BinOpType::Comparison => {
+                let ty = self.type_of(lhs);
+                let mut cmp = ir::CmpOp::from_ast_op(op);
+                if ty == ast::Type::F64 {
+                    cmp = cmp.float();
+                } else if ty.is_integer() && !ty.is_signed() {
+                    cmp = cmp.unsigned();
+                }
+
                 let lhs_val = self.trans_expr_to_value(lhs, block);
                 let rhs_val = self.trans_expr_to_value(rhs, block);
 
-                block.cmp(
-                    ir::CmpOp::from_ast_op(op),
-                    lhs_val,
-                    rhs_val,
-                    self.unwrap_dest(dest),
-                )
+                block.cmp(cmp, lhs_val, rhs_val, self.unwrap_dest(dest))
+            }
+        }
+    }
+
+    /// Translate an arithmetic or bitwise operation on values of type `ty`
+    fn trans_binop(
+        &mut self,
+        op: ast::BinOp,
+        ty: ast::Type,
+        lhs: ir::Value,
+        rhs: ir::Value,
+        dst: ir::Register,
+        block: &mut ir::Block,
+    ) {
+        let mut op = ir::InfixOp::from_ast_op(op);
+        if ty == ast::Type::F64 {
+            op = op.float();
+        } else if ty.is_integer() && !ty.is_signed() {
+            op = op.unsigned();
+        }
+
+        match op {
+            // These operations can't leave the range of their operands' type
+            ir::InfixOp::Mod
+            | ir::InfixOp::Shr
+            | ir::InfixOp::UDiv
+            | ir::InfixOp::UMod
+            | ir::InfixOp::UShr
+            | ir::InfixOp::And
+            | ir::InfixOp::Or
+            | ir::InfixOp::Xor => block.binop(op, lhs, rhs, dst),
+            _ => self.trans_wrapping(ty, dst, block, |block, dst| block.binop(op, lhs, rhs, dst)),
+        }
+    }
+
+    /// Emit an operation whose result has to wrap around to the range of `ty`
+    ///
+    /// The machine always computes with full words, thus results of narrow
+    /// integer types are truncated and extended again.
+    fn trans_wrapping<F>(&mut self, ty: ast::Type, dst: ir::Register, block: &mut ir::Block, f: F)
+    where
+        F: FnOnce(&mut ir::Block, ir::Register),
+    {
+        match ty.narrow_bits() {
+            Some(bits) => {
+                let tmp = self.next_free_register();
+                f(block, tmp);
+                block.extend(ty.is_signed(), bits, ir::Value::Register(tmp), dst);
             }
+            None => f(block, dst),
         }
     }
 
@@ -257,12 +813,69 @@ impl Translator {
     fn trans_prefix(
         &mut self,
         op: ast::UnOp,
-        item: &ast::Expression,
+        item: &ast::Node<ast::Expression>,
         block: &mut ir::Block,
         dest: Dest,
     ) {
+        let ty = self.type_of(item);
         let tmp = self.trans_expr_to_value(item, block);
+        let dst = self.unwrap_dest(dest);
+
+        let op = match op {
+            ast::UnOp::Neg if ty == ast::Type::F64 => ir::PrefixOp::FNeg,
+            op => ir::PrefixOp::from_ast_op(op),
+        };
+
+        self.trans_wrapping(ty, dst, block, |block, dst| block.unop(op, tmp, dst));
+    }
+
+    /// Translate a cast
+    ///
+    /// As all values are kept extended to a full word, only conversions into a
+    /// narrower type and conversions from or to `f64` need to do anything.
+    /// `u64`s are converted to `f64` as if they were signed.
+    fn trans_cast(
+        &mut self,
+        expr: &ast::Node<ast::Expression>,
+        ty: ast::Type,
+        block: &mut ir::Block,
+        dest: Dest,
+    ) {
+        use front::ast::Type::*;
+
+        let from = self.type_of(expr);
+        if (from == F64) != (ty == F64) {
+            let value = self.trans_expr_to_value(expr, block);
+            let dst = self.unwrap_dest(dest);
+            let op = if ty == F64 {
+                ir::PrefixOp::IntToFloat
+            } else {
+                ir::PrefixOp::FloatToInt
+            };
+
+            self.trans_wrapping(ty, dst, block, |block, dst| block.unop(op, value, dst));
+            return;
+        }
 
-        block.unop(ir::PrefixOp::from_ast_op(op), tmp, self.unwrap_dest(dest))
+        let fits = match ty {
+            U8 => from == U8 || from == Bool,
+            I32 => from == I32 || from == U8 || from == Bool || from == Char,
+            _ => true,
+        };
+
+        match ty.narrow_bits() {
+            Some(bits) if !fits => {
+                let value = self.trans_expr_to_value(expr, block);
+                let dst = self.unwrap_dest(dest);
+                block.extend(ty.is_signed(), bits, value, dst);
+            }
+            // The bits don't change, so this is a no-op
+            _ => self.trans_expr(expr, block, dest),
+        }
     }
 }
+
+/// The name of a loop's label
+fn label_name(label: &Option<ast::Node<Ident>>) -> Option<Ident> {
+    label.as_ref().map(|label| **label)
+}
@@ -80,6 +80,9 @@ struct FunctionContext {
     /// The function's arguments
     stack_slots: HashSet<Ident>,
 
+    /// The arguments that hold the address of their value (structs)
+    by_reference: HashSet<Ident>,
+
     /// All registers used in this function
     registers: HashMap<Ident, Register>, // FIXME: Can we use a HashSet<Ident> instead here?
 
@@ -90,11 +93,33 @@ struct FunctionContext {
     /// if the return type is non-void
     return_slot: Option<Register>,
 
+    /// The label of the block that returns from the function
+    return_label: Option<ir::Label>,
+
     /// The next free register to use
     next_register: u32,
 
-    /// The current loop's exit
-    loop_exit: Option<ir::Label>,
+    /// The loops we're in, the innermost one last
+    loops: Vec<LoopLabels>,
+
+    /// The blocks that abort the program when a runtime check fails
+    traps: Vec<ir::Block>,
+}
+
+/// The blocks `continue` and `break` jump to
+#[derive(Debug)]
+struct LoopLabels {
+    /// The loop's label (`'outer`)
+    name: Option<Ident>,
+
+    /// The block that starts the next iteration
+    header: ir::Label,
+
+    /// The block following the loop
+    exit: ir::Label,
+
+    /// The values passed to `break` along with the blocks they come from
+    values: Vec<(ir::Value, ir::Label)>,
 }
 
 #[derive(Clone, Copy)]
@@ -124,6 +149,9 @@ pub struct Translator {
     /// As the translator might want to use the same label multiple times,
     /// we always append an index to it, which is stored here
     next_label: HashMap<Ident, u32>,
+    /// The symbols holding the string literals we've seen so far, by their
+    /// contents
+    strings: HashMap<Ident, Ident>,
 }
 
 impl Translator {
@@ -132,6 +160,7 @@ impl Translator {
             ir: ir::Program::new(),
             fcx: None,
             next_label: HashMap::new(),
+            strings: HashMap::new(),
         }
     }
 
@@ -145,6 +174,11 @@ impl Translator {
         self.fcx.as_mut().unwrap()
     }
 
+    /// The type of an expression as determined by the type checker
+    fn type_of(&self, expr: &ast::Node<ast::Expression>) -> ast::Type {
+        session().type_table.type_of(expr)
+    }
+
     /// Get the next free register
     fn next_free_register(&mut self) -> Register {
         let next_register = self.fcx().next_register;
@@ -165,6 +199,21 @@ impl Translator {
         ir::Label::from_str(&name)
     }
 
+    /// Get the symbol holding a string literal's contents (`str$1`)
+    ///
+    /// Literals with the same contents share a symbol.
+    fn string_symbol(&mut self, value: Ident) -> Ident {
+        if let Some(&name) = self.strings.get(&value) {
+            return name;
+        }
+
+        let name = Ident::from_str(&format!("str${}", self.strings.len() + 1));
+        self.strings.insert(value, name);
+        self.ir.emit(ir::Symbol::Str { name, value });
+
+        name
+    }
+
     /// Unwrap a destination
     ///
     /// When the destination contains a register, we return it. Otherwise we
@@ -192,6 +241,21 @@ impl Translator {
         }
     }
 
+    /// Get the label of the current function's return block
+    ///
+    /// The label is chosen on first use so functions without a return block
+    /// don't use up a label.
+    fn return_label(&mut self) -> ir::Label {
+        if let Some(label) = self.fcx().return_label {
+            return label;
+        }
+
+        let label = self.next_free_label(Ident::from_str("return"));
+        self.fcx().return_label = Some(label);
+
+        label
+    }
+
     fn with_first_block<F>(&mut self, current: &mut ir::Block, f: F)
     where
         F: Fn(&mut ir::Block) -> (),
@@ -255,6 +319,23 @@ impl Translator {
         register
     }
 
+    /// Allocate an unnamed stack slot for a temporary value (e.g. an array
+    /// literal we index into)
+    fn temporary_slot(&mut self, size: u32, block: &mut ir::Block) -> Register {
+        let mut id = Ident::from_str("tmp");
+        let mut i = 1;
+
+        while self.fcx().stack_slots.contains(&id) {
+            id = Ident::from_str(&format!("tmp{}", i));
+            i += 1;
+        }
+
+        self.fcx().stack_slots.insert(id);
+        self.with_first_block(block, |block| block.alloc(Register::Stack(id), size));
+
+        Register::Stack(id)
+    }
+
     /// Register a stack slot variable and return its register
     fn register_stack_slot(&mut self, id: Ident) -> Register {
         // Find a stack slot name
@@ -276,17 +357,22 @@ impl Translator {
     }
 
     /// Look up the alternative name we assigned in case of variable shadowing
-    fn lookup_register(&mut self, reg: Register) -> Register {
+    fn lookup_register(&mut self, name: &ast::Node<Ident>, vkind: VariableKind) -> Register {
         let var = session()
             .symbol_table
-            .resolve_variable(self.fcx().scope, &reg.ident())
-            .expect(&format!("variable {} not yet declared", reg));
-
-        match reg {
-            Register::Local(_) => var.reg.expect(&format!("No register assigned to {}", reg)),
-            Register::Stack(_) => var
+            .resolve_variable(self.fcx().scope, name)
+            .unwrap_or_else(|| panic!("variable {} not yet declared", name));
+
+        match vkind {
+            VariableKind::Local => var
+                .reg
+                .unwrap_or_else(|| panic!("No register assigned to {}", name)),
+            VariableKind::Stack => var
                 .slot
-                .expect(&format!("No stack slot assigned to {}", reg)),
+                .unwrap_or_else(|| panic!("No stack slot assigned to {}", name)),
+            VariableKind::Static | VariableKind::Constant => {
+                panic!("{} doesn't have a register", name)
+            }
         }
     }
 
@@ -319,11 +405,14 @@ impl Translator {
         self.fcx = Some(FunctionContext {
             body: Vec::new(),
             stack_slots: HashSet::new(),
+            by_reference: HashSet::new(),
             registers: HashMap::new(),
             return_slot: None,
+            return_label: None,
             scope: body.id,
             next_register: 0,
-            loop_exit: None,
+            loops: Vec::new(),
+            traps: Vec::new(),
         });
 
         // Prepare ast block
@@ -338,7 +427,11 @@ impl Translator {
         // TODO: Maybe use calling convention here?
         // (only use stack for non-register arguments)
         for binding in bindings.iter().rev() {
-            self.register_stack_slot(*binding.name);
+            let slot = self.register_stack_slot(*binding.name);
+
+            if binding.ty.is_aggregate() {
+                self.fcx().by_reference.insert(slot.ident());
+            }
         }
 
         // Translate ast block
@@ -365,16 +458,18 @@ impl Translator {
         };
 
         // Finalize the function
-        if is_void && !block.finalized() {
-            block.ret(None);
+        if is_void {
+            if !block.finalized() {
+                block.ret(None);
+            }
         } else {
             // Build the return block
             // FIXME: If there is a single store to the return slot,
             //        return it directly and skip the alloca/store
             let ret_slot = self.fcx().return_slot.unwrap();
-            self.with_first_block(&mut block, |block| block.alloc(ret_slot));
+            self.with_first_block(&mut block, |block| block.alloc(ret_slot, 1));
 
-            let return_label = self.next_free_label(Ident::from_str("return"));
+            let return_label = self.return_label();
             if !block.finalized() {
                 block.jump(return_label);
             };
@@ -389,8 +484,11 @@ impl Translator {
 
         self.commit_block(block);
 
-        // Emit the symbol
-        let fcx = self.fcx.take().unwrap();
+        // Emit the symbol with the trap blocks placed at the end, out of the
+        // way of the regular control flow
+        let mut fcx = self.fcx.take().unwrap();
+        fcx.body.append(&mut fcx.traps);
+
         self.ir.emit(ir::Symbol::Function {
             name,
             body: fcx.body,
@@ -402,10 +500,18 @@ impl Translator {
     fn trans_block(&mut self, b: &ast::Node<ast::Block>, block: &mut ir::Block, dest: Dest) {
         with_reset!(self.fcx().scope, b.id, {
             for stmt in &b.stmts {
+                if block.finalized() {
+                    // The rest of the block is unreachable as it follows a
+                    // `return`, `break` or `continue`
+                    break;
+                }
+
                 self.trans_stmt(stmt, block);
             }
 
-            self.trans_expr(&b.expr, block, dest);
+            if !block.finalized() {
+                self.trans_expr(&b.expr, block, dest);
+            }
         });
     }
 
@@ -416,9 +522,24 @@ impl Translator {
                 ref binding,
                 ref value,
             } => {
+                // The binding's type may have been inferred by typeck, so
+                // look it up instead of using the annotation
+                let ty = session()
+                    .symbol_table
+                    .resolve_variable(self.fcx().scope, &binding.name)
+                    .expect("declared variable not in symbol table")
+                    .ty;
+
                 // Allocate memory on stack for the binding
                 let dst = self.register_stack_slot(*binding.name);
-                self.with_first_block(block, |block| block.alloc(dst));
+                let size = ty.slots();
+                self.with_first_block(block, |block| block.alloc(dst, size));
+
+                if ty.is_aggregate() {
+                    // Initialize the array/struct element by element
+                    self.trans_init(value, ir::Value::Register(dst), block);
+                    return;
+                }
 
                 // Store the expression in the new slot
                 let value = self.trans_expr_to_value(value, block);
@@ -436,29 +557,66 @@ impl Translator {
 impl<'v> Visitor<'v> for Translator {
     fn visit_symbol(&mut self, s: &'v ast::Node<ast::Symbol>) {
         match **s {
-            ast::Symbol::Static {
-                ref binding,
-                ref value,
-            } => {
+            ast::Symbol::Static { ref binding, .. } => {
+                // The initializer has been evaluated at compile time
+                let symbol = session().symbol_table.lookup_symbol(&binding.name).unwrap();
                 self.ir.emit(ir::Symbol::Global {
                     name: *binding.name,
-                    value: ir::Immediate(value.unwrap_literal().as_u32()),
+                    value: ir::Immediate(symbol.get_value().unwrap_literal().as_i64()),
                 });
             }
             ast::Symbol::Constant { .. } => {
                 // Will be inlined on usage
             }
+            ast::Symbol::Struct { .. } => {
+                // Only determines the stack layout
+            }
+            ast::Symbol::Impl {
+                ref name,
+                ref methods,
+                ..
+            } => {
+                // Methods are functions with mangled names
+                let ty = ast::StructType(**name);
+
+                for method in methods {
+                    if let ast::Symbol::Function {
+                        ref name,
+                        ref bindings,
+                        ref ret_ty,
+                        ref body,
+                        ..
+                    } = **method
+                    {
+                        let bindings: Vec<_> = bindings.iter().map(|b| **b).collect();
+
+                        self.trans_fn(ty.mangle_method(**name), &bindings, *ret_ty, body);
+                    }
+                }
+            }
             ast::Symbol::Function {
                 ref name,
                 ref bindings,
                 ref ret_ty,
                 ref body,
+                ..
             } => {
                 // Get the Binding out of the Node<Binding>
                 let bindings: Vec<_> = bindings.iter().map(|b| **b).collect();
 
                 self.trans_fn(**name, &bindings, *ret_ty, body);
             }
+            ast::Symbol::Extern {
+                ref name,
+                ref bindings,
+                ..
+            } => {
+                // Defined elsewhere, so there's no body to translate
+                self.ir.emit(ir::Symbol::Extern {
+                    name: **name,
+                    args: bindings.iter().map(|b| *b.name).collect(),
+                });
+            }
         }
     }
 }
@@ -36,7 +36,7 @@ where
     V: Visitor<'v>,
 {
     match *symbol {
-        Symbol::Global { ref name, .. } => {
+        Symbol::Global { ref name, .. } | Symbol::Str { ref name, .. } => {
             visitor.visit_ident(*name);
             // visitor.visit_value(value); <-- is an Immediate
         }
@@ -53,6 +53,12 @@ where
                 visitor.visit_block(block);
             }
         }
+        Symbol::Extern { ref name, ref args } => {
+            visitor.visit_ident(*name);
+            for arg in args {
+                visitor.visit_ident(*arg);
+            }
+        }
     }
 }
 
@@ -94,10 +100,22 @@ where
             visitor.visit_value(*item);
             visitor.visit_register(*dst);
         }
-        Instruction::Alloca { ref dst } => {
+        Instruction::Alloca { ref dst, .. } => {
             visitor.visit_register(*dst);
         }
-        Instruction::Load { ref src, ref dst } => {
+        Instruction::Index {
+            ref base,
+            ref index,
+            ref dst,
+        } => {
+            visitor.visit_value(*base);
+            visitor.visit_value(*index);
+            visitor.visit_register(*dst);
+        }
+        Instruction::Load { ref src, ref dst }
+        | Instruction::Extend {
+            ref src, ref dst, ..
+        } => {
             visitor.visit_value(*src);
             visitor.visit_register(*dst);
         }
@@ -146,6 +164,17 @@ where
         ControlFlowInstruction::Jump { ref dest } => {
             visitor.visit_label(*dest);
         }
-        ControlFlowInstruction::NotYetProcessed => {}
+        ControlFlowInstruction::Switch {
+            ref value,
+            ref cases,
+            ref default,
+        } => {
+            visitor.visit_value(*value);
+            for &(_, _, dest) in cases {
+                visitor.visit_label(dest);
+            }
+            visitor.visit_label(*default);
+        }
+        ControlFlowInstruction::Trap { .. } | ControlFlowInstruction::NotYetProcessed => {}
     }
 }
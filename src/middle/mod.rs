@@ -2,4 +2,8 @@
 // TODO: Insert intrinsics implementations
 // TODO: Replace intrinsics usage with appropriate calls
 
+mod const_folding;
+pub mod interp;
 pub mod ir;
+
+pub use self::const_folding::fold_constants;
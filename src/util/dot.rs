@@ -0,0 +1,62 @@
+//! Graphviz helpers
+//!
+//! Used to visualize control flow graphs (`--emit cfg-dot`). Every node is
+//! a box containing a list of left-aligned lines.
+
+use std::fmt;
+
+/// A directed graph in Graphviz' dot language
+pub struct Digraph {
+    name: String,
+    nodes: Vec<(String, Vec<String>)>,
+    edges: Vec<(String, String, Option<String>)>,
+}
+
+impl Digraph {
+    pub fn new(name: &str) -> Digraph {
+        Digraph {
+            name: name.to_owned(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Add a node that displays the given lines
+    pub fn node(&mut self, id: &str, lines: Vec<String>) {
+        self.nodes.push((id.to_owned(), lines));
+    }
+
+    /// Add an edge with an optional label
+    pub fn edge(&mut self, from: &str, to: &str, label: Option<&str>) {
+        self.edges
+            .push((from.to_owned(), to.to_owned(), label.map(str::to_owned)));
+    }
+}
+
+/// Escape a string so it can be used in a quoted dot string
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl fmt::Display for Digraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "digraph \"{}\" {{", escape(&self.name))?;
+        writeln!(f, "    node [shape=box, fontname=\"monospace\"];")?;
+
+        for (id, lines) in &self.nodes {
+            // `\l` ends a left-aligned line
+            let label: String = lines.iter().map(|l| escape(l) + "\\l").collect();
+            writeln!(f, "    \"{}\" [label=\"{}\"];", escape(id), label)?;
+        }
+
+        for (from, to, label) in &self.edges {
+            write!(f, "    \"{}\" -> \"{}\"", escape(from), escape(to))?;
+            if let Some(ref label) = *label {
+                write!(f, " [label=\"{}\"]", escape(label))?;
+            }
+            writeln!(f, ";")?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
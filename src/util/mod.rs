@@ -5,6 +5,7 @@ use std::hash::Hash;
 
 pub use self::io::{read_file, write_file};
 
+pub mod dot;
 mod io;
 
 /// A helper that tries to insert a key/value into a hashmap and returns
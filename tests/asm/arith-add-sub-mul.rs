@@ -0,0 +1,10 @@
+fn arith(a: int, b: int) -> int {
+    let sum: int = a + b;
+    let diff: int = sum - 3;
+    let prod: int = 4 * diff;
+    prod * a
+}
+
+fn main() {
+    arith(1, 2);
+}
@@ -0,0 +1,48 @@
+.intel_syntax noprefix
+.text
+.globl arith
+arith:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+entry.block1:
+    mov %2, {%a}
+    mov %3, {%b}
+    mov %1, %2
+    add %1, %3
+    mov {%sum}, %1
+    mov %5, {%sum}
+    mov %4, %5
+    sub %4, 3
+    mov {%diff}, %4
+    mov %7, {%diff}
+    imul %6, %7, 4
+    mov {%prod}, %6
+    mov %8, {%prod}
+    mov %9, {%a}
+    mov %0, %8
+    imul %0, %9
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %10, {%ret_slot}
+    mov rax, %10
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 1
+    push 2
+    call arith
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,9 @@
+fn bitwise(a: int, b: int) -> int {
+    let x: int = a & b;
+    let y: int = x | 8;
+    y ^ a
+}
+
+fn main() {
+    bitwise(5, 3);
+}
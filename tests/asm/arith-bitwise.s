@@ -0,0 +1,45 @@
+.intel_syntax noprefix
+.text
+.globl bitwise
+bitwise:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 24
+entry.block1:
+    mov %2, {%a}
+    mov %3, {%b}
+    mov %1, %2
+    and %1, %3
+    mov {%x}, %1
+    mov %5, {%x}
+    mov %4, %5
+    or %4, 8
+    mov {%y}, %4
+    mov %6, {%y}
+    mov %7, {%a}
+    mov %0, %6
+    xor %0, %7
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %8, {%ret_slot}
+    mov rax, %8
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 5
+    push 3
+    call bitwise
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,9 @@
+fn div_mod(a: int, b: int) -> int {
+    let q: int = a / b;
+    let r: int = a % 7;
+    q + r
+}
+
+fn main() {
+    div_mod(7, 2);
+}
@@ -0,0 +1,50 @@
+.intel_syntax noprefix
+.text
+.globl div_mod
+div_mod:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 24
+entry.block1:
+    mov %2, {%a}
+    mov %3, {%b}
+    mov rax, %2
+    cqo
+    idiv %3
+    mov %1, rax
+    mov {%q}, %1
+    mov %5, {%a}
+    mov %tmp, 7
+    mov rax, %5
+    cqo
+    idiv %tmp
+    mov %4, rdx
+    mov {%r}, %4
+    mov %6, {%q}
+    mov %7, {%r}
+    mov %0, %6
+    add %0, %7
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %8, {%ret_slot}
+    mov rax, %8
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 7
+    push 2
+    call div_mod
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,11 @@
+fn scale(n: int, x: f64, y: f64) -> f64 {
+    x * y + n as f64
+}
+
+fn main() {
+    let a: f64 = scale(2, 1.5, 0.5);
+    let b: f64 = -a / 3.0;
+    let lt: bool = a < b;
+    let eq: bool = a == b;
+    let c: int = b as int;
+}
@@ -0,0 +1,84 @@
+.intel_syntax noprefix
+.text
+.globl scale
+scale:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+    movsd {%x}, xmm0
+    movsd {%y}, xmm1
+entry.block1:
+    mov %2, {%x}
+    mov %3, {%y}
+    movq xmm0, %2
+    movq xmm1, %3
+    mulsd xmm0, xmm1
+    movq %1, xmm0
+    mov %5, {%n}
+    cvtsi2sd xmm0, %5
+    movq %4, xmm0
+    movq xmm0, %1
+    movq xmm1, %4
+    addsd xmm0, xmm1
+    movq %0, xmm0
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %6, {%ret_slot}
+    mov rax, %6
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 40
+entry.block2:
+    push 2
+    mov %tmp, 4609434218613702656
+    movq xmm0, %tmp
+    mov %tmp, 4602678819172646912
+    movq xmm1, %tmp
+    call scale
+    mov %0, rax
+    mov {%a}, %0
+    mov %3, {%a}
+    mov %2, %3
+    btc %2, 63
+    movq xmm0, %2
+    mov %tmp, 4613937818241073152
+    movq xmm1, %tmp
+    divsd xmm0, xmm1
+    movq %1, xmm0
+    mov {%b}, %1
+    mov %5, {%a}
+    mov %6, {%b}
+    movq xmm0, %5
+    movq xmm1, %6
+    ucomisd xmm1, xmm0
+    seta cl
+    and cl, 1
+    movzx %4, cl
+    mov {%lt}, %4
+    mov %8, {%a}
+    mov %9, {%b}
+    movq xmm0, %8
+    movq xmm1, %9
+    ucomisd xmm0, xmm1
+    sete cl
+    movzx %7, cl
+    setnp cl
+    movzx %parity, cl
+    and %7, %parity
+    mov {%eq}, %7
+    mov %11, {%b}
+    movq xmm0, %11
+    cvttsd2si %10, xmm0
+    mov {%c}, %10
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,7 @@
+fn power(a: int, b: int) -> int {
+    a ** b
+}
+
+fn main() {
+    power(3, 4);
+}
@@ -0,0 +1,51 @@
+.intel_syntax noprefix
+.text
+.globl power
+power:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block1:
+    mov %1, {%a}
+    mov %2, {%b}
+    mov %pow_result1, 1
+    mov %pow_base1, %1
+    mov %pow_exp1, %2
+    shl %pow_exp1, 32
+    shr %pow_exp1, 32
+pow_loop1:
+    test %pow_exp1, %pow_exp1
+    jz pow_done1
+    test %pow_exp1, 1
+    jz pow_square1
+    imul %pow_result1, %pow_base1
+pow_square1:
+    imul %pow_base1, %pow_base1
+    shr %pow_exp1, 1
+    jmp pow_loop1
+pow_done1:
+    mov %0, %pow_result1
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %3, {%ret_slot}
+    mov rax, %3
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 3
+    push 4
+    call power
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
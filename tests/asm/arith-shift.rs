@@ -0,0 +1,8 @@
+fn shift(a: int, b: int) -> int {
+    let l: int = a << b;
+    l >> 2
+}
+
+fn main() {
+    shift(1, 3);
+}
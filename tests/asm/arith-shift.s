@@ -0,0 +1,41 @@
+.intel_syntax noprefix
+.text
+.globl shift
+shift:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+entry.block1:
+    mov %2, {%a}
+    mov %3, {%b}
+    mov %1, %2
+    mov rcx, %3
+    sal %1, cl
+    mov {%l}, %1
+    mov %4, {%l}
+    mov %0, %4
+    sar %0, 2
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %5, {%ret_slot}
+    mov rax, %5
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 1
+    push 3
+    call shift
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
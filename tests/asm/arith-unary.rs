@@ -0,0 +1,8 @@
+fn unary(a: int) -> int {
+    let n: int = -a;
+    !n
+}
+
+fn main() {
+    unary(5);
+}
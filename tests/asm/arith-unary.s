@@ -0,0 +1,38 @@
+.intel_syntax noprefix
+.text
+.globl unary
+unary:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+entry.block1:
+    mov %2, {%a}
+    mov %1, %2
+    neg %1
+    mov {%n}, %1
+    mov %3, {%n}
+    mov %0, %3
+    not %0
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %4, {%ret_slot}
+    mov rax, %4
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 5
+    call unary
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,5 @@
+fn main() {
+    let a: int = 4294967296;
+    let b: int = a + 8589934592;
+    let c: int = b * -2;
+}
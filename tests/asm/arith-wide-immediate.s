@@ -0,0 +1,26 @@
+.intel_syntax noprefix
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 24
+entry.block1:
+    mov %imm, 4294967296
+    mov {%a}, %imm
+    mov %1, {%a}
+    mov %0, %1
+    mov %imm, 8589934592
+    add %0, %imm
+    mov {%b}, %0
+    mov %3, {%b}
+    mov %4, 2
+    neg %4
+    mov %2, %3
+    imul %2, %4
+    mov {%c}, %2
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,8 @@
+fn add(a: int, b: int) -> int {
+    a + b
+}
+
+fn main() {
+    let a: int = add(1, 2);
+    add(a, 3);
+}
@@ -0,0 +1,42 @@
+.intel_syntax noprefix
+.text
+.globl add
+add:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block1:
+    mov %1, {%a}
+    mov %2, {%b}
+    mov %0, %1
+    add %0, %2
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %3, {%ret_slot}
+    mov rax, %3
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block2:
+    push 1
+    push 2
+    call add
+    mov %0, rax
+    mov {%a}, %0
+    mov %1, {%a}
+    push %1
+    push 3
+    call add
+    mov %2, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,11 @@
+fn max(a: int, b: int) -> int {
+    if a < b {
+        b
+    } else {
+        a
+    }
+}
+
+fn main() {
+    max(1, 2);
+}
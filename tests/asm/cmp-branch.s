@@ -0,0 +1,47 @@
+.intel_syntax noprefix
+.text
+.globl max
+max:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block1:
+    mov %2, {%a}
+    mov %3, {%b}
+    cmp %2, %3
+    jl conseq1
+    jmp altern1
+
+conseq1:
+    mov %0, {%b}
+    jmp next1
+
+altern1:
+    mov %0, {%a}
+    jmp next1
+
+next1:
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %4, {%ret_slot}
+    mov rax, %4
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 1
+    push 2
+    call max
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
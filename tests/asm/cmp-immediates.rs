@@ -0,0 +1,7 @@
+const NEG: int = -7;
+const BIG: u64 = 4294967296;
+
+fn main() {
+    let lt: bool = NEG < 0;
+    let ugt: bool = BIG > 300;
+}
@@ -0,0 +1,25 @@
+.intel_syntax noprefix
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+entry.block1:
+    mov %0, -7
+    cmp %0, 0
+    setl cl
+    and cl, 1
+    movzx %0, cl
+    mov {%lt}, %0
+    mov %1, 4294967296
+    cmp %1, 300
+    seta cl
+    and cl, 1
+    movzx %1, cl
+    mov {%ugt}, %1
+    mov rax, 0
+    leave
+    ret
+
+
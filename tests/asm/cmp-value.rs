@@ -0,0 +1,9 @@
+fn main() {
+    let a: int = 1;
+    let lt: bool = a < 2;
+    let le: bool = a <= 2;
+    let ge: bool = a >= 2;
+    let gt: bool = a > 2;
+    let eq: bool = a == 2;
+    let ne: bool = a != 2;
+}
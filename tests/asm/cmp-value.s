@@ -0,0 +1,50 @@
+.intel_syntax noprefix
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 56
+entry.block1:
+    mov {%a}, 1
+    mov %1, {%a}
+    cmp %1, 2
+    setl cl
+    and cl, 1
+    movzx %0, cl
+    mov {%lt}, %0
+    mov %3, {%a}
+    cmp %3, 2
+    setle cl
+    and cl, 1
+    movzx %2, cl
+    mov {%le}, %2
+    mov %5, {%a}
+    cmp %5, 2
+    setge cl
+    and cl, 1
+    movzx %4, cl
+    mov {%ge}, %4
+    mov %7, {%a}
+    cmp %7, 2
+    setg cl
+    and cl, 1
+    movzx %6, cl
+    mov {%gt}, %6
+    mov %9, {%a}
+    cmp %9, 2
+    sete cl
+    and cl, 1
+    movzx %8, cl
+    mov {%eq}, %8
+    mov %11, {%a}
+    cmp %11, 2
+    setne cl
+    and cl, 1
+    movzx %10, cl
+    mov {%ne}, %10
+    mov rax, 0
+    leave
+    ret
+
+
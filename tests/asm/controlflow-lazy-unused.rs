@@ -0,0 +1,7 @@
+fn check(a: bool, b: bool) {
+    a && b;
+}
+
+fn main() {
+    check(true, false);
+}
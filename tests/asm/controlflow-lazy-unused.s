@@ -0,0 +1,38 @@
+.intel_syntax noprefix
+.text
+.globl check
+check:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block1:
+    mov %0, {%a}
+    test %0, 1
+    mov %2, 0
+    jne lazy.rhs1
+    jmp lazy.next1
+
+lazy.rhs1:
+    mov %1, {%b}
+    mov %2, %1
+    jmp lazy.next1
+
+lazy.next1:
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 1
+    push 0
+    call check
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
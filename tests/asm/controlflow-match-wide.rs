@@ -0,0 +1,19 @@
+fn classify(a: int, b: int) -> int {
+    let x = match a {
+        4294967296 => 1,
+        -4294967296..=-4294967290 => 2,
+        _ => 0,
+    };
+    let y = match b {
+        1 => 1,
+        2 => 2,
+        3 => 3,
+        4 => 4,
+        _ => 0,
+    };
+    x + y
+}
+
+fn main() {
+    classify(1, 2);
+}
@@ -0,0 +1,100 @@
+.intel_syntax noprefix
+
+.data
+.align 8
+jump_table1:
+.quad arm4
+.quad arm5
+.quad arm6
+.quad arm7
+
+.text
+.globl classify
+classify:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 24
+entry.block1:
+    mov %2, {%a}
+    mov %switch_imm1, 4294967296
+    cmp %2, %switch_imm1
+    je arm1
+    mov %switch1, %2
+    mov %switch_imm1, -4294967296
+    sub %switch1, %switch_imm1
+    cmp %switch1, 6
+    jbe arm2
+    jmp arm3
+
+arm1:
+    mov %1, 1
+    jmp next1
+
+arm2:
+    mov %1, 2
+    jmp next1
+
+arm3:
+    mov %1, 0
+    jmp next1
+
+next1:
+    mov {%x}, %1
+    mov %4, {%b}
+    mov %switch2, %4
+    sub %switch2, 1
+    cmp %switch2, 3
+    ja arm8
+    lea %table2, [rip + jump_table1]
+    jmp qword ptr [%table2 + %switch2 * 8]
+
+arm4:
+    mov %3, 1
+    jmp next2
+
+arm5:
+    mov %3, 2
+    jmp next2
+
+arm6:
+    mov %3, 3
+    jmp next2
+
+arm7:
+    mov %3, 4
+    jmp next2
+
+arm8:
+    mov %3, 0
+    jmp next2
+
+next2:
+    mov {%y}, %3
+    mov %5, {%x}
+    mov %6, {%y}
+    mov %0, %5
+    add %0, %6
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %7, {%ret_slot}
+    mov rax, %7
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 1
+    push 2
+    call classify
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,23 @@
+fn dense(n: int) -> int {
+    match n {
+        1 => 10,
+        2 | 3 => 20,
+        4 => 30,
+        6 => 40,
+        _ => 0,
+    }
+}
+
+fn sparse(n: int) -> int {
+    match n {
+        1 => 10,
+        100..=199 => 20,
+        1000 => 30,
+        _ => 0,
+    }
+}
+
+fn main() {
+    dense(2);
+    sparse(150);
+}
@@ -0,0 +1,117 @@
+.intel_syntax noprefix
+
+.data
+.align 8
+jump_table1:
+.quad arm1
+.quad arm2
+.quad arm2
+.quad arm3
+.quad arm5
+.quad arm4
+
+.text
+.globl dense
+dense:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block1:
+    mov %1, {%n}
+    mov %switch1, %1
+    sub %switch1, 1
+    cmp %switch1, 5
+    ja arm5
+    lea %table1, [rip + jump_table1]
+    jmp qword ptr [%table1 + %switch1 * 8]
+
+arm1:
+    mov %0, 10
+    jmp next1
+
+arm2:
+    mov %0, 20
+    jmp next1
+
+arm3:
+    mov %0, 30
+    jmp next1
+
+arm4:
+    mov %0, 40
+    jmp next1
+
+arm5:
+    mov %0, 0
+    jmp next1
+
+next1:
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %2, {%ret_slot}
+    mov rax, %2
+    leave
+    ret
+
+.globl sparse
+sparse:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block2:
+    mov %1, {%n}
+    cmp %1, 1
+    je arm6
+    mov %switch2, %1
+    sub %switch2, 100
+    cmp %switch2, 99
+    jbe arm7
+    cmp %1, 1000
+    je arm8
+    jmp arm9
+
+arm6:
+    mov %0, 10
+    jmp next2
+
+arm7:
+    mov %0, 20
+    jmp next2
+
+arm8:
+    mov %0, 30
+    jmp next2
+
+arm9:
+    mov %0, 0
+    jmp next2
+
+next2:
+    mov {%ret_slot}, %0
+    jmp return2
+
+return2:
+    mov %2, {%ret_slot}
+    mov rax, %2
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block3:
+    push 2
+    call dense
+    mov %0, rax
+    push 150
+    call sparse
+    mov %1, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,11 @@
+fn count(n: int) -> int {
+    let mut i: int = 0;
+    while i < n {
+        i += 1;
+    }
+    return i;
+}
+
+fn main() {
+    count(3);
+}
@@ -0,0 +1,50 @@
+.intel_syntax noprefix
+.text
+.globl count
+count:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+entry.block1:
+    mov {%i}, 0
+    jmp while_cond1
+
+while_cond1:
+    mov %2, {%i}
+    mov %3, {%n}
+    cmp %2, %3
+    jl while_body1
+    jmp while_exit1
+
+while_body1:
+    mov %5, {%i}
+    mov %4, %5
+    add %4, 1
+    mov {%i}, %4
+    jmp while_cond1
+
+while_exit1:
+    mov %6, {%i}
+    mov {%ret_slot}, %6
+    jmp return1
+
+return1:
+    mov %7, {%ret_slot}
+    mov rax, %7
+    leave
+    ret
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 3
+    call count
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
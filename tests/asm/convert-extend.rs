@@ -0,0 +1,9 @@
+fn main() {
+    let a: u8 = 200;
+    let b: i32 = 7;
+    let c: u8 = a + 100;
+    let d: i32 = b * 3;
+    let e: u64 = 10;
+    let f: u64 = e / 3;
+    let lt: bool = e < 4;
+}
@@ -0,0 +1,38 @@
+.intel_syntax noprefix
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 56
+entry.block1:
+    mov {%a}, 200
+    mov {%b}, 7
+    mov %1, {%a}
+    mov %2, %1
+    add %2, 100
+    movzx %0, %2:byte
+    mov {%c}, %0
+    mov %4, {%b}
+    imul %5, %4, 3
+    movsxd %3, %5:dword
+    mov {%d}, %3
+    mov {%e}, 10
+    mov %7, {%e}
+    mov %tmp, 3
+    xor rdx, rdx
+    mov rax, %7
+    div %tmp
+    mov %6, rax
+    mov {%f}, %6
+    mov %9, {%e}
+    cmp %9, 4
+    setb cl
+    and cl, 1
+    movzx %8, cl
+    mov {%lt}, %8
+    mov rax, 0
+    leave
+    ret
+
+
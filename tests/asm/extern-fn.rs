@@ -0,0 +1,5 @@
+extern fn putchar(c: int) -> int;
+
+fn main() {
+    putchar(65);
+}
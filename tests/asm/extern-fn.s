@@ -0,0 +1,22 @@
+.intel_syntax noprefix
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block1:
+    mov rax, rsp
+    and rsp, -16
+    push rax
+    push rax
+    mov rdi, 65
+    mov rax, 0
+    call putchar
+    pop rsp
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
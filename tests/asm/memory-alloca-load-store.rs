@@ -0,0 +1,7 @@
+static COUNTER: int = 5;
+
+fn main() {
+    let a: int = 1;
+    let b: int = a;
+    let c: int = COUNTER;
+}
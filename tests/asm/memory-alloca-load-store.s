@@ -0,0 +1,24 @@
+.intel_syntax noprefix
+
+.data
+.align 8
+COUNTER:
+.quad 5
+
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 24
+entry.block1:
+    mov {%a}, 1
+    mov %0, {%a}
+    mov {%b}, %0
+    mov %1, {%COUNTER}
+    mov {%c}, %1
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,9 @@
+fn store(i: int) {
+    let mut a: [char; 2] = ['a', 'b'];
+    a[i] = 'ä';
+    a[i] = '"';
+}
+
+fn main() {
+    store(1);
+}
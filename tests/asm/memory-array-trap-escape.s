@@ -0,0 +1,99 @@
+.intel_syntax noprefix
+
+.data
+.align 8
+trap_msg1:
+.ascii "runtime error: index out of bounds in line 3:5: `a[i] = '\303\244';`\n"
+trap_msg2:
+.ascii "runtime error: index out of bounds in line 4:5: `a[i] = '\"';`\n"
+
+.text
+.globl store
+store:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+entry.block1:
+    lea %0, {%a}
+    mov %tmp, 0
+    lea %0, [%0 + %tmp * 8]
+    mov qword ptr [%0], 97
+    lea %1, {%a}
+    mov %tmp, 1
+    lea %1, [%1 + %tmp * 8]
+    mov qword ptr [%1], 98
+    mov %2, {%i}
+    cmp %2, 0
+    setge cl
+    and cl, 1
+    movzx %3, cl
+    cmp %2, 2
+    setl cl
+    and cl, 1
+    movzx %4, cl
+    mov %5, %3
+    and %5, %4
+    test %5, 1
+    jne bounds.ok1
+    jmp bounds.trap1
+
+bounds.ok1:
+    lea %6, {%a}
+    lea %6, [%6 + %2 * 8]
+    mov qword ptr [%6], 228
+    mov %7, {%i}
+    cmp %7, 0
+    setge cl
+    and cl, 1
+    movzx %8, cl
+    cmp %7, 2
+    setl cl
+    and cl, 1
+    movzx %9, cl
+    mov %10, %8
+    and %10, %9
+    test %10, 1
+    jne bounds.ok2
+    jmp bounds.trap2
+
+bounds.ok2:
+    lea %11, {%a}
+    lea %11, [%11 + %7 * 8]
+    mov qword ptr [%11], 34
+    leave
+    ret
+
+bounds.trap1:
+    mov rax, 1
+    mov rdi, 2
+    lea rsi, [rip + trap_msg1]
+    mov rdx, 63
+    syscall
+    mov rax, 60
+    mov rdi, 1
+    syscall
+
+bounds.trap2:
+    mov rax, 1
+    mov rdi, 2
+    lea rsi, [rip + trap_msg2]
+    mov rdx, 62
+    syscall
+    mov rax, 60
+    mov rdi, 1
+    syscall
+
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block2:
+    push 1
+    call store
+    mov %0, rax
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,5 @@
+fn main() {
+    let mut a: [int; 2] = [1, 2];
+    let i: int = 1;
+    a[i] = a[0];
+}
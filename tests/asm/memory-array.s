@@ -0,0 +1,61 @@
+.intel_syntax noprefix
+
+.data
+.align 8
+trap_msg1:
+.ascii "runtime error: index out of bounds in line 4:5: `a[i] = a[0];`\n"
+
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 24
+entry.block1:
+    lea %0, {%a}
+    mov %tmp, 0
+    lea %0, [%0 + %tmp * 8]
+    mov qword ptr [%0], 1
+    lea %1, {%a}
+    mov %tmp, 1
+    lea %1, [%1 + %tmp * 8]
+    mov qword ptr [%1], 2
+    mov {%i}, 1
+    lea %3, {%a}
+    mov %tmp, 0
+    lea %3, [%3 + %tmp * 8]
+    mov %2, qword ptr [%3]
+    mov %4, {%i}
+    cmp %4, 0
+    setge cl
+    and cl, 1
+    movzx %5, cl
+    cmp %4, 2
+    setl cl
+    and cl, 1
+    movzx %6, cl
+    mov %7, %5
+    and %7, %6
+    test %7, 1
+    jne bounds.ok1
+    jmp bounds.trap1
+
+bounds.ok1:
+    lea %8, {%a}
+    lea %8, [%8 + %4 * 8]
+    mov qword ptr [%8], %2
+    mov rax, 0
+    leave
+    ret
+
+bounds.trap1:
+    mov rax, 1
+    mov rdi, 2
+    lea rsi, [rip + trap_msg1]
+    mov rdx, 63
+    syscall
+    mov rax, 60
+    mov rdi, 1
+    syscall
+
+
@@ -0,0 +1,7 @@
+const STEP: int = 2 * 3;
+static COUNTER: int = STEP * 7 - 2;
+
+fn main() {
+    COUNTER += STEP;
+    COUNTER = STEP;
+}
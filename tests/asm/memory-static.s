@@ -0,0 +1,24 @@
+.intel_syntax noprefix
+
+.data
+.align 8
+COUNTER:
+.quad 40
+
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block1:
+    mov %1, {%COUNTER}
+    mov %0, %1
+    add %0, 6
+    mov {%COUNTER}, %0
+    mov {%COUNTER}, 6
+    mov rax, 0
+    leave
+    ret
+
+
@@ -0,0 +1,5 @@
+fn main() {
+    print_str("Hello\n");
+    print_str("caf\u{e9}\t\"\\\"");
+    print_str("Hello\n");
+}
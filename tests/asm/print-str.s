@@ -0,0 +1,34 @@
+.intel_syntax noprefix
+.section .rodata
+str$1:
+.asciz "Hello\n"
+str$2:
+.asciz "caf\303\251\t\"\\\""
+
+.text
+.globl main
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0
+entry.block1:
+    mov rax, 1
+    mov rdi, 1
+    lea rsi, [rip + str$1]
+    mov rdx, 6
+    syscall
+    mov rax, 1
+    mov rdi, 1
+    lea rsi, [rip + str$2]
+    mov rdx, 9
+    syscall
+    mov rax, 1
+    mov rdi, 1
+    lea rsi, [rip + str$1]
+    mov rdx, 6
+    syscall
+    mov rax, 0
+    leave
+    ret
+
+
@@ -2,39 +2,62 @@
 .text
 .globl func
 func:
-    enter 0, 0
-    mov %ret_slot, rsp
-    sub rsi, 4
-    mov qword ptr [%ret_slot], 10
-    jmp return
-return:
-    mov %0, qword ptr [%ret_slot]
-    mov rax, %0
+    push rbp
+    mov rbp, rsp
+    sub rsp, 8
+entry.block1:
+    mov %1, {%i}
+    mov %0, 10
+    add %0, %1
+    mov {%ret_slot}, %0
+    jmp return1
+
+return1:
+    mov %2, {%ret_slot}
+    mov rax, %2
     leave
     ret
+
 .globl main
 main:
-    enter 0, 0
-    mov %i, rsp
-    sub rsi, 4
-    mov qword ptr [%i], 2
-    mov %2, qword ptr [%i]
-    cmp %2, 1
-    jne lazy-rhs1
-    jmp lazy-next1
-    test %1, 1
-    je lazy-next1
-    jmp lazy-rhs1
-lazy-rhs1:
-    mov qword ptr [%3], 0
-    jmp lazy-next1
-lazy-next1:
-    phi %0 = (entry-block, %1), (lazy-rhs1, %3)
-    test %0, 1
-    je conseq1
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+entry.block2:
+    mov {%i}, 2
+    mov %1, {%i}
+    push %1
+    call func
+    mov %0, rax
+    mov {%j}, %0
+    mov %4, {%j}
+    mov %5, {%i}
+    cmp %4, %5
+    mov %2, 1
+    je lazy.next1
+    jmp lazy.rhs1
+
+lazy.rhs1:
+    mov %7, {%j}
+    cmp %7, 7
+    setg cl
+    and cl, 1
+    movzx %6, cl
+    mov %2, %6
+    jmp lazy.next1
+
+lazy.next1:
+    test %2, 1
+    jne conseq1
     jmp next1
+
 conseq1:
+    mov {%i}, 9
     jmp next1
+
 next1:
+    mov rax, 0
     leave
-    ret
\ No newline at end of file
+    ret
+
+
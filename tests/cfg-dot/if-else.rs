@@ -0,0 +1,8 @@
+fn main() {
+    let mut a: int = 1;
+    if a == 1 {
+        a = 2;
+    } else {
+        a = 3;
+    }
+}
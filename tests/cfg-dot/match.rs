@@ -0,0 +1,15 @@
+fn main() {
+    let mut a: int = 1;
+    match a {
+        0 => {}
+        1 | 2 => {
+            a = 2;
+        }
+        3..=9 => {
+            a = 3;
+        }
+        _ => {
+            a = 4;
+        }
+    }
+}
@@ -0,0 +1,7 @@
+const A: int = B + 1;
+const B: int = C * 2;
+const C: int = A;  //! ERROR(3:16): cycle detected when evaluating constant `A`
+
+const D: int = D;  //! ERROR(5:16): cycle detected when evaluating constant `D`
+
+fn main() {}
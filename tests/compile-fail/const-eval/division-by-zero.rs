@@ -0,0 +1,5 @@
+const ZERO: int = 2 - 2;
+const A: int = 1 / ZERO;  //! ERROR(2:16): attempt to divide by zero in a constant expression
+static B: int = 7 % (ZERO * 3);  //! ERROR(3:17): attempt to divide by zero in a constant expression
+
+fn main() {}
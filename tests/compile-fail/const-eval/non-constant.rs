@@ -0,0 +1,9 @@
+static S: int = 1;
+const A: int = S + 1;  //! ERROR(2:16): cannot use static `S` in a constant expression
+const B: int = five();  //! ERROR(3:16): this expression can't be evaluated at compile time
+
+fn five() -> int {
+    5
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+const A: int = 9223372036854775807 + 1;  //! ERROR(1:16): arithmetic overflow in a constant expression of type `int`
+const B: u8 = 200;
+const C: u8 = B * 2;  //! ERROR(3:15): arithmetic overflow in a constant expression of type `u8`
+const D: u64 = 0 - 1;  //! ERROR(4:16): arithmetic overflow in a constant expression of type `u64`
+static E: i32 = 1 << 32;  //! ERROR(5:17): arithmetic overflow in a constant expression of type `i32`
+
+fn main() {}
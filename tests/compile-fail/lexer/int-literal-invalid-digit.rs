@@ -0,0 +1,3 @@
+fn main() {
+    let mask: int = 0b0110_2001;  //! ERROR(2:21): invalid digit for a base 2 literal: `0b0110_2001`
+}
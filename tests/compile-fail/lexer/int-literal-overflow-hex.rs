@@ -0,0 +1,3 @@
+fn main() {
+    let a: u64 = 0x1_0000_0000_0000_0000;  //! ERROR(2:18): integer literal is too large: `0x1_0000_0000_0000_0000`
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let a: int = 18446744073709551616;  //! ERROR(2:18): integer literal is too large: `18446744073709551616`
+}
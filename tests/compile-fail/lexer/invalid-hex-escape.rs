@@ -0,0 +1,3 @@
+fn main() {
+    print_str("\xFF");  //! ERROR(2:20): out of range hex escape: `\xFF`
+}
@@ -0,0 +1,3 @@
+fn main() {}
+
+/* An unterminated /* nested */ block comment  //! ERROR(3:1): unterminated block comment
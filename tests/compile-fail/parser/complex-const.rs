@@ -1,3 +0,0 @@
-const CONST: int = 3 + 2;  //! ERROR(1:22): expected `;`, found `+`
-
-fn main() {}
@@ -1 +1 @@
-const NAME: int = +;  //! ERROR(1:19): unexpected token: `+`, expected a literal
\ No newline at end of file
+const NAME: int = +;  //! ERROR(1:19): unexpected token: `+`, expected a prefix expression
\ No newline at end of file
@@ -1 +1 @@
-fn a() -> i {}  //! ERROR(1:13): unexpected token: `{`, expected a type
\ No newline at end of file
+fn a() -> 1 {}  //! ERROR(1:11): unexpected token: `1`, expected a type
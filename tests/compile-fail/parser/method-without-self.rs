@@ -0,0 +1,9 @@
+struct Point {
+    x: int,
+}
+
+impl Point {
+    fn get(p: Point) {}  //! ERROR(6:12): unexpected token: `p`, expected `self`
+}
+
+fn main() {}
@@ -0,0 +1,3 @@
+fn main() {
+    [1, 2][0] = 3;  //! ERROR(2:5): left-hand side of assignment is not a variable
+}
@@ -0,0 +1,5 @@
+const LIMIT: int = 10;  //! ERROR(1:7): cannot assign to constant `LIMIT` in line 4:5
+
+fn main() {
+    LIMIT = 20;
+}
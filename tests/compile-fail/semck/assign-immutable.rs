@@ -0,0 +1,17 @@
+struct Point {
+    x: int,
+}
+
+fn foo(n: int, p: Point) {  //! ERROR(5:8): `n` is assigned to in line 6:5, but isn't declared as `mut`
+    n = 2;                  //! ERROR(5:16): `p` is assigned to in line 7:5, but isn't declared as `mut`
+    p.x += 1;
+}
+
+fn main() {
+    let a = [1, 2];  //! ERROR(11:9): `a` is assigned to in line 12:5, but isn't declared as `mut`
+    a[0] = 3;
+
+    for i in 0..3 {  //! ERROR(14:9): `i` is assigned to in line 15:9, but isn't declared as `mut`
+        i += 1;
+    }
+}
@@ -0,0 +1,5 @@
+fn main() {
+    if true {
+        continue;  //! ERROR(3:9): `continue` outside of loop
+    }
+}
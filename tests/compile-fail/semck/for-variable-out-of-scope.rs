@@ -0,0 +1,5 @@
+fn main() {
+    for i in 0..10 {
+    }
+    i;  //! ERROR(4:5): variable `i` not declared
+}
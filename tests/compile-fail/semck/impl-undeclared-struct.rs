@@ -0,0 +1,5 @@
+impl Point {  //! ERROR(1:6): no such struct: `Point`
+    fn get(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+extern fn putchar(c: int) -> int;
+
+fn putchar(c: int) -> int {  //! ERROR(3:1): cannot redeclare `putchar`
+    c
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+struct Point {
+    x: int,
+}
+
+impl Point {
+    fn get(self) -> int { self.x }
+    fn get(self) -> int { self.x }  //! ERROR(7:5): duplicate method `get` for `Point`
+}
+
+fn main() {}
@@ -1,3 +1,3 @@
-static FOO: int = a; //! ERROR(1:19): unexpected token: `a`, expected a literal
+static FOO: int = a; //! ERROR(1:19): variable `a` not declared
 
 fn main() {}
\ No newline at end of file
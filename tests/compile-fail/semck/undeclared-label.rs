@@ -0,0 +1,8 @@
+fn main() {
+    'a: loop {
+        break 'b;  //! ERROR(3:15): use of undeclared label `'b`
+    }
+    while true {
+        continue 'a;  //! ERROR(6:18): use of undeclared label `'a`
+    }
+}
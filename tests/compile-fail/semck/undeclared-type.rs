@@ -0,0 +1,3 @@
+fn main() {
+    let p: Point = 1;  //! ERROR(2:9): no such type: `Point`
+}
@@ -0,0 +1,3 @@
+fn foo(a: [int; 2]) {}  //! ERROR(1:8): arrays cannot be passed to functions
+
+fn main() {}
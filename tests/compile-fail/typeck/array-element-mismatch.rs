@@ -0,0 +1,3 @@
+fn main() {
+    let a: [int; 2] = [1, true];  //! ERROR(2:27): type mismatch: expected int, got bool
+}
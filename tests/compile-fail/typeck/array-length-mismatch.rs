@@ -0,0 +1,3 @@
+fn main() {
+    let a: [int; 3] = [1, 2];  //! ERROR(2:23): type mismatch: expected [int; 3], got [int; 2]
+}
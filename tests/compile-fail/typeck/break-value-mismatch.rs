@@ -0,0 +1,20 @@
+fn main() {
+    let a: int = loop {
+        break true;  //! ERROR(3:15): type mismatch: expected int, got bool
+    };
+    let b: bool = loop {
+        if a == 1 {
+            break;  //! ERROR(7:13): type mismatch: expected bool, got ()
+        }
+        break false;
+    };
+    loop {
+        if b {
+            break 1;
+        }
+        break;  //! ERROR(15:9): type mismatch: expected int, got ()
+    }
+    while b {
+        break 2;  //! ERROR(18:15): `break` with value from a `while` loop
+    }
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let a: int = 3;
+    let b: int = if a == 1 {
+        1
+    } else if a == 2 {  //! ERROR(5:22): missing else clause
+        2
+    };
+}
@@ -0,0 +1,6 @@
+extern fn putchar(c: int) -> int;
+
+fn main() {
+    putchar(true);  //! ERROR(4:13): type mismatch: expected int, got bool
+    putchar();  //! ERROR(5:5): mismatching argument count: expected 1, got 0
+}
@@ -0,0 +1,7 @@
+struct Point {
+    x: int,
+}
+
+extern fn draw(p: Point);  //! ERROR(5:16): structs cannot be passed to extern functions
+
+fn main() {}
@@ -0,0 +1,6 @@
+fn main() {
+    let a: f64 = 1;  //! ERROR(2:18): type mismatch: expected f64, got int
+    let b: f64 = 1.5 % 2.0;  //! ERROR(3:18): binary operation `%` cannot be applied to f64
+    let c: bool = 1.5 as bool;  //! ERROR(4:19): cannot cast f64 as bool
+    let d: f64 = 2.0 + 1;  //! ERROR(5:24): type mismatch: expected f64, got int
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let a: int = 1;
+    a[0];  //! ERROR(3:5): cannot index into a value of type int
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let a: int = 9223372036854775808;  //! ERROR(2:18): literal out of range for `int`
+}
@@ -1,5 +1,5 @@
 fn main() {
-    let a: bool = false;
+    let mut a: bool = false;
 
     a += 2;  //! ERROR(4:5): type mismatch: expected int, got bool
 }
\ No newline at end of file
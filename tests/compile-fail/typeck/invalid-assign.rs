@@ -1,5 +1,5 @@
 fn main() {
-    let a: int = 0;
+    let mut a: int = 0;
 
     a = 'a';  //! ERROR(4:9): type mismatch: expected int, got char
 }
\ No newline at end of file
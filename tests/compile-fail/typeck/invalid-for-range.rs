@@ -0,0 +1,4 @@
+fn main() {
+    for i in 0..true {  //! ERROR(2:17): type mismatch: expected int, got bool
+    }
+}
@@ -0,0 +1,5 @@
+fn nothing() {}
+
+fn main() {
+    let a = nothing();  //! ERROR(4:9): cannot infer the type of `a` from an initializer of type ()
+}
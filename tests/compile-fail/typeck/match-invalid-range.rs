@@ -0,0 +1,7 @@
+fn main() {
+    let n: int = 3;
+    match n {
+        5..=1 => {}  //! ERROR(4:9): lower range bound must be less than or equal to upper
+        _ => {}
+    }
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let n: u8 = 7;
+    let a: int = match n {  //! ERROR(3:24): non-exhaustive patterns: `10` not covered
+        0..=9 => 1,
+        11..=255 => 2,
+    };
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let c: char = 'c';
+    match c {
+        'a' | 'b' => {}
+        1 => {}  //! ERROR(5:9): type mismatch: expected char, got int
+        _ => {}
+    }
+}
@@ -0,0 +1,8 @@
+struct Point {
+    x: int,
+}
+
+fn main() {
+    let p: Point = Point { x: 1 };
+    p.length();  //! ERROR(7:7): no method named `length` found for type Point
+}
@@ -0,0 +1,3 @@
+fn main() {
+    print_str('c');  //! ERROR(2:15): type mismatch: expected str, got char
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let a: u8 = 1;
+    let b: int = 2;
+    let c: u8 = a + b;  //! ERROR(4:21): type mismatch: expected u8, got int
+    let d: i32 = 3000000000;  //! ERROR(5:18): literal out of range for `i32`
+    let e: u8 = -1;  //! ERROR(6:18): cannot apply unary operator `-` to type u8
+    let f: bool = 1 as bool;  //! ERROR(7:19): cannot cast int as bool
+}
@@ -0,0 +1,3 @@
+static A: int = -9223372036854775809;  //! ERROR(1:18): literal out of range for `int`
+
+fn main() {}
@@ -0,0 +1,3 @@
+fn main() {
+    let s = "text";  //! ERROR(2:13): string literals can only be passed to `print_str`
+}
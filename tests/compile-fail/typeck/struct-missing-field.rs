@@ -0,0 +1,8 @@
+struct Point {
+    x: int,
+    y: int,
+}
+
+fn main() {
+    let p: Point = Point { x: 1 };  //! ERROR(7:20): missing field `y` in initializer of `Point`
+}
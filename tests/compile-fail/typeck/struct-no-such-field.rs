@@ -0,0 +1,8 @@
+struct Point {
+    x: int,
+}
+
+fn main() {
+    let p: Point = Point { x: 1 };
+    let a: int = p.z;  //! ERROR(7:20): no field `z` on type Point
+}
@@ -0,0 +1,7 @@
+struct List {
+    //! ERROR(1:8): recursive struct `List` has infinite size
+    value: int,
+    next: List,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+struct Point {
+    x: int,
+}
+
+fn origin() -> Point {  //! ERROR(5:4): structs cannot be returned from functions
+    Point { x: 0 }
+}
+
+fn main() {}
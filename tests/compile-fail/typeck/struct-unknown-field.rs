@@ -0,0 +1,7 @@
+struct Point {
+    x: int,
+}
+
+fn main() {
+    let p: Point = Point { x: 1, y: 2 };  //! ERROR(6:34): struct `Point` has no field named `y`
+}
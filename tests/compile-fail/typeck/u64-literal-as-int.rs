@@ -0,0 +1,4 @@
+fn main() {
+    let a: u64 = 18446744073709551615;
+    let b = 18446744073709551615;  //! ERROR(3:13): literal out of range for `int`
+}
@@ -0,0 +1,190 @@
+//! Differential testing: IR interpreter vs. native execution
+//!
+//! Every program in `tests/run-pass` is executed twice: once by the IR
+//! interpreter (`--target run`) and once as a native binary built from the
+//! generated assembly. Both runs have to produce the same output and the same
+//! exit code. If they don't, the program was miscompiled somewhere between the
+//! IR and the final assembly (instruction selection rules, register
+//! allocation, ...).
+//!
+//! The native comparison needs a backend that produces assemblable code and a
+//! C compiler to link it, so it's ignored by default:
+//!
+//!     cargo test --test differential -- --ignored
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+
+const COMPILER: &str = env!("CARGO_BIN_EXE_rustiny");
+
+/// The compiler flags for each optimization level we compare at
+///
+/// Constant folding (`-O`) must not change what a program computes.
+const OPT_LEVELS: &[&[&str]] = &[&[], &["-O"]];
+
+/// The observable behaviour of a program run
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// The program exited normally
+    Exited { code: i32, stdout: String },
+    /// The program was aborted by a runtime error (e.g. a failed bounds
+    /// check) or a signal (native binary)
+    Crashed { stdout: String },
+}
+
+/// Collect all run-pass programs that aren't marked with `//! SKIP`
+fn run_pass_programs() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("run-pass");
+    let mut programs = Vec::new();
+
+    for category in fs::read_dir(&root).unwrap() {
+        for test in fs::read_dir(category.unwrap().path()).unwrap() {
+            let path = test.unwrap().path();
+            let source = fs::read_to_string(&path).unwrap();
+
+            if !source.lines().any(|l| l.trim() == "//! SKIP") {
+                programs.push(path);
+            }
+        }
+    }
+
+    programs.sort();
+    programs
+}
+
+fn rustiny(args: &[&str], flags: &[&str], program: &Path) -> Output {
+    Command::new(COMPILER)
+        .args(args)
+        .args(flags)
+        .arg(program)
+        .env("COLORED_OUTPUT", "off")
+        .output()
+        .unwrap()
+}
+
+/// Run a program in the IR interpreter
+fn interpret(program: &Path, flags: &[&str]) -> Result<Outcome, String> {
+    let output = rustiny(&["--target", "run"], flags, program);
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    if stderr.contains("runtime error") {
+        Ok(Outcome::Crashed { stdout })
+    } else if output.status.code() == Some(101) || stderr.starts_with("Error") {
+        Err(format!("interpreting failed:\n{}", stderr))
+    } else {
+        Ok(Outcome::Exited {
+            code: output.status.code().unwrap(),
+            stdout,
+        })
+    }
+}
+
+/// A temporary directory that's removed again when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> TempDir {
+        // Use a directory per process so concurrent test runs don't overwrite
+        // each other's binaries
+        let dir = env::temp_dir().join(format!("rustiny-differential-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        TempDir(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+/// Compile a program to a native binary and run it
+fn execute(program: &Path, flags: &[&str], dir: &TempDir) -> Result<Outcome, String> {
+    let name = program.file_stem().unwrap().to_str().unwrap();
+    let asm_file = dir.0.join(name).with_extension("s");
+    let bin_file = dir.0.join(name);
+
+    let output = rustiny(
+        &["--target", "asm", "-o", asm_file.to_str().unwrap()],
+        flags,
+        program,
+    );
+    if !output.status.success() {
+        return Err(format!(
+            "compiling failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let output = Command::new("cc")
+        .arg("-o")
+        .arg(&bin_file)
+        .arg(&asm_file)
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        return Err(format!(
+            "assembling failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let output = Command::new(&bin_file).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    Ok(match output.status.code() {
+        Some(_) if stderr.starts_with("runtime error") => Outcome::Crashed { stdout },
+        Some(code) => Outcome::Exited { code, stdout },
+        None => Outcome::Crashed { stdout },
+    })
+}
+
+/// Run a check on all programs at all optimization levels and report all
+/// failures at once
+fn check_all<F>(check: F)
+where
+    F: Fn(&Path, &[&str]) -> Result<(), String>,
+{
+    let mut failures = Vec::new();
+
+    for program in run_pass_programs() {
+        for flags in OPT_LEVELS {
+            if let Err(msg) = check(&program, flags) {
+                failures.push(format!("{} {:?}: {}", program.display(), flags, msg));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("{} failures:\n\n{}", failures.len(), failures.join("\n\n"));
+    }
+}
+
+#[test]
+fn interpreter_runs_all_programs() {
+    check_all(|program, flags| interpret(program, flags).map(|_| ()));
+}
+
+#[test]
+#[ignore = "needs an assemblable backend and a C compiler"]
+fn native_matches_interpreter() {
+    let dir = TempDir::new();
+
+    check_all(|program, flags| {
+        let expected = interpret(program, flags)?;
+        let actual = execute(program, flags, &dir)?;
+
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(format!("interpreter: {:?}, native: {:?}", expected, actual))
+        }
+    });
+}
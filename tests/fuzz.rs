@@ -0,0 +1,768 @@
+//! Fuzzing the whole pipeline with random programs
+//!
+//! The generator builds random but well-typed programs from `front::ast`
+//! nodes and prints them with `front::ast::pretty`. Each program is then
+//! compiled to assembly, which runs it through all phases of the compiler
+//! (lexing, parsing, semantic checks, type checking, IR generation,
+//! instruction selection and register allocation). As all generated programs
+//! are valid, the compiler has to accept every one of them without panicking.
+//!
+//! The generated programs always terminate: functions only call functions
+//! defined before them and every `while` loop is driven by a counter the
+//! loop's body can't modify.
+//!
+//! A failing program can be reproduced by setting `RUSTINY_FUZZ_SEED` to the
+//! reported seed.
+
+extern crate rustiny;
+
+use rustiny::driver::interner::Ident;
+use rustiny::front::ast::pretty::PrettyPrinter;
+use rustiny::front::ast::*;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command};
+
+const COMPILER: &str = env!("CARGO_BIN_EXE_rustiny");
+
+/// The number of programs to generate and compile
+const ITERATIONS: u64 = 100;
+
+/// The maximum nesting depth of generated expressions
+const MAX_DEPTH: u32 = 4;
+
+/// A xorshift64* pseudo random number generator
+///
+/// Good enough for generating programs and doesn't need an external crate.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        // The state must never be zero
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A random number in `0..n`
+    fn below(&mut self, n: u32) -> u32 {
+        (self.next() % u64::from(n)) as u32
+    }
+
+    /// Returns true with a probability of `1/n`
+    fn one_in(&mut self, n: u32) -> bool {
+        self.below(n) == 0
+    }
+
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u32) as usize]
+    }
+}
+
+/// A variable that's visible at the current position
+#[derive(Clone, Copy)]
+struct Var {
+    name: Ident,
+    ty: Type,
+    /// Whether the variable may be assigned to (false for constants and
+    /// loop counters)
+    assignable: bool,
+}
+
+/// The signature of an already generated function
+struct Function {
+    name: Ident,
+    args: Vec<Type>,
+    ret_ty: Type,
+}
+
+struct Generator {
+    rng: Rng,
+    globals: Vec<Var>,
+    functions: Vec<Function>,
+    /// The variables declared in each block we're currently in, innermost last
+    scopes: Vec<Vec<Var>>,
+    /// The current function's return type
+    ret_ty: Type,
+    /// Whether the current function contains an explicit `return`
+    explicit_return: bool,
+    loop_depth: u32,
+    next_name: u32,
+}
+
+fn node<T>(t: T) -> Node<T> {
+    Node::dummy(t)
+}
+
+fn boxed<T>(t: T) -> Box<Node<T>> {
+    Box::new(Node::dummy(t))
+}
+
+/// Wrap an expression in parentheses unless it's atomic
+///
+/// The generator does this for all operands, so the printed program parses
+/// into the same tree regardless of operator precedence.
+fn group(expr: Expression) -> Expression {
+    match expr {
+        Expression::Literal { .. }
+        | Expression::Variable { .. }
+        | Expression::Call { .. }
+        | Expression::Group(..) => expr,
+        _ => Expression::Group(boxed(expr)),
+    }
+}
+
+impl Generator {
+    fn new(seed: u64) -> Generator {
+        Generator {
+            rng: Rng::new(seed),
+            globals: Vec::new(),
+            functions: Vec::new(),
+            scopes: Vec::new(),
+            ret_ty: Type::Unit,
+            explicit_return: false,
+            loop_depth: 0,
+            next_name: 0,
+        }
+    }
+
+    fn fresh_name(&mut self, prefix: &str) -> Ident {
+        self.next_name += 1;
+        Ident::from_str(&format!("{}{}", prefix, self.next_name))
+    }
+
+    fn random_type(&mut self) -> Type {
+        *self
+            .rng
+            .choose(&[Type::Int, Type::Int, Type::Bool, Type::Char])
+    }
+
+    fn binding(&self, name: Ident, ty: Type, mutable: bool) -> Node<Binding> {
+        node(Binding {
+            ty,
+            name: node(name),
+            mutable,
+        })
+    }
+
+    // --- Symbols ----------------------------------------------------------------
+
+    fn program(&mut self) -> Program {
+        let mut program = Vec::new();
+
+        for _ in 0..self.rng.below(4) {
+            program.push(self.global());
+        }
+
+        for _ in 0..self.rng.below(5) {
+            let name = self.fresh_name("f");
+            let args = (0..self.rng.below(4)).map(|_| self.random_type()).collect();
+            let ret_ty = *self
+                .rng
+                .choose(&[Type::Unit, Type::Int, Type::Bool, Type::Char]);
+            program.push(self.function(name, args, ret_ty));
+        }
+
+        program.push(self.function(Ident::from_str("main"), vec![], Type::Unit));
+
+        program
+    }
+
+    fn global(&mut self) -> Node<Symbol> {
+        let ty = self.random_type();
+        let constant = self.rng.one_in(2);
+        let name = self.fresh_name(if constant { "C" } else { "S" });
+        let binding = Box::new(self.binding(name, ty, false));
+        let value = boxed(self.literal(ty));
+        let docs = self.docs();
+
+        self.globals.push(Var {
+            name,
+            ty,
+            assignable: !constant,
+        });
+
+        node(if constant {
+            Symbol::Constant {
+                binding,
+                value,
+                docs,
+            }
+        } else {
+            Symbol::Static {
+                binding,
+                value,
+                docs,
+            }
+        })
+    }
+
+    fn function(&mut self, name: Ident, args: Vec<Type>, ret_ty: Type) -> Node<Symbol> {
+        let mut bindings = Vec::new();
+        let mut scope = Vec::new();
+
+        for &ty in &args {
+            let arg = self.fresh_name("a");
+            bindings.push(self.binding(arg, ty, true));
+            scope.push(Var {
+                name: arg,
+                ty,
+                assignable: true,
+            });
+        }
+
+        self.ret_ty = ret_ty;
+        self.explicit_return = false;
+        self.scopes = vec![scope];
+
+        let mut body = self.block(ret_ty, MAX_DEPTH);
+
+        if self.explicit_return && ret_ty != Type::Unit {
+            // A function with an explicit return has to end in one as well
+            let val = body.expr.clone();
+            body.stmts.push(node(Statement::Expression {
+                val: boxed(Expression::Return { val }),
+            }));
+            body.expr = boxed(Expression::Unit);
+        }
+
+        // Make the function available to the following ones. Registering it
+        // only now prevents recursion, so all programs terminate.
+        self.functions.push(Function { name, args, ret_ty });
+
+        node(Symbol::Function {
+            name: node(name),
+            bindings,
+            ret_ty,
+            body: Box::new(node(body)),
+            docs: self.docs(),
+        })
+    }
+
+    /// Generate the doc comments of a symbol
+    fn docs(&mut self) -> Vec<Doc> {
+        let count = self.rng.below(3);
+
+        (0..count)
+            .map(|_| Doc {
+                kind: *self.rng.choose(&[DocKind::Outer, DocKind::Inner]),
+                text: Ident::from_str(*self.rng.choose(&[
+                    "",
+                    " Does something",
+                    "! Not an inner doc comment",
+                    " `code` /* not a block comment */",
+                ])),
+            })
+            .collect()
+    }
+
+    // --- Blocks and statements --------------------------------------------------
+
+    /// Generate a block evaluating to `ty`
+    ///
+    /// The function's top-level scope is set up by the caller, all other
+    /// blocks get a new scope here.
+    fn block(&mut self, ty: Type, depth: u32) -> Block {
+        let mut stmts = Vec::new();
+
+        for _ in 0..self.rng.below(4) {
+            self.statement(depth, &mut stmts);
+        }
+
+        let expr = boxed(self.expression(ty, depth));
+
+        Block { stmts, expr }
+    }
+
+    fn nested_block(&mut self, ty: Type, depth: u32) -> Box<Node<Block>> {
+        self.scopes.push(Vec::new());
+        let block = self.block(ty, depth);
+        self.scopes.pop();
+
+        Box::new(node(block))
+    }
+
+    fn statement(&mut self, depth: u32, stmts: &mut Vec<Node<Statement>>) {
+        if self.rng.one_in(3) {
+            let decl = self.declaration(depth);
+            stmts.push(decl);
+            return;
+        }
+
+        if depth > 0 && self.rng.one_in(5) {
+            self.while_loop(depth - 1, stmts);
+            return;
+        }
+
+        if self.rng.one_in(10) {
+            // The type checker assigns `return` the type of its value, so it
+            // only may be used as a statement
+            self.explicit_return = true;
+            let ret_ty = self.ret_ty;
+            let val = boxed(if ret_ty == Type::Unit {
+                Expression::Unit
+            } else {
+                self.expression(ret_ty, depth)
+            });
+            stmts.push(node(Statement::Expression {
+                val: boxed(Expression::Return { val }),
+            }));
+            return;
+        }
+
+        let val = if self.rng.one_in(2) {
+            let ty = self.random_type();
+            self.expression(ty, depth)
+        } else {
+            self.expression(Type::Unit, depth)
+        };
+
+        // `()` can't be written as a statement
+        if let Expression::Unit = val {
+            return;
+        }
+
+        stmts.push(node(Statement::Expression { val: boxed(val) }));
+    }
+
+    fn declaration(&mut self, depth: u32) -> Node<Statement> {
+        // Maybe shadow a variable from an outer block. The scope table isn't
+        // ordered, so previous usages in this block would refer to the new
+        // variable as well. Keeping the type makes sure they stay well-typed.
+        let outer: Vec<Var> = self.scopes[..self.scopes.len() - 1]
+            .iter()
+            .flat_map(|scope| scope.iter().cloned())
+            .filter(|var| var.assignable && !self.declared_in_current_scope(var.name))
+            .collect();
+
+        let (name, ty) = if !outer.is_empty() && self.rng.one_in(3) {
+            let var = *self.rng.choose(&outer);
+            (var.name, var.ty)
+        } else {
+            (self.fresh_name("v"), self.random_type())
+        };
+
+        let value = boxed(self.expression(ty, depth));
+        self.declare(Var {
+            name,
+            ty,
+            assignable: true,
+        });
+
+        node(Statement::Declaration {
+            binding: Box::new(self.binding(name, ty, true)),
+            value,
+        })
+    }
+
+    fn declared_in_current_scope(&self, name: Ident) -> bool {
+        self.scopes
+            .last()
+            .unwrap()
+            .iter()
+            .any(|var| var.name == name)
+    }
+
+    fn declare(&mut self, var: Var) {
+        self.scopes.last_mut().unwrap().push(var);
+    }
+
+    /// All variables of type `ty` visible at the current position
+    fn visible(&self, ty: Type, assignable_only: bool) -> Vec<Var> {
+        let mut vars: Vec<Var> = Vec::new();
+
+        // Inner declarations shadow outer ones
+        for var in self.scopes.iter().rev().flatten().chain(&self.globals) {
+            if vars.iter().all(|v| v.name != var.name) {
+                vars.push(*var);
+            }
+        }
+
+        vars.retain(|var| var.ty == ty && (var.assignable || !assignable_only));
+        vars
+    }
+
+    // --- Expressions ------------------------------------------------------------
+
+    fn literal(&mut self, ty: Type) -> Expression {
+        let val = match ty {
+            Type::Int => {
+                let i = *self
+                    .rng
+                    .choose(&[0, 1, 2, 7, 42, 255, 65_536, 1 << 32, i64::MAX - 2])
+                    + i64::from(self.rng.below(3));
+
+                // Exercise all the ways to spell an integer
+                let spelling = match self.rng.below(6) {
+                    0 => Some(format!("{:#x}", i)),
+                    1 => Some(format!("0x_{:X}", i)),
+                    2 => Some(format!("{:#o}", i)),
+                    3 => Some(format!("{:#b}", i)),
+                    4 => Some(format!("{}_", i)),
+                    _ => None,
+                };
+
+                Value::Int(i128::from(i), spelling.map(|s| Ident::from_str(&s)))
+            }
+            Type::Bool => Value::Bool(self.rng.one_in(2)),
+            Type::Char => Value::Char(*self.rng.choose(&['a', 'Z', '0', ' ', '\n', '\''])),
+            _ => panic!("no literals of type {}", ty),
+        };
+
+        Expression::Literal { val }
+    }
+
+    /// Generate an expression of type `ty` that's at most `depth` levels deep
+    fn expression(&mut self, ty: Type, depth: u32) -> Expression {
+        if depth == 0 {
+            return self.leaf(ty);
+        }
+
+        let depth = depth - 1;
+
+        match self.rng.below(8) {
+            0 => self.if_expression(ty, depth),
+            1 => self.call(ty, depth),
+            _ => match ty {
+                Type::Int => self.int_expression(depth),
+                Type::Bool => self.bool_expression(depth),
+                Type::Char => self.leaf(ty),
+                Type::Unit => self.unit_expression(depth),
+                Type::U8 | Type::I32 | Type::U64 | Type::F64 => {
+                    panic!("the generator only uses int")
+                }
+                Type::Array(..) => panic!("the generator doesn't use arrays"),
+                Type::Struct(..) => panic!("the generator doesn't use structs"),
+                Type::Str => panic!("the generator only uses string literals in calls"),
+                Type::Err | Type::Infer => panic!("cannot generate an erroneous expression"),
+            },
+        }
+    }
+
+    fn leaf(&mut self, ty: Type) -> Expression {
+        if ty == Type::Unit {
+            return Expression::Unit;
+        }
+
+        let vars = self.visible(ty, false);
+        if !vars.is_empty() && self.rng.one_in(2) {
+            let var = *self.rng.choose(&vars);
+            Expression::Variable {
+                name: node(var.name),
+            }
+        } else {
+            self.literal(ty)
+        }
+    }
+
+    fn infix(&mut self, op: BinOp, operand_ty: Type, depth: u32) -> Expression {
+        Expression::Infix {
+            op,
+            lhs: boxed(group(self.expression(operand_ty, depth))),
+            rhs: boxed(group(self.expression(operand_ty, depth))),
+        }
+    }
+
+    fn int_expression(&mut self, depth: u32) -> Expression {
+        use self::BinOp::*;
+
+        match self.rng.below(3) {
+            0 => self.leaf(Type::Int),
+            1 => Expression::Prefix {
+                op: *self.rng.choose(&[UnOp::Neg, UnOp::Not]),
+                item: boxed(group(self.expression(Type::Int, depth))),
+            },
+            _ => {
+                let op = *self.rng.choose(&[
+                    Add, Sub, Mul, Div, Mod, Pow, Shl, Shr, BitAnd, BitOr, BitXor,
+                ]);
+                self.infix(op, Type::Int, depth)
+            }
+        }
+    }
+
+    fn bool_expression(&mut self, depth: u32) -> Expression {
+        use self::BinOp::*;
+
+        match self.rng.below(4) {
+            0 => self.leaf(Type::Bool),
+            1 => Expression::Prefix {
+                op: UnOp::Not,
+                item: boxed(group(self.expression(Type::Bool, depth))),
+            },
+            2 => {
+                let op = *self.rng.choose(&[And, Or, BitAnd, BitOr, BitXor]);
+                self.infix(op, Type::Bool, depth)
+            }
+            _ => {
+                let op = *self.rng.choose(&[EqEq, Lt, Le, Ne, Ge, Gt]);
+                self.infix(op, Type::Int, depth)
+            }
+        }
+    }
+
+    fn unit_expression(&mut self, depth: u32) -> Expression {
+        match self.rng.below(6) {
+            0 if self.loop_depth > 0 => Expression::Break {
+                label: None,
+                value: None,
+            },
+            1 => Expression::Unit,
+            2 => Expression::Call {
+                func: boxed(Expression::Variable {
+                    name: node(Ident::from_str("print_str")),
+                }),
+                args: vec![node(Expression::Literal {
+                    val: Value::Str(Ident::from_str(*self.rng.choose(&[
+                        "hello\n",
+                        "\t\"quoted\" \\",
+                        "caf\u{e9}\0",
+                    ]))),
+                })],
+            },
+            _ => {
+                let ty = self.random_type();
+                let vars = self.visible(ty, true);
+                if vars.is_empty() {
+                    return Expression::Unit;
+                }
+
+                let lhs = boxed(Expression::Variable {
+                    name: node(self.rng.choose(&vars).name),
+                });
+
+                if ty == Type::Int && self.rng.one_in(2) {
+                    use self::BinOp::*;
+                    let op = *self
+                        .rng
+                        .choose(&[Add, Sub, Mul, Div, Mod, Shl, Shr, BitXor]);
+                    Expression::AssignOp {
+                        op,
+                        lhs,
+                        rhs: boxed(self.expression(Type::Int, depth)),
+                    }
+                } else {
+                    Expression::Assign {
+                        lhs,
+                        rhs: boxed(self.expression(ty, depth)),
+                    }
+                }
+            }
+        }
+    }
+
+    fn if_expression(&mut self, ty: Type, depth: u32) -> Expression {
+        let cond = boxed(self.expression(Type::Bool, depth));
+        let conseq = self.nested_block(ty, depth);
+        let altern = if ty != Type::Unit || self.rng.one_in(2) {
+            Some(self.nested_block(ty, depth))
+        } else {
+            None
+        };
+
+        Expression::If {
+            cond,
+            conseq,
+            altern,
+        }
+    }
+
+    fn call(&mut self, ty: Type, depth: u32) -> Expression {
+        let candidates: Vec<usize> = (0..self.functions.len())
+            .filter(|&i| self.functions[i].ret_ty == ty)
+            .collect();
+
+        if candidates.is_empty() {
+            return self.leaf(ty);
+        }
+
+        let f = *self.rng.choose(&candidates);
+        let name = self.functions[f].name;
+        let arg_types = self.functions[f].args.clone();
+
+        Expression::Call {
+            func: boxed(Expression::Variable { name: node(name) }),
+            args: arg_types
+                .into_iter()
+                .map(|ty| node(self.expression(ty, depth)))
+                .collect(),
+        }
+    }
+
+    /// Generate a while loop driven by a counter
+    ///
+    /// ```ignore
+    /// let i: int = 0;
+    /// while i < N {
+    ///     ...
+    ///     i += 1;
+    /// }
+    /// ```
+    ///
+    /// The counter isn't assignable, so the loop body can't modify or shadow
+    /// it.
+    fn while_loop(&mut self, depth: u32, stmts: &mut Vec<Node<Statement>>) {
+        let counter = self.fresh_name("i");
+        self.declare(Var {
+            name: counter,
+            ty: Type::Int,
+            assignable: false,
+        });
+
+        stmts.push(node(Statement::Declaration {
+            binding: Box::new(self.binding(counter, Type::Int, true)),
+            value: boxed(Expression::Literal {
+                val: Value::Int(0, None),
+            }),
+        }));
+
+        let cond = boxed(Expression::Infix {
+            op: BinOp::Lt,
+            lhs: boxed(Expression::Variable {
+                name: node(counter),
+            }),
+            rhs: boxed(Expression::Literal {
+                val: Value::Int(1 + i128::from(self.rng.below(10)), None),
+            }),
+        });
+
+        self.loop_depth += 1;
+        self.scopes.push(Vec::new());
+        let mut body = self.block(Type::Unit, depth);
+        self.scopes.pop();
+        self.loop_depth -= 1;
+
+        body.stmts.push(node(Statement::Expression {
+            val: boxed(Expression::AssignOp {
+                op: BinOp::Add,
+                lhs: boxed(Expression::Variable {
+                    name: node(counter),
+                }),
+                rhs: boxed(Expression::Literal {
+                    val: Value::Int(1, None),
+                }),
+            }),
+        }));
+
+        stmts.push(node(Statement::Expression {
+            val: boxed(Expression::While {
+                cond,
+                body: Box::new(node(body)),
+                label: None,
+            }),
+        }));
+    }
+}
+
+/// Generate a program from a seed and return its source code
+fn generate(seed: u64) -> String {
+    let program = Generator::new(seed).program();
+    let mut source = Vec::new();
+    PrettyPrinter::print(&program, &mut source);
+
+    String::from_utf8(source).unwrap()
+}
+
+/// Why the compiler didn't produce any assembly
+enum Failure {
+    /// The compiler reported an error. As all generated programs are valid,
+    /// this is either a bug in the front end or in the generator.
+    Rejected(String),
+    /// The compiler panicked
+    Crashed(String),
+}
+
+/// A temporary directory that's removed again when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        // Use a directory per process so concurrent test runs don't overwrite
+        // each other's programs
+        let dir = env::temp_dir().join(format!("rustiny-fuzz-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        TempDir(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+/// Generate a program and compile it to assembly
+fn compile(seed: u64, dir: &TempDir) -> Result<(), Failure> {
+    let source_file = dir.0.join(format!("{}.rs", seed));
+    let asm_file = dir.0.join(format!("{}.s", seed));
+    fs::write(&source_file, generate(seed)).unwrap();
+
+    let output = Command::new(COMPILER)
+        .args(&["--target", "asm", "-o"])
+        .arg(&asm_file)
+        .arg(&source_file)
+        .env("COLORED_OUTPUT", "off")
+        .output()
+        .unwrap();
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let msg = format!(
+        "seed {}:\n{}",
+        seed,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Panics exit with 101, compilation errors with 1
+    if output.status.code() == Some(1) {
+        Err(Failure::Rejected(msg))
+    } else {
+        Err(Failure::Crashed(msg))
+    }
+}
+
+/// Compile programs for all seeds and report the failures `check` cares about
+fn fuzz<F>(name: &str, check: F)
+where
+    F: Fn(Result<(), Failure>) -> Option<String>,
+{
+    let seeds: Vec<u64> = match env::var("RUSTINY_FUZZ_SEED") {
+        Ok(seed) => vec![seed.parse().expect("invalid RUSTINY_FUZZ_SEED")],
+        Err(..) => (0..ITERATIONS).collect(),
+    };
+
+    let dir = TempDir::new(name);
+    let failures: Vec<String> = seeds
+        .into_iter()
+        .filter_map(|seed| check(compile(seed, &dir)))
+        .collect();
+
+    if !failures.is_empty() {
+        panic!("{} failures:\n\n{}", failures.len(), failures.join("\n\n"));
+    }
+}
+
+#[test]
+fn generated_programs_are_accepted() {
+    fuzz("accepted", |result| match result {
+        Err(Failure::Rejected(msg)) => Some(msg),
+        _ => None,
+    });
+}
+
+#[test]
+fn generated_programs_compile() {
+    fuzz("compile", |result| match result {
+        Err(Failure::Crashed(msg)) => Some(msg),
+        _ => None,
+    });
+}
@@ -0,0 +1,7 @@
+const SIZE: int = 3 * (LIMIT - 1);
+const LIMIT: int = 5;
+static TOTAL: int = SIZE * 2;
+
+fn main() {
+    TOTAL = TOTAL + SIZE;
+}
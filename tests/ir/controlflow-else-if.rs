@@ -0,0 +1,20 @@
+fn classify(n: int) -> int {
+    if n < 0 {
+        1
+    } else if n == 0 {
+        2
+    } else if n < 10 {
+        3
+    } else {
+        4
+    }
+}
+
+fn main() {
+    let mut a: int = classify(5);
+    if a == 1 {
+        a = 0;
+    } else if a == 3 {
+        a = 7;
+    }
+}
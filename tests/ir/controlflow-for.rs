@@ -0,0 +1,11 @@
+fn main() {
+    let mut a: int = 0;
+
+    for i in 0..10 {
+        a += i;
+    }
+
+    for i in 0..=a {
+        break;
+    }
+}
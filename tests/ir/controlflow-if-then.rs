@@ -21,7 +21,7 @@
 //   ret i8 %4
 
 fn foo() -> int {
-    let a: int = 2;
+    let mut a: int = 2;
 
     if a == 2 {
         a += 3;
@@ -0,0 +1,12 @@
+fn main() {
+    let mut a: int = 0;
+    let b: int = loop {
+        a += 1;
+        if a == 3 {
+            break a * 2;
+        }
+        if a > 10 {
+            break 0;
+        }
+    };
+}
@@ -0,0 +1,14 @@
+fn main() {
+    let mut a: int = 0;
+
+    'outer: loop {
+        a += 1;
+        while a < 10 {
+            if a == 5 {
+                continue 'outer;
+            }
+            break 'outer;
+        }
+        continue;
+    }
+}
@@ -0,0 +1,13 @@
+fn main() {
+    let c: char = 'x';
+    let kind: int = match c {
+        'a'..='z' => 1,
+        '0' | '1' => 2,
+        _ => 3,
+    };
+    let flag: bool = kind == 1;
+    match flag {
+        true => {}
+        false => {}
+    }
+}
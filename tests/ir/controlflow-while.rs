@@ -20,7 +20,7 @@
 //   br label %while_cond
 
 fn main() {
-    let a: int = 2;
+    let mut a: int = 2;
 
     while a > 0 {
         a -= 1;
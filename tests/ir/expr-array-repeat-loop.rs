@@ -0,0 +1,3 @@
+fn main() {
+    let a: [int; 16] = [7; 16];
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let mut a: [int; 3] = [1, 2, 3];
+    let mut b: [[int; 2]; 2] = [[0; 2]; 2];
+    let i: int = 1;
+    b[i][1] = a[i];
+    a[2] += 4;
+}
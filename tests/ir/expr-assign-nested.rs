@@ -1,7 +1,7 @@
 fn main() {
     let a: int = 2;
     if a == 3 {
-        let a: bool = false;
+        let mut a: bool = false;
         a = true;
     }
 }
\ No newline at end of file
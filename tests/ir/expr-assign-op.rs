@@ -8,6 +8,6 @@
 //  ret void
 
 fn main() {
-    let a: int = 1;
+    let mut a: int = 1;
     a += 2;
 }
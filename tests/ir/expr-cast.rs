@@ -0,0 +1,9 @@
+fn main() {
+    let a: u8 = 200;
+    let b: u8 = a + 100;
+    let c: i32 = a as i32;
+    let d: u64 = c as u64 / 3;
+    let e: int = b as int;
+    let f: i32 = d as i32;
+    let g: u8 = c as u8;
+}
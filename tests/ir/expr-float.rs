@@ -0,0 +1,8 @@
+fn main() {
+    let a: f64 = 1.5;
+    let b: f64 = a * 2.0 - a / 4.0;
+    let c: f64 = -b;
+    let d: bool = a < b;
+    let e: int = c as int;
+    let f: f64 = e as f64;
+}
@@ -0,0 +1,5 @@
+fn main() {
+    let a = 1;
+    let b = a < 2;
+    let c = [a; 2];
+}
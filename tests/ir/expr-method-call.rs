@@ -0,0 +1,15 @@
+struct Point {
+    x: int,
+    y: int,
+}
+
+impl Point {
+    fn sum(self, z: int) -> int {
+        self.x + self.y + z
+    }
+}
+
+fn main() {
+    let p: Point = Point { x: 1, y: 2 };
+    p.sum(3);
+}
@@ -0,0 +1,15 @@
+struct Point {
+    x: int,
+    y: int,
+}
+
+struct Line {
+    from: Point,
+    to: Point,
+}
+
+fn main() {
+    let p: Point = Point { x: 1, y: 2 };
+    let mut l: Line = Line { from: p, to: Point { x: 3, y: 4 } };
+    l.to = l.from;
+}
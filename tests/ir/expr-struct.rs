@@ -0,0 +1,14 @@
+struct Point {
+    x: int,
+    y: int,
+}
+
+fn norm(p: Point) -> int {
+    p.x * p.x + p.y * p.y
+}
+
+fn main() {
+    let mut p: Point = Point { x: 1, y: 2 };
+    p.y = p.x;
+    norm(p);
+}
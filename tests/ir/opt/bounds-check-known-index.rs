@@ -0,0 +1,5 @@
+fn main() {
+    let a: [int; 3] = [1, 2, 3];
+    let i = 1;
+    let b: int = a[i];
+}
@@ -0,0 +1,91 @@
+//! Building binaries (`--target bin`)
+//!
+//! The compiler assembles and links programs with the system's C compiler.
+//! As long as the register allocator doesn't assign machine registers, only
+//! programs that don't need any registers can be linked, all others have to
+//! be rejected with a clear error.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Output};
+
+const COMPILER: &str = env!("CARGO_BIN_EXE_rustiny");
+
+/// A temporary directory that's removed again when dropped
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+/// Write a program to a directory of its own and return the directory
+fn write_program(name: &str, source: &str) -> TempDir {
+    // Use a directory per process so concurrent test runs don't overwrite each
+    // other's binaries
+    let dir = env::temp_dir().join(format!("rustiny-link-{}-{}", name, process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("main.rs"), source).unwrap();
+
+    TempDir(dir)
+}
+
+fn rustiny(args: &[&str], dir: &TempDir) -> Output {
+    Command::new(COMPILER)
+        .args(args)
+        .arg(dir.0.join("main.rs"))
+        .env("COLORED_OUTPUT", "off")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn links_program() {
+    let dir = write_program("empty", "fn main() {}\n");
+    let bin_file = dir.0.join("main");
+
+    let output = rustiny(&["--target", "bin", "-o", bin_file.to_str().unwrap()], &dir);
+    assert!(
+        output.status.success(),
+        "linking failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // A `main` without a return value exits with 0
+    let output = Command::new(&bin_file).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn rejects_virtual_registers() {
+    let dir = write_program(
+        "putchar",
+        "extern fn putchar(c: int) -> int;\n\nfn main() {\n    putchar(65);\n}\n",
+    );
+    let bin_file = dir.0.join("main");
+
+    let output = rustiny(&["--target", "bin", "-o", bin_file.to_str().unwrap()], &dir);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("register allocation isn't implemented yet"),
+        "unexpected error:\n{}",
+        stderr
+    );
+    assert!(!bin_file.exists());
+}
+
+#[test]
+fn requires_output_file() {
+    let dir = write_program("no-output", "fn main() {}\n");
+
+    let output = rustiny(&["--target", "bin"], &dir);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(stderr.contains("use `-o`"), "unexpected error:\n{}", stderr);
+}
@@ -0,0 +1,25 @@
+fn sum(n: int) -> int {
+    let mut a: [int; 4] = [1, 2, 3, 4];
+    let mut b: [[int; 2]; 3] = [[0; 2]; 3];
+    let mut big: [int; 20] = [7; 20];
+    let mut i: int = 0;
+    let mut s: int = 0;
+
+    while i < 4 {
+        s += a[i];
+        i += 1;
+    }
+
+    b[1][1] = s;
+    b[2] = [5, 6];
+    b[0] = b[2];
+    a = [a[3], a[2], a[1], a[0]];
+    a[0] *= 10;
+    big[19] += 1;
+
+    s + b[1][1] + b[0][1] + a[0] + [10, 20, 30][n] + big[19] + big[0]
+}
+
+fn main() -> int {
+    sum(2)
+}
@@ -0,0 +1,18 @@
+//! Comments of every kind are accepted anywhere whitespace is
+
+/// The answer, documented
+const ANSWER: int = 42;
+
+/* A block comment /* with a nested one */ spanning
+   several lines */
+fn identity(x: int) -> int {
+    //! Doc comments inside a body are ignored
+    x /* inline */ + 0
+}
+
+//// Four slashes make a plain comment
+fn main() -> int {
+    /// Not attached to anything either
+    let x = identity(ANSWER);
+    x - 42
+}
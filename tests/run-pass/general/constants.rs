@@ -0,0 +1,20 @@
+const WIDTH: int = 4 * 8;
+const AREA: int = WIDTH * HEIGHT - 2;
+const HEIGHT: int = (WIDTH + 2) / 3;
+const BIG: bool = AREA > 300 && HEIGHT % 2 == 1;
+const BIT: u8 = 1 << 3;
+const MASK: u8 = !BIT;
+const HALF_MAX: u64 = 9223372036854775807;
+const LARGEST: u64 = HALF_MAX * 2 + 1;
+const SIGN: int = if WIDTH < 0 { -1 } else { if WIDTH == 0 { 0 } else { 1 } };
+const HALF: f64 = 2.0 / 4.0;
+static COUNTER: int = AREA - 350;
+
+fn main() -> int {
+    COUNTER += SIGN;
+    if BIG && HALF < 1.0 && LARGEST > 5 {
+        COUNTER + MASK as int
+    } else {
+        0
+    }
+}
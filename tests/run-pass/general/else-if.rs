@@ -0,0 +1,26 @@
+fn grade(score: int) -> int {
+    if score >= 90 {
+        1
+    } else if score >= 80 {
+        2
+    } else if score >= 70 {
+        3
+    } else {
+        4
+    }
+}
+
+fn main() -> int {
+    let mut count = 0;
+    for score in 60..100 {
+        let g = grade(score);
+        if g == 1 {
+            count += 100;
+        } else if g == 2 {
+            count += 10;
+        } else if g == 4 {
+            count += 1;
+        }
+    }
+    count % 256
+}
@@ -0,0 +1,16 @@
+/// Write a single byte to stdout
+extern fn putchar(c: int) -> int;
+
+fn print_digit(d: int) {
+    putchar('0' as int + d);
+}
+
+fn main() -> int {
+    let mut i = 0;
+    while i < 10 {
+        print_digit(i);
+        i += 1;
+    }
+
+    putchar('\n' as int) - 10
+}
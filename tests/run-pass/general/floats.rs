@@ -0,0 +1,29 @@
+// Approximate Pi using the Leibniz series
+const STEPS: int = 1000;
+const HALF: f64 = 0.5;
+static NEG: f64 = -2.25;
+
+fn term(k: int, sign: f64) -> f64 {
+    sign * 4.0 / (2.0 * k as f64 + 1.0)
+}
+
+fn main() -> int {
+    let mut pi: f64 = 0.0;
+    let mut k: int = 0;
+    let mut sign: f64 = 1.0;
+
+    while k < STEPS {
+        pi += term(k, sign);
+        sign = -sign;
+        k += 1;
+    }
+
+    let nan: f64 = 0.0 / 0.0;
+    let trunc: int = NEG as int;
+
+    if pi > 3.14 && pi < 3.15 && nan != nan && !(nan == nan) && !(nan < 1.0) && trunc == -2 && HALF * 4.0 == 2.0 {
+        (pi * 10.0) as int
+    } else {
+        0
+    }
+}
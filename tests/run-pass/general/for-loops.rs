@@ -0,0 +1,44 @@
+fn sum(n: int) -> int {
+    let mut total: int = 0;
+    for i in 1..=n {
+        total += i;
+    }
+    total
+}
+
+fn main() -> int {
+    let mut squares: [int; 5] = [0; 5];
+    for i in 0..5 {
+        squares[i] = i * i;
+    }
+
+    // The range is evaluated once and the loop variable shadows `i`
+    let i: int = 3;
+    let mut count: int = 0;
+    for i in i..i * 2 {
+        count += 1;
+        if i == 4 {
+            break;
+        }
+    }
+
+    // Empty ranges don't run the body
+    for j in 5..5 {
+        count += 100;
+    }
+    for j in 5..=4 {
+        count += 100;
+    }
+
+    // The largest int is a valid inclusive bound
+    let mut last: int = 0;
+    for j in 9223372036854775806..=9223372036854775807 {
+        last = j;
+    }
+
+    if last == 9223372036854775807 && i == 3 {
+        sum(10) + squares[4] + count
+    } else {
+        0
+    }
+}
@@ -0,0 +1,26 @@
+struct Point {
+    x: int,
+    y: int,
+}
+
+fn square(n: int) -> int {
+    n * n
+}
+
+fn main() -> int {
+    let a = square(3);
+    let flag = a > 5;
+    let small = 200 as u8;
+    let half = 2.5;
+    let point = Point { x: a, y: 4 };
+    let values = [1, 2, 3];
+    let counter = loop {
+        break values[2];
+    };
+
+    if flag && half > 2.0 {
+        point.x + point.y + values[0] + counter + small as int
+    } else {
+        0
+    }
+}
@@ -0,0 +1,10 @@
+fn main() -> int {
+    let a = 0xFF + 0o17 + 0b1010 + 1_000_000;
+    let b: u8 = 0xf_f;
+    let c = -0x10;
+    match a {
+        0x0..=0xF => 1,
+        -0b1 => 2,
+        _ => a + (b as int) + c - 1_015_000 - 255,
+    }
+}
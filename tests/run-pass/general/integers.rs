@@ -0,0 +1,18 @@
+static MIN: int = -9223372036854775808;
+const BIG: int = 4294967296;
+const NEG: int = -7;
+
+fn main() -> int {
+    let max: int = 9223372036854775807;
+    let a: int = BIG * 2 + 1;
+    let b: int = NEG / 2;
+    let c: int = NEG % 2;
+    let d: int = -9223372036854775808;
+    let e: int = NEG >> 1;
+
+    if max + 1 == MIN && d == MIN && a > BIG && b == -3 && c == -1 && e == -4 && NEG < 0 {
+        a / BIG
+    } else {
+        0
+    }
+}
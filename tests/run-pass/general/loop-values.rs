@@ -0,0 +1,37 @@
+fn first_multiple(n: int, of: int) -> int {
+    let mut i: int = n;
+    loop {
+        if i % of == 0 {
+            break i;
+        }
+        i += 1;
+    }
+}
+
+fn find(limit: int) -> int {
+    'search: loop {
+        for i in 1..limit {
+            if i * i > 50 {
+                break 'search i;
+            }
+        }
+        break -1;
+    }
+}
+
+fn main() -> int {
+    let x: int = loop {
+        break 42;
+    };
+    let done: bool = loop {
+        if x > 40 {
+            break true;
+        }
+        break false;
+    };
+    if done {
+        x + first_multiple(10, 7) + find(100) + find(5)
+    } else {
+        0
+    }
+}
@@ -0,0 +1,41 @@
+fn main() -> int {
+    let mut found: int = 0;
+    let mut n: int = 0;
+    'outer: for i in 2..50 {
+        for j in 2..i {
+            if i % j == 0 {
+                continue 'outer;
+            }
+        }
+        n += 1;
+        if n == 10 {
+            found = i;
+            break 'outer;
+        }
+    }
+
+    let mut k: int = 0;
+    loop {
+        k += 1;
+        if k < 5 {
+            continue;
+        }
+        break;
+        k += 100;
+    }
+
+    let mut w: int = 0;
+    let mut evens: int = 0;
+    'a: while w < 10 {
+        w += 1;
+        loop {
+            if w % 2 == 1 {
+                continue 'a;
+            }
+            break;
+        }
+        evens += 1;
+    }
+
+    found + k + evens
+}
@@ -0,0 +1,40 @@
+fn classify(c: char) -> int {
+    match c {
+        'a'..='z' => 1,
+        'A'..='Z' => 2,
+        '0'..='9' | '_' => 3,
+        _ => 0,
+    }
+}
+
+fn digit(n: int) -> int {
+    match n {
+        0 => 10,
+        1 | 2 => 20,
+        3 => 30,
+        4 => { 40 }
+        5 => 50,
+        _ => 0
+    }
+}
+
+fn sign(n: i32) -> int {
+    match n {
+        -100..=-1 => 1,
+        0 => 2,
+        _ => 3,
+    }
+}
+
+fn main() -> int {
+    let b = true;
+    let x = match b {
+        true => 1,
+        false => 2,
+    };
+    let mut r = classify('q') + classify('Q') * 10 + classify('_') * 100;
+    r = r + digit(2) + digit(4) + digit(9) + x;
+    r = r + sign(-5) + sign(0) - sign(-101);
+    match r { 0 => {} _ => { r = r + 1; } }
+    return r % 256;
+}
@@ -0,0 +1,32 @@
+struct Counter {
+    count: int,
+    step: int,
+}
+
+impl Counter {
+    fn next(self) -> int {
+        self.count + self.step
+    }
+
+    fn scaled(mut self, factor: int) -> int {
+        self.step = self.step * factor;
+        self.next()
+    }
+}
+
+struct Pair {
+    left: Counter,
+    right: Counter,
+}
+
+impl Pair {
+    fn sum(self) -> int {
+        self.left.next() + self.right.scaled(2)
+    }
+}
+
+fn main() -> int {
+    let c: Counter = Counter { count: 1, step: 2 };
+    let p: Pair = Pair { left: c, right: Counter { count: 10, step: 5 } };
+    p.sum() + c.scaled(3) + c.step
+}
@@ -0,0 +1,27 @@
+fn print_digit(d: int) {
+    match d {
+        0 => print_str("zero"),
+        1 => print_str("one"),
+        2 => print_str("two"),
+        _ => print_str("many"),
+    }
+}
+
+fn main() -> int {
+    print_str("Hello, \"world\"!\n");
+    print_str("tab:\t| backslash: \\ | hex: \x41\x42 | unicode: \u{e9}\u{1F600}\n");
+
+    let mut i = 0;
+    while i < 4 {
+        print_digit(i);
+        if i < 3 {
+            print_str(", ");
+        }
+        i += 1;
+    }
+    print_str("\n");
+
+    print_str("multi-line
+string\n");
+    0
+}
@@ -0,0 +1,27 @@
+fn main() -> int {
+    let x: int = 1;
+    let mut sum: int = 0;
+
+    if true {
+        // `x` still refers to the outer variable until it's shadowed
+        let y: int = x;
+        sum += x;
+        let x: bool = true;
+        if x {
+            sum += y * 10;
+        }
+    }
+
+    let mut flag: bool = false;
+    while !flag {
+        flag = true;
+        let flag: int = 2;
+        sum += flag * 100;
+    }
+
+    if flag {
+        x + sum
+    } else {
+        0
+    }
+}
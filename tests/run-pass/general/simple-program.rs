@@ -1,8 +1,8 @@
 const CONST: int = 0;
 static STATIC: int = 0;
 
-fn mul(a: int, b: int) -> int {
-    let result: int = 0;
+fn mul(mut a: int, mut b: int) -> int {
+    let mut result: int = 0;
 
     while b > 0 {
         result += if true { 1 } else { 0 };
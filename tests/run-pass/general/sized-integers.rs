@@ -0,0 +1,26 @@
+static MASK: u8 = 255;
+const SMALL: i32 = -2147483648;
+
+fn widen(x: u8) -> u64 {
+    x as u64 * 1000
+}
+
+fn main() -> int {
+    let a: u8 = 200;
+    let b: u8 = a + 100;
+    let c: i32 = 2147483647;
+    let d: i32 = c + 1;
+    let e: u64 = -1 as u64;
+    let f: u64 = e / 2;
+    let g: u8 = MASK >> 4;
+    let h: int = 300 as u8 as int;
+    let i: i32 = -7;
+    let j: char = 97 as u8 as char;
+    let k: u64 = 0xFFFF_FFFF_FFFF_FFFF;
+
+    if e > 5 as u64 && k == e && d == SMALL && f == 9223372036854775807 as u64 && g == 15 && i % 2 == -1 && j as int == 97 && widen(b) == 44000 {
+        b as int + h
+    } else {
+        0
+    }
+}
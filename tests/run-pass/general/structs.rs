@@ -0,0 +1,29 @@
+struct Point {
+    x: int,
+    y: int,
+}
+
+struct Line {
+    from: Point,
+    to: Point,
+    tags: [int; 2],
+}
+
+fn length2(mut l: Line) -> int {
+    let dx: int = l.to.x - l.from.x;
+    let dy: int = l.to.y - l.from.y;
+    l.from.x = 100;
+    dx * dx + dy * dy
+}
+
+fn main() -> int {
+    let mut p: Point = Point { y: 2, x: 1 };
+    let mut l: Line = Line { from: p, to: Point { x: 4, y: 6 }, tags: [7, 8] };
+    p.x = 50;
+    l.tags[1] += l.from.x;
+    let mut d: int = length2(l);
+    if (Point { x: 1, y: 1 }).x == 1 {
+        d += l.tags[1];
+    }
+    d + l.from.x + p.x
+}
@@ -0,0 +1,15 @@
+static COUNTER: int = 0;
+
+fn count_to(n: int) {
+    while true {
+        if COUNTER == n {
+            return;
+        }
+        COUNTER += 1;
+    }
+}
+
+fn main() -> int {
+    count_to(3);
+    COUNTER
+}
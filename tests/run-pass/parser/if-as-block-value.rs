@@ -0,0 +1,12 @@
+fn sign(a: int) -> int {
+    let zero: int = 0;
+    if a < zero {
+        -1
+    } else {
+        1
+    }
+}
+
+fn main() {
+    sign(2);
+}
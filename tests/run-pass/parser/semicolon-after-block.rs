@@ -0,0 +1,11 @@
+fn main() {
+    let mut a: int = 0;
+    while a < 3 {
+        a += 1;
+    };
+    let mut b: int = a;
+    if b == 3 {
+        b = 0;
+    };
+    let c: int = b;
+}
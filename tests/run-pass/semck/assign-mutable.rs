@@ -0,0 +1,28 @@
+static COUNTER: int = 0;
+
+struct Point {
+    x: int,
+    y: int,
+}
+
+impl Point {
+    fn moved_x(mut self, dx: int) -> int {
+        self.x += dx;
+        self.x
+    }
+}
+
+fn bump(mut n: int) -> int {
+    n += 1;
+    COUNTER = COUNTER + n;
+    n
+}
+
+fn main() {
+    let mut a = [1, 2];
+    a[0] = bump(a[1]);
+
+    let mut p = Point { x: 1, y: 2 };
+    p.y = 3;
+    p.x = p.moved_x(4);
+}
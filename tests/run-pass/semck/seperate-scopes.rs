@@ -1,5 +1,5 @@
 fn main() {
-    let main: int = 0;
+    let mut main: int = 0;
     main += 2;
 
     main();
@@ -1,6 +1,6 @@
 fn main() {
-    let a: bool = false;
-    let b: int = 0;
+    let mut a: bool = false;
+    let mut b: int = 0;
 
     a &= true;
     b &= 2;
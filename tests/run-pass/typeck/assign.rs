@@ -1,4 +1,4 @@
 fn main() {
-    let a: int = 0;
+    let mut a: int = 0;
     a = 2
 }
\ No newline at end of file
@@ -1,10 +1,10 @@
 fn foo() {
-    let a: int = 2;
+    let mut a: int = 2;
     a += 4;
 }
 
 fn bar() {
-    let a: bool = false;
+    let mut a: bool = false;
     a |= true;
 }
 
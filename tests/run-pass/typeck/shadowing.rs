@@ -1,7 +1,7 @@
 fn main() {
-    let a: bool = false;
+    let mut a: bool = false;
     if a {
-        let a: int = 3;
+        let mut a: int = 3;
         a += 2;
     }
 
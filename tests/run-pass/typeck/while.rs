@@ -1,5 +1,5 @@
 fn main() {
-    let a: int = 10;
+    let mut a: int = 10;
 
     while a > 0 {
         a -= 1;